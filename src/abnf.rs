@@ -0,0 +1,148 @@
+//! Index of ABNF rule definitions.
+//!
+//! Locates rules defined like `name = elements` so that references to them
+//! can be followed to their definition. The detection is heuristic, as
+//! grammars are embedded in free-form text.
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::types::LineNumber;
+
+/// Matches an indented ABNF rule definition, capturing the rule name.
+///
+/// Incremental alternatives (`name =/ elements`) are not definitions.
+static ABNF_DEFINITION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s+([A-Za-z][A-Za-z0-9-]*)\s*=(?:[^/]|$)")
+        .expect("Invalid ABNF definition regex")
+});
+
+/// Matches a name that may refer to an ABNF rule.
+static ABNF_RULE_NAME_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new("[A-Za-z][A-Za-z0-9-]*").expect("Invalid ABNF rule name regex")
+});
+
+/// Index mapping ABNF rule names to their definition lines.
+#[derive(Debug, Default)]
+pub struct AbnfIndex
+{
+    /// Line of the first definition of each rule, keyed by the lowercased
+    /// name since rule names are case-insensitive.
+    definitions: HashMap<Box<str>, LineNumber>,
+}
+
+impl AbnfIndex
+{
+    /// Builds the index from the document content.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The content of the document
+    ///
+    /// # Returns
+    ///
+    /// The index of the rules defined in the content.
+    #[must_use]
+    pub fn new(content: &str) -> Self
+    {
+        let mut definitions = HashMap::new();
+
+        for (line_num, line) in content.lines().enumerate()
+        {
+            if let Some(caps) = ABNF_DEFINITION_REGEX.captures(line)
+            {
+                definitions
+                    .entry(caps[1].to_ascii_lowercase().into_boxed_str())
+                    .or_insert(line_num);
+            }
+        }
+
+        Self { definitions }
+    }
+
+    /// Gets the definition line of a rule.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule_name` - The name of the rule, in any case
+    ///
+    /// # Returns
+    ///
+    /// The line the rule is defined on, or `None` if it isn't defined.
+    #[must_use]
+    pub fn definition(&self, rule_name: &str) -> Option<LineNumber>
+    {
+        self.definitions
+            .get(rule_name.to_ascii_lowercase().as_str())
+            .copied()
+    }
+
+    /// Finds the definition of the first rule referenced on a line.
+    ///
+    /// Rules defined on the line itself are skipped, so a definition leads
+    /// to the rules it is made of.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The text of the line
+    /// * `line_num` - The number of the line
+    ///
+    /// # Returns
+    ///
+    /// The definition line of the referenced rule, or `None` if the line
+    /// references no defined rule.
+    #[must_use]
+    pub fn find_referenced_definition(
+        &self,
+        line: &str,
+        line_num: LineNumber,
+    ) -> Option<LineNumber>
+    {
+        ABNF_RULE_NAME_REGEX
+            .find_iter(line)
+            .filter_map(|name| self.definition(name.as_str()))
+            .find(|&definition| definition != line_num)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// Grammar excerpt in the layout used by RFCs.
+    const GRAMMAR: &str = "\
+Section 2 defines the grammar.
+
+     URI         = scheme \":\" hier-part
+     scheme      = ALPHA *( ALPHA / DIGIT )
+     hier-part   = \"//\" path
+     scheme      =/ \"urn\"
+     path        = *( \"/\" segment )
+";
+
+    #[test]
+    fn test_definition_lines()
+    {
+        let index = AbnfIndex::new(GRAMMAR);
+
+        assert_eq!(index.definition("URI"), Some(2));
+        // Incremental alternatives don't override the definition
+        assert_eq!(index.definition("Scheme"), Some(3));
+        assert_eq!(index.definition("segment"), None);
+        // Prose isn't a definition
+        assert_eq!(index.definition("Section"), None);
+    }
+
+    #[test]
+    fn test_find_referenced_definition()
+    {
+        let index = AbnfIndex::new(GRAMMAR);
+        let uri_line = GRAMMAR.lines().nth(2).unwrap_or_default();
+
+        // Skips `URI` itself, leading to `scheme`
+        assert_eq!(index.find_referenced_definition(uri_line, 2), Some(3));
+        assert_eq!(index.find_referenced_definition("no rules here", 0), None);
+    }
+}
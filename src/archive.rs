@@ -0,0 +1,577 @@
+//! Tar archives of flat file collections.
+//!
+//! Writes and reads the `ustar` format just far enough to move the cache
+//! between machines: regular files without directories, optionally gzipped.
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path};
+
+use anyhow::{Context as _, Result, bail, ensure};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+/// Size of tar headers and of the blocks the data is padded to.
+const BLOCK_SIZE: usize = 512;
+/// [`BLOCK_SIZE`] for arithmetic on file sizes.
+const BLOCK_SIZE_U64: u64 = BLOCK_SIZE as u64;
+/// Maximum length of a file name in the header, excluding the terminator.
+const MAX_NAME_LEN: usize = 99;
+/// Magic bytes starting gzip streams.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Type flag of regular files.
+const REGULAR_FILE: u8 = b'0';
+/// Type flag of regular files written by pre-POSIX archivers.
+const LEGACY_REGULAR_FILE: u8 = 0;
+
+/// A file stored in an archive, without its content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry
+{
+    /// The name of the file, without directories.
+    pub name: Box<str>,
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// When the file was last modified, in seconds since the Unix epoch.
+    pub modified: u64,
+}
+
+/// Where an [`ArchiveWriter`] writes the tar stream to.
+enum TarSink<W: Write>
+{
+    /// Written as is.
+    Plain(BufWriter<W>),
+    /// Compressed with gzip.
+    Gzipped(GzEncoder<BufWriter<W>>),
+}
+
+impl<W: Write> Write for TarSink<W>
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize>
+    {
+        match self
+        {
+            Self::Plain(writer) => writer.write(buf),
+            Self::Gzipped(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()>
+    {
+        match self
+        {
+            Self::Plain(writer) => writer.flush(),
+            Self::Gzipped(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// Writes files to a tar archive one at a time, without holding them in
+/// memory.
+pub struct ArchiveWriter<W: Write>
+{
+    /// The stream the entries are written to.
+    sink: TarSink<W>,
+}
+
+impl<W: Write> ArchiveWriter<W>
+{
+    /// Creates a writer of an empty archive.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the archive to
+    /// * `is_gzipped` - Whether to compress the archive with gzip
+    ///
+    /// # Returns
+    ///
+    /// A new `ArchiveWriter`.
+    pub fn new(writer: W, is_gzipped: bool) -> Self
+    {
+        let writer = BufWriter::new(writer);
+        let sink = if is_gzipped
+        {
+            TarSink::Gzipped(GzEncoder::new(writer, Compression::default()))
+        }
+        else
+        {
+            TarSink::Plain(writer)
+        };
+
+        Self { sink }
+    }
+
+    /// Appends a file to the archive.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The file to store
+    /// * `content` - The reader of the content, giving exactly `entry.size`
+    ///   bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file name doesn't fit the header, the content
+    /// is shorter than the size or writing fails.
+    pub fn append(
+        &mut self,
+        entry: &ArchiveEntry,
+        content: impl Read,
+    ) -> Result<()>
+    {
+        let header = build_header(entry)?;
+        let padding = usize::try_from(padding(entry.size)).unwrap_or_default();
+
+        self.sink
+            .write_all(&header)
+            .with_context(|| format!("Failed to archive {}", entry.name))?;
+
+        let copied = io::copy(&mut content.take(entry.size), &mut self.sink)
+            .with_context(|| format!("Failed to archive {}", entry.name))?;
+        // The header is already written, it can't be fixed anymore
+        ensure!(
+            copied == entry.size,
+            "{} shrank while it was archived",
+            entry.name
+        );
+
+        self.sink
+            .write_all(&[0; BLOCK_SIZE][..padding])
+            .with_context(|| format!("Failed to archive {}", entry.name))
+    }
+
+    /// Ends the archive and flushes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn finish(mut self) -> Result<()>
+    {
+        // The end of the archive is marked by two zero blocks
+        self.sink
+            .write_all(&[0; BLOCK_SIZE * 2])
+            .context("Failed to write the archive")?;
+
+        match self.sink
+        {
+            TarSink::Plain(mut writer) => writer.flush(),
+            TarSink::Gzipped(encoder) => encoder
+                .finish()
+                .and_then(|mut writer| writer.flush()),
+        }
+        .context("Failed to finish the archive")
+    }
+}
+
+/// Reads the files of a tar archive one at a time.
+///
+/// Gzipped archives are detected and decompressed.
+///
+/// # Arguments
+///
+/// * `reader` - The reader to read the archive from
+/// * `extract` - Called with each file and the reader of its content, which
+///   needn't be read to the end
+///
+/// # Returns
+///
+/// The number of files in the archive.
+///
+/// # Errors
+///
+/// Returns an error if the archive is malformed or contains anything but
+/// regular files with plain names, like paths escaping the target
+/// directory, or if `extract` fails.
+pub fn read_archive(
+    reader: impl Read,
+    extract: impl FnMut(&ArchiveEntry, &mut dyn Read) -> Result<()>,
+) -> Result<usize>
+{
+    let mut reader = BufReader::new(reader);
+    let mut magic = [0; GZIP_MAGIC.len()];
+    let magic_len = reader
+        .read(&mut magic)
+        .context("Failed to read the archive")?;
+    // Put the peeked bytes back in front
+    let reader = magic
+        .get(..magic_len)
+        .unwrap_or_default()
+        .chain(reader);
+
+    if magic == GZIP_MAGIC
+    {
+        read_tar(GzDecoder::new(reader), extract)
+    }
+    else
+    {
+        read_tar(reader, extract)
+    }
+}
+
+/// Reads the entries of an uncompressed tar stream.
+///
+/// # Arguments
+///
+/// * `reader` - The reader to read the stream from
+/// * `extract` - Called with each file and the reader of its content
+///
+/// # Returns
+///
+/// The number of files in the stream.
+///
+/// # Errors
+///
+/// Returns an error if the stream is malformed or contains anything but
+/// regular files with plain names, or if `extract` fails.
+fn read_tar(
+    mut reader: impl Read,
+    mut extract: impl FnMut(&ArchiveEntry, &mut dyn Read) -> Result<()>,
+) -> Result<usize>
+{
+    let mut count: usize = 0;
+    let mut header = [0; BLOCK_SIZE];
+
+    loop
+    {
+        reader
+            .read_exact(&mut header)
+            .context("Archive ended unexpectedly")?;
+
+        if header.iter().all(|&byte| byte == 0)
+        {
+            return Ok(count);
+        }
+
+        let entry = parse_header(&header)?;
+
+        let mut content = reader.by_ref().take(entry.size);
+        extract(&entry, &mut content)?;
+        // Skip what wasn't read so the next header is found
+        io::copy(&mut content, &mut io::sink())
+            .with_context(|| format!("Failed to read {}", entry.name))?;
+        ensure!(
+            content.limit() == 0,
+            "Archive ended in the middle of {}",
+            entry.name
+        );
+
+        io::copy(
+            &mut reader.by_ref().take(padding(entry.size)),
+            &mut io::sink(),
+        )
+        .context("Failed to read the archive")?;
+
+        count = count.saturating_add(1);
+    }
+}
+
+/// Builds the `ustar` header of an entry.
+///
+/// # Arguments
+///
+/// * `entry` - The entry to describe
+///
+/// # Returns
+///
+/// The header block.
+///
+/// # Errors
+///
+/// Returns an error if the name is too long or not a plain file name.
+fn build_header(entry: &ArchiveEntry) -> Result<[u8; BLOCK_SIZE]>
+{
+    ensure!(
+        entry.name.len() <= MAX_NAME_LEN,
+        "File name {} is too long to archive",
+        entry.name
+    );
+    validate_name(&entry.name)?;
+
+    let mut header = [0; BLOCK_SIZE];
+    let mut put = |offset: usize, bytes: &[u8]| {
+        if let Some(field) =
+            header.get_mut(offset..offset.saturating_add(bytes.len()))
+        {
+            field.copy_from_slice(bytes);
+        }
+    };
+
+    put(0, entry.name.as_bytes());
+    put(100, b"0000644\0");
+    put(108, b"0000000\0");
+    put(116, b"0000000\0");
+    put(124, format!("{:011o}\0", entry.size).as_bytes());
+    put(136, format!("{:011o}\0", entry.modified).as_bytes());
+    // Summed as spaces while computing the checksum
+    put(148, b"        ");
+    put(156, &[REGULAR_FILE]);
+    put(257, b"ustar\0");
+    put(263, b"00");
+
+    let checksum = checksum(&header);
+    header
+        .get_mut(148..156)
+        .unwrap_or_default()
+        .copy_from_slice(format!("{checksum:06o}\0 ").as_bytes());
+
+    Ok(header)
+}
+
+/// Parses a tar header, validating it for extraction.
+///
+/// # Arguments
+///
+/// * `header` - The header block
+///
+/// # Returns
+///
+/// The file described by the header.
+///
+/// # Errors
+///
+/// Returns an error if the checksum doesn't match, the entry isn't a
+/// regular file or its name isn't a plain file name.
+fn parse_header(header: &[u8; BLOCK_SIZE]) -> Result<ArchiveEntry>
+{
+    let field = |start: usize, end: usize| {
+        let bytes = header.get(start..end).unwrap_or_default();
+        let len = bytes
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(bytes.len());
+
+        bytes.get(..len).unwrap_or_default()
+    };
+    let octal = |start: usize, end: usize| {
+        let text = String::from_utf8_lossy(field(start, end));
+        u64::from_str_radix(text.trim(), 8)
+            .with_context(|| format!("Invalid number {text:?} in archive"))
+    };
+
+    ensure!(
+        octal(148, 156)? == checksum(header),
+        "Archive header checksum mismatch"
+    );
+
+    let name = std::str::from_utf8(field(0, 100))
+        .context("Archived file name is not valid UTF-8")?;
+    validate_name(name)?;
+
+    let type_flag = header.get(156).copied().unwrap_or_default();
+    if type_flag != REGULAR_FILE && type_flag != LEGACY_REGULAR_FILE
+    {
+        bail!("Archived {name} is not a regular file");
+    }
+
+    Ok(ArchiveEntry {
+        name: name.into(),
+        size: octal(124, 136)?,
+        modified: octal(136, 148)?,
+    })
+}
+
+/// Checks that a name refers to a file directly inside the target
+/// directory.
+///
+/// # Arguments
+///
+/// * `name` - The name to check
+///
+/// # Errors
+///
+/// Returns an error for empty names, absolute paths, names with directory
+/// components like `..` and names with separators of any platform.
+fn validate_name(name: &str) -> Result<()>
+{
+    let mut components = Path::new(name).components();
+    let is_plain = matches!(components.next(), Some(Component::Normal(_))) &&
+        components.next().is_none() &&
+        !name.contains(['/', '\\']);
+
+    ensure!(is_plain, "Refusing to extract {name:?} outside the cache");
+
+    Ok(())
+}
+
+/// Computes the checksum of a header, its checksum field read as spaces.
+///
+/// # Arguments
+///
+/// * `header` - The header block
+///
+/// # Returns
+///
+/// The sum of the header bytes.
+fn checksum(header: &[u8; BLOCK_SIZE]) -> u64
+{
+    header
+        .iter()
+        .enumerate()
+        .map(|(index, &byte)| {
+            if (148..156).contains(&index)
+            {
+                u64::from(b' ')
+            }
+            else
+            {
+                u64::from(byte)
+            }
+        })
+        .sum()
+}
+
+/// Number of zero bytes padding data to the next block boundary.
+///
+/// # Arguments
+///
+/// * `len` - The length of the data
+///
+/// # Returns
+///
+/// The number of padding bytes.
+const fn padding(len: u64) -> u64
+{
+    // The distance to the next multiple, `-len mod BLOCK_SIZE`
+    len.wrapping_neg() % BLOCK_SIZE_U64
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// Files stored across the tests, along with their content.
+    fn entries() -> Vec<(ArchiveEntry, Vec<u8>)>
+    {
+        let files = [
+            (
+                "rfc2119.txt",
+                b"Key words for use in RFCs".to_vec(),
+                1_700_000_000,
+            ),
+            ("rfc-index.txt", vec![b'x'; BLOCK_SIZE], 0),
+        ];
+
+        files
+            .into_iter()
+            .map(|(name, content, modified)| {
+                let entry = ArchiveEntry {
+                    name: name.into(),
+                    size: content.len() as u64,
+                    modified,
+                };
+                (entry, content)
+            })
+            .collect()
+    }
+
+    /// Writes the files to an archive.
+    fn write_entries(
+        files: &[(ArchiveEntry, Vec<u8>)],
+        is_gzipped: bool,
+    ) -> Result<Vec<u8>>
+    {
+        let mut archive = Vec::new();
+        let mut writer = ArchiveWriter::new(&mut archive, is_gzipped);
+        for (entry, content) in files
+        {
+            writer.append(entry, content.as_slice())?;
+        }
+        writer.finish()?;
+
+        Ok(archive)
+    }
+
+    /// Reads all the files of an archive.
+    fn read_entries(archive: &[u8]) -> Result<Vec<(ArchiveEntry, Vec<u8>)>>
+    {
+        let mut files = Vec::new();
+        read_archive(archive, |entry, content| {
+            let mut bytes = Vec::new();
+            content.read_to_end(&mut bytes)?;
+            files.push((entry.clone(), bytes));
+            Ok(())
+        })?;
+
+        Ok(files)
+    }
+
+    #[test]
+    fn test_archive_round_trip() -> Result<()>
+    {
+        for is_gzipped in [false, true]
+        {
+            let archive = write_entries(&entries(), is_gzipped)?;
+
+            assert_eq!(archive.starts_with(&GZIP_MAGIC), is_gzipped);
+            assert_eq!(read_entries(&archive)?, entries());
+
+            // Unread content is skipped
+            let mut names = Vec::new();
+            let count = read_archive(archive.as_slice(), |entry, _| {
+                names.push(entry.name.clone());
+                Ok(())
+            })?;
+            assert_eq!(count, 2);
+            assert_eq!(
+                names,
+                [Box::from("rfc2119.txt"), "rfc-index.txt".into()]
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_truncated_content_is_rejected() -> Result<()>
+    {
+        let (mut entry, content) = entries().remove(0);
+        entry.size = entry.size.saturating_add(1);
+
+        let mut writer = ArchiveWriter::new(Vec::new(), false);
+        let error = writer
+            .append(&entry, content.as_slice())
+            .expect_err("short content archived");
+        assert!(error.to_string().contains("shrank"));
+
+        let archive = write_entries(&entries(), false)?;
+        let truncated = archive
+            .get(..BLOCK_SIZE + 10)
+            .unwrap_or_default();
+        assert!(read_entries(truncated).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_path_traversal_is_rejected() -> Result<()>
+    {
+        for name in ["../evil.txt", "/etc/passwd", "dir/rfc1.txt", "..", ""]
+        {
+            assert!(validate_name(name).is_err(), "{name:?} was accepted");
+        }
+
+        // Forge a header with a traversing name, bypassing the writer checks
+        let mut archive = write_entries(&entries(), false)?;
+        archive
+            .get_mut(..6)
+            .unwrap_or_default()
+            .copy_from_slice(b"../rfc");
+        let mut header = [0; BLOCK_SIZE];
+        header.copy_from_slice(archive.get(..BLOCK_SIZE).unwrap_or_default());
+        let checksum = checksum(&header);
+        archive
+            .get_mut(148..156)
+            .unwrap_or_default()
+            .copy_from_slice(format!("{checksum:06o}\0 ").as_bytes());
+
+        let error = read_entries(&archive).expect_err("traversal accepted");
+        assert!(
+            error
+                .to_string()
+                .contains("outside the cache")
+        );
+
+        Ok(())
+    }
+}
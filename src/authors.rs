@@ -0,0 +1,304 @@
+//! Parsing of the authors' addresses section.
+//!
+//! The section at the end of RFCs lists a block per author: the name, then
+//! the organization and the postal address, with contact lines like
+//! `Email: ...` either following right away or in a paragraph of their own.
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+/// Matches the heading of the section, like `Authors' Addresses`,
+/// `Author's Address` or `9. Authors' Addresses`.
+static ADDRESSES_HEADING_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:[0-9A-Z]+\.?\s+)?authors?'?s?\s+address(?:es)?\s*$")
+        .expect("Invalid addresses heading regex")
+});
+
+/// Names of the contact lines following the postal address, lowercased.
+const CONTACT_FIELDS: [&str; 8] = [
+    "phone", "tel", "fax", "email", "e-mail", "uri", "url", "mobile",
+];
+
+/// An author of an RFC with the contact details found for them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuthorAddress
+{
+    /// The name of the author.
+    pub name: Box<str>,
+    /// The organization of the author, the line after the name.
+    pub organization: Option<Box<str>>,
+    /// The email address of the author.
+    pub email: Option<Box<str>>,
+}
+
+impl AuthorAddress
+{
+    /// Creates an author from the first paragraph of their block.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The trimmed lines of the paragraph, starting with the name
+    ///
+    /// # Returns
+    ///
+    /// The author, or `None` if the paragraph is empty.
+    fn from_lines(lines: &[&str]) -> Option<Self>
+    {
+        let (&name, rest) = lines.split_first()?;
+        let organization = rest
+            .first()
+            .filter(|line| contact_field(line).is_none())
+            .map(|&line| Box::from(line));
+
+        let mut author = Self {
+            name: name.into(),
+            organization,
+            email: None,
+        };
+        author.add_contacts(rest);
+
+        Some(author)
+    }
+
+    /// Takes the email address from the contact lines, keeping the first
+    /// one found.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The trimmed lines to look for contact lines in
+    fn add_contacts(&mut self, lines: &[&str])
+    {
+        if self.email.is_some()
+        {
+            return;
+        }
+
+        self.email = lines
+            .iter()
+            .filter_map(|line| contact_field(line))
+            .find(|(field, _)| *field == "email" || *field == "e-mail")
+            .map(|(_, value)| {
+                value
+                    .strip_prefix("mailto:")
+                    .unwrap_or(value)
+                    .into()
+            });
+    }
+}
+
+/// Parses the authors' addresses section of an RFC.
+///
+/// The section is taken to end at the next heading, skipping the page
+/// headers and footers in between.
+///
+/// # Arguments
+///
+/// * `content` - The text of the RFC
+///
+/// # Returns
+///
+/// The authors in order of appearance, empty if there's no such section.
+#[must_use]
+pub fn parse_author_addresses(content: &str) -> Vec<AuthorAddress>
+{
+    let lines: Vec<&str> = content.lines().collect();
+
+    // The table of contents lists the heading too, but indented
+    let Some(heading_index) = lines
+        .iter()
+        .rposition(|line| ADDRESSES_HEADING_REGEX.is_match(line))
+    else
+    {
+        return Vec::new();
+    };
+
+    let mut authors: Vec<AuthorAddress> = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+
+    for &line in lines
+        .iter()
+        .skip(heading_index.saturating_add(1))
+    {
+        if is_page_break(line)
+        {
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() || !line.starts_with(' ')
+        {
+            push_paragraph(&mut authors, &paragraph);
+            paragraph.clear();
+
+            if trimmed.is_empty()
+            {
+                continue;
+            }
+            break;
+        }
+
+        paragraph.push(trimmed);
+    }
+    push_paragraph(&mut authors, &paragraph);
+
+    authors
+}
+
+/// Adds a paragraph of the section to the authors.
+///
+/// A paragraph of only contact lines belongs to the previous author, any
+/// other starts a new author.
+///
+/// # Arguments
+///
+/// * `authors` - The authors found so far
+/// * `lines` - The trimmed lines of the paragraph
+fn push_paragraph(authors: &mut Vec<AuthorAddress>, lines: &[&str])
+{
+    let is_contacts = lines
+        .iter()
+        .all(|line| contact_field(line).is_some());
+
+    match authors.last_mut()
+    {
+        Some(author) if is_contacts => author.add_contacts(lines),
+        // Contact lines without an author before them are dropped
+        None if is_contacts =>
+        {},
+        _ => authors.extend(AuthorAddress::from_lines(lines)),
+    }
+}
+
+/// Splits a contact line like `Email: ekr@rtfm.com`.
+///
+/// # Arguments
+///
+/// * `line` - The trimmed line
+///
+/// # Returns
+///
+/// The lowercased field name and the trimmed value, or `None` if the line
+/// isn't a contact line.
+fn contact_field(line: &str) -> Option<(String, &str)>
+{
+    let (field, value) = line.split_once(':')?;
+    let field = field.trim().to_lowercase();
+
+    CONTACT_FIELDS
+        .contains(&field.as_str())
+        .then(|| (field, value.trim()))
+}
+
+/// Checks whether a line is part of a page break, like a `[Page N]` footer,
+/// the running header of the next page or a form feed.
+///
+/// # Arguments
+///
+/// * `line` - The line to check
+///
+/// # Returns
+///
+/// `true` if the line is part of a page break.
+fn is_page_break(line: &str) -> bool
+{
+    line.trim_start().starts_with('\x0c') ||
+        line.starts_with("RFC ") ||
+        line.trim_end().ends_with(']') && line.contains("[Page ")
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// Creates an author from its fields.
+    fn author(
+        name: &str,
+        organization: Option<&str>,
+        email: Option<&str>,
+    ) -> AuthorAddress
+    {
+        AuthorAddress {
+            name: name.into(),
+            organization: organization.map(Box::from),
+            email: email.map(Box::from),
+        }
+    }
+
+    #[test]
+    fn test_parse_separate_contact_paragraphs()
+    {
+        let content = "\
+Table of Contents
+
+   9 Authors' Addresses ............................................ 175
+
+9 Authors' Addresses
+
+   Roy T. Fielding
+   Information and Computer Science
+   Irvine, CA 92697-3425
+
+   Fax: +1(949)824-1715
+   EMail: fielding@ics.uci.edu
+
+Fielding, et al.            Standards Track                   [Page 175]
+\x0c
+RFC 2616                        HTTP/1.1                       June 1999
+
+   Jim Gettys
+   World Wide Web Consortium
+
+   EMail: jg@w3.org
+
+10 Full Copyright Statement
+
+   Plain: not@an.author
+";
+
+        assert_eq!(
+            parse_author_addresses(content),
+            [
+                author(
+                    "Roy T. Fielding",
+                    Some("Information and Computer Science"),
+                    Some("fielding@ics.uci.edu")
+                ),
+                author(
+                    "Jim Gettys",
+                    Some("World Wide Web Consortium"),
+                    Some("jg@w3.org")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_inline_contacts()
+    {
+        let content = "\
+Author's Address
+
+   Eric Rescorla
+   Mozilla
+   Email: mailto:ekr@rtfm.com
+
+
+   Solo Writer
+   Email: solo@example.com
+
+   Nobody Known
+";
+
+        assert_eq!(
+            parse_author_addresses(content),
+            [
+                author("Eric Rescorla", Some("Mozilla"), Some("ekr@rtfm.com")),
+                author("Solo Writer", None, Some("solo@example.com")),
+                author("Nobody Known", None, None),
+            ]
+        );
+        assert!(parse_author_addresses("1.  Introduction\n").is_empty());
+    }
+}
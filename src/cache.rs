@@ -1,23 +1,86 @@
 //! Manages local caching of RFC documents.
 //!
 //! Stores document content on disk to minimize redundant network requests.
+use std::collections::BTreeMap;
+use std::ffi::OsString;
 use std::fs::{self, File};
-use std::io::Write as _;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, io};
 
-use anyhow::{Context as _, Result, bail};
+use anyhow::{Context as _, Result, bail, ensure};
 use directories::ProjectDirs;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 
-use crate::types::RfcNum;
+use crate::archive::{ArchiveEntry, ArchiveWriter, read_archive};
+use crate::client::normalize_document;
+use crate::metadata::RfcMetadata;
+use crate::types::{DocumentText, LineNumber, RfcNum};
+
+/// Size in bytes from which cached RFCs are memory-mapped.
+#[cfg(feature = "mmap")]
+const MMAP_THRESHOLD: u64 = 256 * 1024;
+
+/// Environment variable overriding the cache directory.
+pub const CACHE_DIR_ENV: &str = "RFC_READER_CACHE_DIR";
+
+/// Extension of the sidecar files with the entity tags of the cached RFCs.
+const ETAG_EXTENSION: &str = ".etag";
+
+/// Name of the cached RFC index file.
+const INDEX_FILE_NAME: &str = "rfc-index.txt";
+
+/// Name of the file with the last reading positions of the RFCs.
+const POSITIONS_FILE_NAME: &str = "positions.json";
+
+/// Name of the file with the bookmarks of the RFCs.
+const BOOKMARKS_FILE_NAME: &str = "bookmarks.json";
+
+/// Files of the user's own data kept in the cache directory, rather than
+/// cached content.
+const USER_DATA_FILE_NAMES: [&str; 2] =
+    [POSITIONS_FILE_NAME, BOOKMARKS_FILE_NAME];
+
+/// Kind of a file in the cache directory.
+///
+/// Ordered as listed, the index first and the unknown files last.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CachedFile
+{
+    /// The RFC index.
+    Index,
+    /// A cached RFC.
+    Rfc(RfcNum),
+    /// A file that is neither, with its name.
+    Unknown(Box<str>),
+}
+
+/// A file in the cache directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedEntry
+{
+    /// What the file holds.
+    pub file: CachedFile,
+    /// The size of the file in bytes.
+    pub size: u64,
+}
 
 /// Cache for storing RFC documents locally.
 ///
 /// Provides functionality to read and write RFCs to disk,
 /// reducing the need for repeated network requests.
+#[derive(Clone)]
 pub struct RfcCache
 {
     /// Directory where cache files are stored.
     cache_dir: Box<Path>,
+    /// Whether writes to the cache are refused.
+    is_read_only: bool,
+    /// Whether RFCs are cached as served, to be normalized when presented.
+    keeps_original_text: bool,
+    /// Whether presented RFCs keep the form feeds between pages.
+    keeps_page_breaks: bool,
 }
 
 impl RfcCache
@@ -36,16 +99,135 @@ impl RfcCache
     /// Returns an error if the cache directory cannot be determined or created.
     pub fn new() -> Result<Self>
     {
+        Self::with_dir(&Self::default_dir()?)
+    }
+
+    /// Sets how the cached RFCs are presented by
+    /// [`Self::get_presented_rfc_text`].
+    ///
+    /// # Arguments
+    ///
+    /// * `keeps_original_text` - Whether RFCs are cached as served
+    /// * `keeps_page_breaks` - Whether the form feeds between pages are kept
+    ///
+    /// # Returns
+    ///
+    /// The cache presenting the RFCs that way.
+    #[must_use]
+    pub const fn with_original_text(
+        mut self,
+        keeps_original_text: bool,
+        keeps_page_breaks: bool,
+    ) -> Self
+    {
+        self.keeps_original_text = keeps_original_text;
+        self.keeps_page_breaks = keeps_page_breaks;
+        self
+    }
+
+    /// Gets the default cache directory without creating it.
+    ///
+    /// # Returns
+    ///
+    /// The directory given by [`CACHE_DIR_ENV`] if set, otherwise the
+    /// platform specific cache directory of the app.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be determined.
+    pub fn default_dir() -> Result<PathBuf>
+    {
+        if let Some(cache_dir) = dir_override(env::var_os(CACHE_DIR_ENV))
+        {
+            return Ok(cache_dir);
+        }
+
         let project_dirs = ProjectDirs::from("", "", env!("CARGO_PKG_NAME"))
             .context("Failed to determine project directories")?;
 
-        let cache_dir = project_dirs.cache_dir();
+        Ok(project_dirs.cache_dir().to_path_buf())
+    }
+
+    /// Creates a new `RfcCache` instance that only reads the given
+    /// directory.
+    ///
+    /// The directory isn't created, and every write to the cache fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache_dir` - The directory to read the cache files from
+    ///
+    /// # Returns
+    ///
+    /// The new read-only `RfcCache`.
+    #[must_use]
+    pub fn read_only(cache_dir: &Path) -> Self
+    {
+        Self {
+            cache_dir: cache_dir.into(),
+            is_read_only: true,
+            keeps_original_text: false,
+            keeps_page_breaks: false,
+        }
+    }
+
+    /// Checks whether writes to the cache are refused.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the cache was created with [`Self::read_only`].
+    #[must_use]
+    pub const fn is_read_only(&self) -> bool
+    {
+        self.is_read_only
+    }
+
+    /// Refuses writes to a read-only cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache is read-only.
+    fn ensure_writable(&self) -> Result<()>
+    {
+        ensure!(!self.is_read_only, "The cache is read-only");
+
+        Ok(())
+    }
+
+    /// Creates a new `RfcCache` instance storing into the given directory.
+    ///
+    /// Creates the directory if it doesn't already exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache_dir` - The directory to store the cache files in
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the new `RfcCache` or an error if the directory
+    /// could not be created.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be created.
+    pub fn with_dir(cache_dir: &Path) -> Result<Self>
+    {
+        ensure!(
+            !cache_dir.exists() || cache_dir.is_dir(),
+            "Cache directory {} is not a directory",
+            cache_dir.display()
+        );
+
         // Create if cache_dir doesn't exist.
-        fs::create_dir_all(cache_dir)
-            .context("Failed to create cache directory")?;
+        fs::create_dir_all(cache_dir).with_context(|| {
+            format!("Failed to create cache directory {}", cache_dir.display())
+        })?;
 
         Ok(Self {
             cache_dir: cache_dir.into(),
+            is_read_only: false,
+            keeps_original_text: false,
+            keeps_page_breaks: false,
         })
     }
 
@@ -85,8 +267,194 @@ impl RfcCache
         Ok(content.into_boxed_str())
     }
 
+    /// Retrieves an RFC from the cache unless it's older than the given age.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number to retrieve
+    /// * `max_age` - The age from which the cached copy is stale
+    ///
+    /// # Returns
+    ///
+    /// The content of the RFC, or `None` if the cached copy is stale.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cached RFC does not exist or cannot be read.
+    pub fn get_cached_rfc_fresh(
+        &self,
+        rfc_number: RfcNum,
+        max_age: Duration,
+    ) -> Result<Option<Box<str>>>
+    {
+        if self.cached_rfc_age(rfc_number)? > max_age
+        {
+            return Ok(None);
+        }
+
+        self.get_cached_rfc(rfc_number).map(Some)
+    }
+
+    /// Gets how long ago an RFC was cached or found current.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number
+    ///
+    /// # Returns
+    ///
+    /// The time since the cache file was last modified, zero if that's in the
+    /// future.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RFC is not cached or its modification time
+    /// cannot be read.
+    pub fn cached_rfc_age(&self, rfc_number: RfcNum) -> Result<Duration>
+    {
+        let rfc_path = self.format_cache_path(rfc_number);
+        let modified = fs::metadata(&rfc_path)
+            .and_then(|metadata| metadata.modified())
+            .with_context(|| {
+                format!(
+                    "Failed to get the age of cached RFC {rfc_number} at {}",
+                    rfc_path.display()
+                )
+            })?;
+
+        Ok(SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default())
+    }
+
+    /// Marks a cached RFC as found current, restarting its age.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache is read-only or the RFC is not cached.
+    pub fn mark_rfc_fresh(&self, rfc_number: RfcNum) -> Result<()>
+    {
+        self.ensure_writable()?;
+
+        File::options()
+            .write(true)
+            .open(self.format_cache_path(rfc_number))
+            .and_then(|file| file.set_modified(SystemTime::now()))
+            .with_context(|| {
+                format!("Failed to mark cached RFC {rfc_number} as current")
+            })
+    }
+
+    /// Retrieves an RFC from the cache as document text.
+    ///
+    /// With the `mmap` feature, files of at least [`MMAP_THRESHOLD`] bytes
+    /// are memory-mapped instead of read to the heap. Falls back to a
+    /// regular read if mapping fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number to retrieve
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the text of the RFC if it exists in the cache,
+    /// or an error if the RFC is not cached or cannot be read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cached RFC does not exist, cannot be read or
+    /// is not valid UTF-8.
+    pub fn get_cached_rfc_text(
+        &self,
+        rfc_number: RfcNum,
+    ) -> Result<DocumentText>
+    {
+        #[cfg(feature = "mmap")]
+        if let Some(text) = self.map_cached_rfc(rfc_number)
+        {
+            return text;
+        }
+
+        self.get_cached_rfc(rfc_number)
+            .map(DocumentText::from)
+    }
+
+    /// Gets the text of a cached RFC as it's shown in the app.
+    ///
+    /// RFCs cached as served are normalized like fetched ones are otherwise,
+    /// so the line numbers match those in the app. Only `--cat` reads them
+    /// as cached, with [`Self::get_cached_rfc_text`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number to retrieve
+    ///
+    /// # Returns
+    ///
+    /// The presented text of the RFC.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cached RFC does not exist, cannot be read or
+    /// is not valid UTF-8.
+    pub fn get_presented_rfc_text(
+        &self,
+        rfc_number: RfcNum,
+    ) -> Result<DocumentText>
+    {
+        let text = self.get_cached_rfc_text(rfc_number)?;
+
+        if !self.keeps_original_text
+        {
+            return Ok(text);
+        }
+
+        Ok(normalize_document(text, self.keeps_page_breaks))
+    }
+
+    /// Memory-maps a cached RFC if it is large enough.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number to map
+    ///
+    /// # Returns
+    ///
+    /// The mapped text, or `None` if the file is small or cannot be mapped
+    /// and should be read normally instead.
+    #[cfg(feature = "mmap")]
+    fn map_cached_rfc(&self, rfc_number: RfcNum)
+    -> Option<Result<DocumentText>>
+    {
+        let file = File::open(self.format_cache_path(rfc_number)).ok()?;
+
+        if file.metadata().ok()?.len() < MMAP_THRESHOLD
+        {
+            return None;
+        }
+
+        // SAFETY: The mapping is read-only. Cached files do get replaced, by
+        // reloads, refreshes, imports and evictions, but only through
+        // `write_atomically`'s rename or by unlinking them. Either leaves the
+        // mapped inode intact until it's unmapped, so the mapping never sees
+        // a truncated file. Writing to the file in place, only possible from
+        // outside the application, could still raise `SIGBUS`.
+        let mmap = unsafe { memmap2::Mmap::map(&file) }.ok()?;
+
+        Some(DocumentText::from_mmap(mmap).with_context(|| {
+            format!("Cached RFC {rfc_number} is not valid UTF-8")
+        }))
+    }
+
     /// Stores an RFC in the cache.
     ///
+    /// The RFC is written atomically, so an interrupted write leaves the
+    /// previously cached copy, if any, instead of a truncated one.
+    ///
     /// # Arguments
     ///
     /// * `rfc_number` - The RFC number to cache
@@ -98,27 +466,102 @@ impl RfcCache
     ///
     /// # Errors
     ///
-    /// Returns an error if the cache file cannot be created or written to.
+    /// Returns an error if the cache file cannot be written, like when an
+    /// open copy can't be replaced on Windows.
     pub fn cache_rfc(&self, rfc_number: RfcNum, content: &str) -> Result<()>
     {
-        let rfc_path = self.format_cache_path(rfc_number);
+        self.ensure_writable()?;
 
-        let mut file = File::create(&rfc_path).with_context(|| {
+        write_atomically(
+            &self.format_cache_path(rfc_number),
+            content.as_bytes(),
+        )
+        .with_context(|| {
             format!("Failed to create cache file for RFC {rfc_number}")
-        })?;
+        })
+    }
 
-        // Write the contents.
-        if let Err(write_err) = file.write_all(content.as_bytes())
+    /// Retrieves the entity tag of a cached RFC.
+    ///
+    /// The tag is stored in a sidecar file next to the RFC, so the cached
+    /// text stays as served.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number
+    ///
+    /// # Returns
+    ///
+    /// The entity tag the server sent along with the cached RFC, or `None`
+    /// if it isn't stored or the RFC was cached in another form, as the
+    /// cached copy then differs from a fresh one even if the RFC didn't
+    /// change.
+    #[must_use]
+    pub fn get_etag(&self, rfc_number: RfcNum) -> Option<Box<str>>
+    {
+        let content =
+            fs::read_to_string(self.format_etag_path(rfc_number)).ok()?;
+        let (etag, text_form) = content.trim().split_once('\n')?;
+
+        (!etag.is_empty() && text_form == self.text_form()).then(|| etag.into())
+    }
+
+    /// Stores the entity tag of a cached RFC, removing it if `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number
+    /// * `etag` - The entity tag the server sent along with the RFC
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache is read-only or the sidecar file cannot
+    /// be written or removed.
+    pub fn cache_etag(
+        &self,
+        rfc_number: RfcNum,
+        etag: Option<&str>,
+    ) -> Result<()>
+    {
+        self.ensure_writable()?;
+
+        let path = self.format_etag_path(rfc_number);
+
+        match etag
         {
-            // We already messed up but the empty file remains.
-            // Attempt cleanup, but don't let the cleanup errors override the
-            // original error
-            let _ = fs::remove_file(&rfc_path);
+            Some(etag) =>
+            {
+                let content = format!("{etag}\n{}\n", self.text_form());
 
-            bail!(write_err);
+                write_atomically(&path, content.as_bytes()).with_context(|| {
+                    format!("Failed to cache the ETag of RFC {rfc_number}")
+                })
+            },
+            None if path.exists() =>
+            {
+                fs::remove_file(&path).with_context(|| {
+                    format!("Failed to remove the ETag of RFC {rfc_number}")
+                })
+            },
+            None => Ok(()),
         }
+    }
 
-        Ok(())
+    /// Names the form RFCs are cached in, stored along with their entity
+    /// tags.
+    ///
+    /// # Returns
+    ///
+    /// The name of the form, the page breaks only matter for normalized
+    /// RFCs.
+    const fn text_form(&self) -> &'static str
+    {
+        match (self.keeps_original_text, self.keeps_page_breaks)
+        {
+            (true, _) => "original",
+            (false, true) => "normalized with page breaks",
+            (false, false) => "normalized",
+        }
     }
 
     /// Retrieves the RFC index from the cache.
@@ -149,6 +592,9 @@ impl RfcCache
 
     /// Stores the RFC index in the cache.
     ///
+    /// The index is written to a temporary file first and renamed into place,
+    /// so it's never left partially written.
+    ///
     /// # Arguments
     ///
     /// * `content` - The content of the RFC index to store
@@ -162,15 +608,164 @@ impl RfcCache
     /// Returns an error if the cache file cannot be created or written to.
     pub fn cache_index(&self, content: &str) -> Result<()>
     {
-        let path = self.get_index_cache_path();
+        self.ensure_writable()?;
+
+        // An interrupted write leaves the previous index in place
+        write_atomically(&self.get_index_cache_path(), content.as_bytes())
+            .context("Failed to write RFC index to cache")
+    }
 
-        let mut file = File::create(&path)
-            .context("Failed to create cache file for RFC index")?;
+    /// Stores the line an RFC was last read at.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number
+    /// * `line` - The line scrolled to
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache is read-only or the positions cannot be
+    /// written.
+    pub fn save_position(&self, rfc_number: RfcNum, line: usize) -> Result<()>
+    {
+        self.save_rfc_value(POSITIONS_FILE_NAME, rfc_number, Some(line))
+            .context("Failed to write reading positions to cache")
+    }
 
-        file.write_all(content.as_bytes())
-            .context("Failed to write RFC index to cache")?;
+    /// Gets the line an RFC was last read at.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number
+    ///
+    /// # Returns
+    ///
+    /// The stored line, or `None` if the RFC has no position stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the positions cannot be read or parsed.
+    pub fn load_position(&self, rfc_number: RfcNum) -> Result<Option<usize>>
+    {
+        Ok(self
+            .load_rfc_values(POSITIONS_FILE_NAME)?
+            .remove(&rfc_number))
+    }
 
-        Ok(())
+    /// Stores the bookmarked lines of an RFC, removing them if empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number
+    /// * `bookmarks` - The bookmarked lines
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache is read-only or the bookmarks cannot be
+    /// written.
+    pub fn save_bookmarks(
+        &self,
+        rfc_number: RfcNum,
+        bookmarks: &[LineNumber],
+    ) -> Result<()>
+    {
+        self.save_rfc_value(
+            BOOKMARKS_FILE_NAME,
+            rfc_number,
+            (!bookmarks.is_empty()).then(|| bookmarks.to_vec()),
+        )
+        .context("Failed to write bookmarks to cache")
+    }
+
+    /// Gets the bookmarked lines of an RFC.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number
+    ///
+    /// # Returns
+    ///
+    /// The stored lines, empty if the RFC has no bookmarks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bookmarks cannot be read or parsed.
+    pub fn load_bookmarks(&self, rfc_number: RfcNum)
+    -> Result<Vec<LineNumber>>
+    {
+        Ok(self
+            .load_rfc_values(BOOKMARKS_FILE_NAME)?
+            .remove(&rfc_number)
+            .unwrap_or_default())
+    }
+
+    /// Stores a value of an RFC in a JSON file keyed by RFC number.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the file in the cache directory
+    /// * `rfc_number` - The RFC number
+    /// * `value` - The value to store, or `None` to remove it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache is read-only or the file cannot be
+    /// written.
+    fn save_rfc_value<T>(
+        &self,
+        file_name: &str,
+        rfc_number: RfcNum,
+        value: Option<T>,
+    ) -> Result<()>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        self.ensure_writable()?;
+
+        // A corrupted file only loses the previous values
+        let mut values = self
+            .load_rfc_values(file_name)
+            .unwrap_or_default();
+        match value
+        {
+            Some(value) => values.insert(rfc_number, value),
+            None => values.remove(&rfc_number),
+        };
+
+        let content = serde_json::to_vec(&values)
+            .with_context(|| format!("Failed to serialize {file_name}"))?;
+        write_atomically(&self.cache_dir.join(file_name), &content)
+    }
+
+    /// Reads the values of all RFCs from a JSON file keyed by RFC number.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the file in the cache directory
+    ///
+    /// # Returns
+    ///
+    /// The stored values by RFC, empty if the file doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    fn load_rfc_values<T>(&self, file_name: &str) -> Result<BTreeMap<RfcNum, T>>
+    where
+        T: DeserializeOwned,
+    {
+        let path = self.cache_dir.join(file_name);
+
+        if !path.exists()
+        {
+            return Ok(BTreeMap::new());
+        }
+
+        let content = fs::read(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        serde_json::from_slice(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
     }
 
     /// Format the file path for a specific RFC in the cache.
@@ -182,13 +777,30 @@ impl RfcCache
     /// # Returns
     ///
     /// The path where the RFC should be cached.
-    fn format_cache_path(&self, rfc_number: RfcNum) -> Box<Path>
+    #[must_use]
+    pub fn format_cache_path(&self, rfc_number: RfcNum) -> Box<Path>
     {
         self.cache_dir
             .join(format!("rfc{rfc_number}.txt"))
             .into_boxed_path()
     }
 
+    /// Formats the path of the sidecar file with the entity tag of an RFC.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number
+    ///
+    /// # Returns
+    ///
+    /// The path where the entity tag of the RFC is stored.
+    fn format_etag_path(&self, rfc_number: RfcNum) -> Box<Path>
+    {
+        self.cache_dir
+            .join(format!("rfc{rfc_number}{ETAG_EXTENSION}"))
+            .into_boxed_path()
+    }
+
     /// Gets the file path for the RFC index in the cache.
     ///
     /// # Returns
@@ -197,12 +809,50 @@ impl RfcCache
     fn get_index_cache_path(&self) -> Box<Path>
     {
         self.cache_dir
-            .join("rfc-index.txt")
+            .join(INDEX_FILE_NAME)
             .into_boxed_path()
     }
 
+    /// Removes a single RFC from the cache, along with its entity tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number to remove
+    ///
+    /// # Returns
+    ///
+    /// Whether the RFC was cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache is read-only or the RFC cannot be
+    /// removed.
+    pub fn remove_rfc(&self, rfc_number: RfcNum) -> Result<bool>
+    {
+        self.ensure_writable()?;
+
+        let rfc_path = self.format_cache_path(rfc_number);
+        if let Err(error) = fs::remove_file(&rfc_path)
+        {
+            if error.kind() == io::ErrorKind::NotFound
+            {
+                return Ok(false);
+            }
+
+            return Err(error).with_context(|| {
+                format!("Failed to remove cached RFC {rfc_number}")
+            });
+        }
+        self.cache_etag(rfc_number, None)?;
+
+        Ok(true)
+    }
+
     /// Clears all cached RFCs and the index.
     ///
+    /// The reading positions and bookmarks are kept, along with the cache
+    /// directory if any of them exist.
+    ///
     /// # Returns
     ///
     /// A Result indicating success or an error if clearing the cache failed.
@@ -212,123 +862,630 @@ impl RfcCache
     /// Returns an error if removing files from the cache directory fails.
     pub fn clear(&self) -> Result<()>
     {
+        self.ensure_writable()?;
+
         // Read the directory entries
         let entries = fs::read_dir(&self.cache_dir)
             .context("Failed to read cache directory")?;
 
-        // Remove each file or directory in the cache directory
-        for entry in entries.filter_map(Result::ok)
+        // Remove each file or directory in the cache directory
+        for entry in entries.filter_map(Result::ok)
+        {
+            if USER_DATA_FILE_NAMES
+                .iter()
+                .any(|&file_name| entry.file_name() == file_name)
+            {
+                continue;
+            }
+
+            let path = entry.path();
+            // Doesn't follow symlinks, unlike `Path::is_dir`
+            let Ok(file_type) = entry.file_type()
+            else
+            {
+                continue;
+            };
+
+            if file_type.is_symlink()
+            {
+                // Unlink without touching the target, directory links on
+                // Windows are removed as directories
+                fs::remove_file(&path)
+                    .or_else(|_| fs::remove_dir(&path))
+                    .with_context(|| {
+                        format!(
+                            "Failed to remove cache symlink: {}",
+                            path.display()
+                        )
+                    })?;
+            }
+            else if file_type.is_file()
+            {
+                fs::remove_file(&path).with_context(|| {
+                    format!("Failed to remove cache file: {}", path.display())
+                })?;
+            }
+            else if file_type.is_dir()
+            {
+                fs::remove_dir_all(&path).with_context(|| {
+                    format!(
+                        "Failed to remove cache directory: {}",
+                        path.display()
+                    )
+                })?;
+            }
+        }
+
+        // A symlinked cache directory is kept, only its content belongs to
+        // the cache
+        let is_symlink = fs::symlink_metadata(&self.cache_dir)
+            .is_ok_and(|metadata| metadata.file_type().is_symlink());
+        if is_symlink
+        {
+            return Ok(());
+        }
+
+        // Remove the directory if it is empty.
+        let is_empty = self
+            .cache_dir
+            .read_dir()
+            .context("Failed to check if cache directory is empty")?
+            .next()
+            .is_none();
+
+        if is_empty
+        {
+            fs::remove_dir(&self.cache_dir)
+                .context("Failed to remove empty cache directory")?;
+        }
+
+        Ok(())
+    }
+
+    /// Exports all cached files to a tar archive.
+    ///
+    /// # Arguments
+    ///
+    /// * `archive_path` - The archive to create, gzipped if its name ends with
+    ///   `.gz` or `.tgz`
+    ///
+    /// # Returns
+    ///
+    /// The number of exported files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache can't be read or the archive can't be
+    /// written.
+    pub fn export_archive(&self, archive_path: &Path) -> Result<usize>
+    {
+        // Listed first, so an archive written into the cache isn't included
+        let paths: Vec<PathBuf> = fs::read_dir(&self.cache_dir)
+            .context("Failed to read cache directory")?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect();
+
+        let is_gzipped = archive_path
+            .extension()
+            .is_some_and(|extension| extension == "gz" || extension == "tgz");
+        let archive = File::create(archive_path).with_context(|| {
+            format!("Failed to create archive {}", archive_path.display())
+        })?;
+        let mut writer = ArchiveWriter::new(archive, is_gzipped);
+        let mut count: usize = 0;
+
+        for path in paths
+        {
+            let Some(name) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(Box::from)
+            else
+            {
+                continue;
+            };
+
+            let mut file = File::open(&path).with_context(|| {
+                format!("Failed to read cache file {}", path.display())
+            })?;
+            let metadata = file.metadata().with_context(|| {
+                format!("Failed to read cache file {}", path.display())
+            })?;
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |duration| duration.as_secs());
+
+            let entry = ArchiveEntry {
+                name,
+                size: metadata.len(),
+                modified,
+            };
+            writer.append(&entry, &mut file)?;
+            count = count.saturating_add(1);
+        }
+
+        writer.finish()?;
+
+        Ok(count)
+    }
+
+    /// Imports the files of a tar archive into the cache.
+    ///
+    /// Existing files with the same names are replaced. The files are
+    /// extracted next to their targets first and moved in place only once
+    /// the whole archive was read, keeping their modification times.
+    ///
+    /// # Arguments
+    ///
+    /// * `archive_path` - The archive to import, possibly gzipped
+    ///
+    /// # Returns
+    ///
+    /// The number of imported files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive can't be read, contains names
+    /// escaping the cache directory, or the files can't be written.
+    pub fn import_archive(&self, archive_path: &Path) -> Result<usize>
+    {
+        self.ensure_writable()?;
+
+        let file = File::open(archive_path).with_context(|| {
+            format!("Failed to open archive {}", archive_path.display())
+        })?;
+
+        // The temporary files and the files they replace
+        let mut extracted: Vec<(PathBuf, PathBuf)> = Vec::new();
+
+        let result = read_archive(file, |entry, content| {
+            let path = self.cache_dir.join(&*entry.name);
+            let temp_path = temp_path(&path)?;
+
+            let mut temp_file =
+                File::create(&temp_path).with_context(|| {
+                    format!("Failed to write {}", temp_path.display())
+                })?;
+            if !extracted
+                .iter()
+                .any(|(_, target)| *target == path)
+            {
+                extracted.push((temp_path.clone(), path));
+            }
+
+            io::copy(content, &mut temp_file).with_context(|| {
+                format!("Failed to write {}", temp_path.display())
+            })?;
+
+            // Unknown times are left as the time of the import
+            if let Some(modified) = (entry.modified > 0)
+                .then(|| {
+                    UNIX_EPOCH.checked_add(Duration::from_secs(entry.modified))
+                })
+                .flatten()
+            {
+                temp_file
+                    .set_modified(modified)
+                    .with_context(|| {
+                        format!(
+                            "Failed to set the time of {}",
+                            temp_path.display()
+                        )
+                    })?;
+            }
+
+            Ok(())
+        })
+        .with_context(|| {
+            format!("Failed to read archive {}", archive_path.display())
+        })
+        // Replaced by renaming, as the files may be memory-mapped
+        .and_then(|count| {
+            for (temp_path, path) in &extracted
+            {
+                fs::rename(temp_path, path).with_context(|| {
+                    format!("Failed to write cache file {}", path.display())
+                })?;
+            }
+
+            Ok(count)
+        });
+
+        if result.is_err()
+        {
+            for (temp_path, _) in &extracted
+            {
+                // Don't let the cleanup errors override the original error
+                let _ = fs::remove_file(temp_path);
+            }
+        }
+
+        result
+    }
+
+    /// Get the cache directory.
+    ///
+    /// # Returns
+    ///
+    /// The cache directory.
+    #[must_use]
+    pub const fn cache_dir(&self) -> &Path
+    {
+        &self.cache_dir
+    }
+
+    /// Gets the numbers of all cached RFCs.
+    ///
+    /// # Returns
+    ///
+    /// The cached RFC numbers in ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be read.
+    pub fn cached_rfc_numbers(&self) -> Result<Vec<RfcNum>>
+    {
+        let mut rfc_numbers: Vec<RfcNum> = fs::read_dir(&self.cache_dir)
+            .context("Failed to read cache directory")?
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix("rfc")?
+                    .strip_suffix(".txt")?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+
+        rfc_numbers.sort_unstable();
+
+        Ok(rfc_numbers)
+    }
+
+    /// Evicts the least recently used RFCs until the cache fits a size.
+    ///
+    /// RFCs are evicted by the later of their access and modification
+    /// times, along with their entity tags. The index is never evicted.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_bytes` - The total size the cached RFCs may take up
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes freed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache is read-only, the cache directory
+    /// cannot be read or an RFC cannot be removed.
+    pub fn enforce_size_limit(&self, max_bytes: u64) -> Result<u64>
+    {
+        self.ensure_writable()?;
+
+        let mut cached_rfcs: Vec<(SystemTime, u64, RfcNum)> = self
+            .cached_rfc_numbers()?
+            .into_iter()
+            .filter_map(|rfc_number| {
+                let metadata =
+                    fs::metadata(self.format_cache_path(rfc_number)).ok()?;
+                let modified = metadata.modified().ok()?;
+                // Access times may not be tracked
+                let last_used = metadata
+                    .accessed()
+                    .map_or(modified, |accessed| accessed.max(modified));
+
+                Some((last_used, metadata.len(), rfc_number))
+            })
+            .collect();
+        cached_rfcs.sort_unstable();
+
+        let mut total_bytes: u64 = cached_rfcs
+            .iter()
+            .map(|&(_, len, _)| len)
+            .sum();
+        let mut freed_bytes: u64 = 0;
+
+        for (_, len, rfc_number) in cached_rfcs
+        {
+            if total_bytes <= max_bytes
+            {
+                break;
+            }
+
+            fs::remove_file(self.format_cache_path(rfc_number)).with_context(
+                || format!("Failed to evict cached RFC {rfc_number}"),
+            )?;
+            self.cache_etag(rfc_number, None)?;
+
+            total_bytes = total_bytes.saturating_sub(len);
+            freed_bytes = freed_bytes.saturating_add(len);
+        }
+
+        Ok(freed_bytes)
+    }
+
+    /// Gets the files in the cache directory.
+    ///
+    /// The entity tags, the reading positions and the bookmarks are left
+    /// out, as they belong to the cached RFCs.
+    ///
+    /// # Returns
+    ///
+    /// The index first, then the RFCs in ascending order, then the files
+    /// that are neither by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory or a file in it cannot be
+    /// read.
+    pub fn cached_entries(&self) -> Result<Vec<CachedEntry>>
+    {
+        let mut entries = Vec::new();
+
+        for entry in fs::read_dir(&self.cache_dir)
+            .context("Failed to read cache directory")?
         {
-            let path = entry.path();
+            let entry = entry.context("Failed to read cache directory")?;
+            let metadata = entry.metadata().with_context(|| {
+                format!("Failed to read {}", entry.path().display())
+            })?;
+            if !metadata.is_file()
+            {
+                continue;
+            }
 
-            if path.is_file()
+            let file_name = entry.file_name();
+            let file_name = file_name.to_string_lossy();
+            if file_name.ends_with(ETAG_EXTENSION) ||
+                USER_DATA_FILE_NAMES.contains(&&*file_name)
             {
-                fs::remove_file(&path).with_context(|| {
-                    format!("Failed to remove cache file: {}", path.display())
-                })?;
+                continue;
             }
-            else if path.is_dir()
+
+            let file = if file_name == INDEX_FILE_NAME
             {
-                fs::remove_dir_all(&path).with_context(|| {
-                    format!(
-                        "Failed to remove cache directory: {}",
-                        path.display()
-                    )
-                })?;
+                CachedFile::Index
             }
+            else if let Some(rfc_number) = file_name
+                .strip_prefix("rfc")
+                .and_then(|name| name.strip_suffix(".txt"))
+                .and_then(|number| number.parse().ok())
+            {
+                CachedFile::Rfc(rfc_number)
+            }
+            else
+            {
+                CachedFile::Unknown(file_name.into())
+            };
+
+            entries.push(CachedEntry {
+                file,
+                size: metadata.len(),
+            });
         }
 
-        // Remove the directory if it is empty.
-        let is_empty = self
-            .cache_dir
-            .read_dir()
-            .context("Failed to check if cache directory is empty")?
-            .next()
-            .is_none();
+        entries.sort_unstable_by(|entry, other| entry.file.cmp(&other.file));
 
-        if is_empty
-        {
-            fs::remove_dir(&self.cache_dir)
-                .context("Failed to remove empty cache directory")?;
-        }
+        Ok(entries)
+    }
 
-        Ok(())
+    /// Gets the cached RFCs along with their titles.
+    ///
+    /// # Returns
+    ///
+    /// The cached RFCs in ascending order, each with its title if it can be
+    /// read and found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be read.
+    pub fn list_cached(&self) -> Result<Vec<(RfcNum, Option<Box<str>>)>>
+    {
+        Ok(self
+            .cached_rfc_numbers()?
+            .into_iter()
+            .map(|rfc_number| (rfc_number, self.cached_title(rfc_number)))
+            .collect())
     }
 
-    /// Get the cache directory.
+    /// Gets the title of a cached RFC.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number
     ///
     /// # Returns
     ///
-    /// The cache directory.
+    /// The title, or `None` if the RFC cannot be read or has no title.
     #[must_use]
-    pub const fn cache_dir(&self) -> &Path
+    pub fn cached_title(&self, rfc_number: RfcNum) -> Option<Box<str>>
     {
-        &self.cache_dir
+        self.get_cached_rfc_text(rfc_number)
+            .ok()
+            .and_then(|content| RfcMetadata::parse(&content).title)
     }
 
     /// List the cached RFCs.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if the cache directory cannot be read.
-    pub fn print_list(&self)
+    /// Returns an error if the cache directory cannot be read.
+    pub fn print_list(&self) -> Result<()>
     {
-        // Read the directory entries.
-        let entries: Vec<_> = fs::read_dir(&self.cache_dir)
-            .expect("Failed to read cache directory")
-            .filter_map(Result::ok)
-            .collect();
+        let entries = self.cached_entries()?;
 
         if entries.is_empty()
         {
             println!("No cached RFCs found.");
-            return;
+            return Ok(());
         }
 
         println!("List of cached RFCs:");
 
         for entry in entries
         {
-            let path = entry.path();
-            if path.is_file()
+            match entry.file
             {
-                let file_name = path
-                    .file_name()
-                    .expect("Failed to get file name")
-                    .to_string_lossy();
-
-                // Skip the index file
-                if file_name == "rfc-index.txt"
-                {
-                    println!("- RFC Index");
-                }
-                // Extract the RFC number from the file name.
-                else if let Some(rfc_num) = file_name
-                    .strip_prefix("rfc")
-                    .and_then(|name| name.strip_suffix(".txt"))
+                CachedFile::Index => println!("- RFC Index"),
+                CachedFile::Rfc(rfc_number) =>
                 {
-                    println!("- RFC {rfc_num}");
-                }
-                else
+                    match self.cached_title(rfc_number)
+                    {
+                        Some(title) => println!("- RFC {rfc_number} - {title}"),
+                        None => println!("- RFC {rfc_number}"),
+                    }
+                },
+                // Warn the user for stray files
+                CachedFile::Unknown(file_name) =>
                 {
-                    // Warn the user for stray files
-                    println!("{} (not a valid RFC document)", file_name);
-                }
+                    println!("{file_name} (not a valid RFC document)");
+                },
             }
         }
+
+        Ok(())
+    }
+}
+
+/// Takes the cache directory from the value of [`CACHE_DIR_ENV`].
+///
+/// # Arguments
+///
+/// * `value` - The value of the variable, if set
+///
+/// # Returns
+///
+/// The directory, or `None` if the variable is unset or empty.
+fn dir_override(value: Option<OsString>) -> Option<PathBuf>
+{
+    value
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Writes a file through a temporary file renamed into place.
+///
+/// The temporary file is in the same directory, so the rename replaces the
+/// file at once. The rename replaces existing files on Windows as well,
+/// unless they're open there. On failure the temporary file is removed and
+/// the file is left as it was.
+///
+/// # Arguments
+///
+/// * `path` - The file to write
+/// * `content` - The content to write
+///
+/// # Errors
+///
+/// Returns an error if the temporary file cannot be written or renamed.
+fn write_atomically(path: &Path, content: &[u8]) -> Result<()>
+{
+    let temp_path = temp_path(path)?;
+
+    let result = fs::write(&temp_path, content)
+        .with_context(|| format!("Failed to write {}", temp_path.display()))
+        .and_then(|()| {
+            fs::rename(&temp_path, path).with_context(|| {
+                format!(
+                    "Failed to rename {} to {}",
+                    temp_path.display(),
+                    path.display()
+                )
+            })
+        });
+
+    if result.is_err()
+    {
+        // Don't let the cleanup errors override the original error
+        let _ = fs::remove_file(&temp_path);
     }
+
+    result
+}
+
+/// Gets the hidden temporary file a file is written to before being moved
+/// in place.
+///
+/// # Arguments
+///
+/// * `path` - The file to write
+///
+/// # Returns
+///
+/// The temporary file next to it, like `.rfc2119.txt.tmp`.
+///
+/// # Errors
+///
+/// Returns an error if the path has no file name.
+fn temp_path(path: &Path) -> Result<PathBuf>
+{
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("No file name in {}", path.display()))?;
+    let mut temp_name = OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".tmp");
+
+    Ok(path.with_file_name(temp_name))
 }
 
 #[cfg(test)]
 mod tests
 {
-    use std::fs::File;
+    use std::fs::{File, FileTimes};
     use std::io::Write as _;
 
     use tempfile::TempDir;
 
     use super::*;
 
+    #[test]
+    fn test_archive_round_trip() -> Result<()>
+    {
+        const MAX_AGE: Duration = Duration::from_hours(24);
+
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(&temp_dir.path().join("old"))?;
+        let rfc_number = RfcNum::new(791).expect("its non-zero");
+        cache.cache_rfc(rfc_number, "Internet Protocol")?;
+        cache.cache_index("0791 Internet Protocol.")?;
+        File::options()
+            .write(true)
+            .open(cache.format_cache_path(rfc_number))?
+            .set_modified(SystemTime::now() - MAX_AGE * 2)?;
+
+        let archive_path = temp_dir.path().join("cache.tar.gz");
+        assert_eq!(cache.export_archive(&archive_path)?, 2);
+
+        let new_cache = RfcCache::with_dir(&temp_dir.path().join("new"))?;
+        assert_eq!(new_cache.import_archive(&archive_path)?, 2);
+        assert_eq!(
+            &*new_cache.get_cached_rfc(rfc_number)?,
+            "Internet Protocol"
+        );
+        assert_eq!(&*new_cache.get_cached_index()?, "0791 Internet Protocol.");
+        // Still as old as the exported copy
+        assert!(new_cache.cached_rfc_age(rfc_number)? > MAX_AGE);
+
+        // A broken archive leaves the cache as it was
+        fs::write(&archive_path, b"not an archive")?;
+        assert!(
+            new_cache
+                .import_archive(&archive_path)
+                .is_err()
+        );
+        assert_eq!(fs::read_dir(new_cache.cache_dir())?.count(), 2);
+
+        Ok(())
+    }
+
     #[test]
     fn test_clear_with_files() -> Result<()>
     {
@@ -339,6 +1496,9 @@ mod tests
         // Bypass the ctor for the temp dir.
         let cache = RfcCache {
             cache_dir: cache_dir.into(),
+            is_read_only: false,
+            keeps_original_text: false,
+            keeps_page_breaks: false,
         };
 
         // Create test files in the temp dir
@@ -370,6 +1530,77 @@ mod tests
         Ok(())
     }
 
+    #[test]
+    fn test_remove_rfc() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        let removed_number = RfcNum::new(2119).expect("its non-zero");
+        let kept_number = RfcNum::new(8174).expect("its non-zero");
+        cache.cache_rfc(removed_number, "Key words")?;
+        cache.cache_etag(removed_number, Some("\"v1\""))?;
+        cache.cache_rfc(kept_number, "Key words update")?;
+        cache.cache_index("index")?;
+
+        assert!(cache.remove_rfc(removed_number)?);
+        assert!(cache.get_cached_rfc(removed_number).is_err());
+        assert_eq!(cache.get_etag(removed_number), None);
+        assert_eq!(&*cache.get_cached_rfc(kept_number)?, "Key words update");
+        assert_eq!(&*cache.get_cached_index()?, "index");
+
+        // Removing it again isn't an error
+        assert!(!cache.remove_rfc(removed_number)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_presented_rfc_text() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let rfc_number = RfcNum::new(2119).expect("its non-zero");
+        let served = "\n\nKey words\n\x0c\nPage 2\n";
+        RfcCache::with_dir(temp_dir.path())?.cache_rfc(rfc_number, served)?;
+
+        // Cached normalized, read as is
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        assert_eq!(&*cache.get_presented_rfc_text(rfc_number)?, served);
+
+        let cache = cache.with_original_text(true, false);
+        assert_eq!(
+            &*cache.get_presented_rfc_text(rfc_number)?,
+            "Key words\n\nPage 2"
+        );
+        assert_eq!(&*cache.get_cached_rfc_text(rfc_number)?, served);
+
+        let cache = cache.with_original_text(true, true);
+        assert_eq!(
+            &*cache.get_presented_rfc_text(rfc_number)?,
+            "Key words\n\x0c\nPage 2"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_clear_keeps_user_data() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        let rfc_number = RfcNum::new(2119).expect("its non-zero");
+        cache.cache_rfc(rfc_number, "Key words")?;
+        cache.save_position(rfc_number, 42)?;
+        cache.save_bookmarks(rfc_number, &[3, 12])?;
+
+        cache.clear()?;
+
+        assert!(cache.get_cached_rfc(rfc_number).is_err());
+        assert_eq!(cache.load_position(rfc_number)?, Some(42));
+        assert_eq!(cache.load_bookmarks(rfc_number)?, [3, 12]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_clear_with_no_files() -> Result<()>
     {
@@ -379,6 +1610,9 @@ mod tests
 
         let cache = RfcCache {
             cache_dir: cache_dir.into(),
+            is_read_only: false,
+            keeps_original_text: false,
+            keeps_page_breaks: false,
         };
 
         // Call the clear function on an empty directory
@@ -400,6 +1634,9 @@ mod tests
         // Create an instance with the temp directory
         let cache = RfcCache {
             cache_dir: cache_dir.into(),
+            is_read_only: false,
+            keeps_original_text: false,
+            keeps_page_breaks: false,
         };
 
         // Create a file
@@ -425,12 +1662,63 @@ mod tests
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_clear_unlinks_symlinks() -> Result<()>
+    {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new()?;
+        let cache_dir = temp_dir.path().join("cache");
+        fs::create_dir(&cache_dir)?;
+        let cache = RfcCache {
+            cache_dir: cache_dir.clone().into(),
+            is_read_only: false,
+            keeps_original_text: false,
+            keeps_page_breaks: false,
+        };
+
+        // Content outside the cache that is linked into it
+        let shared_dir = temp_dir.path().join("shared");
+        fs::create_dir(&shared_dir)?;
+        let shared_file = shared_dir.join("rfc1.txt");
+        fs::write(&shared_file, "shared content")?;
+        symlink(&shared_dir, cache_dir.join("linked_dir"))?;
+        symlink(&shared_file, cache_dir.join("rfc1.txt"))?;
+
+        cache.clear()?;
+
+        assert!(!cache_dir.exists());
+        assert_eq!(fs::read_to_string(&shared_file)?, "shared content");
+
+        // A symlinked cache directory is emptied but kept
+        let linked_cache_dir = temp_dir.path().join("linked_cache");
+        symlink(&shared_dir, &linked_cache_dir)?;
+        let cache = RfcCache {
+            cache_dir: linked_cache_dir.clone().into(),
+            is_read_only: false,
+            keeps_original_text: false,
+            keeps_page_breaks: false,
+        };
+
+        cache.clear()?;
+
+        assert!(linked_cache_dir.exists());
+        assert!(shared_dir.exists());
+        assert!(!shared_file.exists());
+
+        Ok(())
+    }
+
     #[test]
     fn test_rfc_round_trip() -> Result<()>
     {
         let temp_dir = TempDir::new()?;
         let cache = RfcCache {
             cache_dir: temp_dir.path().into(),
+            is_read_only: false,
+            keeps_original_text: false,
+            keeps_page_breaks: false,
         };
 
         let rfc_number = RfcNum::new(1234).expect("its non-zero");
@@ -451,4 +1739,363 @@ mod tests
 
         Ok(())
     }
+
+    #[test]
+    fn test_get_cached_rfc_text_large() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache {
+            cache_dir: temp_dir.path().into(),
+            is_read_only: false,
+            keeps_original_text: false,
+            keeps_page_breaks: false,
+        };
+
+        let rfc_number = RfcNum::new(4321).expect("its non-zero");
+        // Large enough to be memory-mapped with the `mmap` feature
+        let content = "Lorem ipsum dolor sit amet.\n".repeat(16 * 1024);
+
+        cache.cache_rfc(rfc_number, &content)?;
+
+        let text = cache.get_cached_rfc_text(rfc_number)?;
+        assert_eq!(&*text, content);
+        assert_eq!(text.is_mapped(), cfg!(feature = "mmap"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cached_rfc_numbers() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache {
+            cache_dir: temp_dir.path().into(),
+            is_read_only: false,
+            keeps_original_text: false,
+            keeps_page_breaks: false,
+        };
+
+        for rfc_number in [9110, 791]
+        {
+            let rfc_number = RfcNum::new(rfc_number).expect("its non-zero");
+            cache.cache_rfc(rfc_number, "content")?;
+        }
+        cache.cache_index("index")?;
+        File::create(temp_dir.path().join("stray.txt"))?;
+
+        let rfc_numbers: Vec<u16> = cache
+            .cached_rfc_numbers()?
+            .into_iter()
+            .map(RfcNum::get)
+            .collect();
+        assert_eq!(rfc_numbers, [791, 9110]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_only_cache() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let rfc_number = RfcNum::new(791).expect("its non-zero");
+        RfcCache::with_dir(temp_dir.path())?
+            .cache_rfc(rfc_number, "Internet Protocol")?;
+
+        let cache = RfcCache::read_only(temp_dir.path());
+        assert!(cache.is_read_only());
+        assert_eq!(&*cache.get_cached_rfc(rfc_number)?, "Internet Protocol");
+        assert!(
+            cache
+                .cache_rfc(rfc_number, "changed")
+                .is_err()
+        );
+        assert!(cache.cache_index("index").is_err());
+        assert!(cache.clear().is_err());
+        assert_eq!(&*cache.get_cached_rfc(rfc_number)?, "Internet Protocol");
+
+        // A missing directory isn't created
+        let missing_dir = temp_dir.path().join("missing");
+        let cache = RfcCache::read_only(&missing_dir);
+        assert!(cache.get_cached_rfc(rfc_number).is_err());
+        assert!(
+            cache
+                .cache_rfc(rfc_number, "content")
+                .is_err()
+        );
+        assert!(!missing_dir.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_dir_override() -> Result<()>
+    {
+        assert_eq!(
+            dir_override(Some("/mnt/shared/rfcs".into())),
+            Some(PathBuf::from("/mnt/shared/rfcs"))
+        );
+        // Empty is as good as unset
+        assert_eq!(dir_override(Some(OsString::new())), None);
+        assert_eq!(dir_override(None), None);
+
+        let temp_dir = TempDir::new()?;
+        let nested_dir = temp_dir.path().join("team").join("rfcs");
+        RfcCache::with_dir(&nested_dir)?;
+        assert!(nested_dir.is_dir());
+
+        let file_path = temp_dir.path().join("file");
+        File::create(&file_path)?;
+        let error = RfcCache::with_dir(&file_path)
+            .err()
+            .expect("a file is not a directory");
+        assert!(
+            error
+                .to_string()
+                .contains("is not a directory")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cached_rfc_age() -> Result<()>
+    {
+        /// Age of a month.
+        const MONTH: Duration = Duration::from_hours(30 * 24);
+
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        let rfc_number = RfcNum::new(791).expect("its non-zero");
+        assert!(cache.cached_rfc_age(rfc_number).is_err());
+        assert!(
+            cache
+                .get_cached_rfc_fresh(rfc_number, MONTH)
+                .is_err()
+        );
+
+        cache.cache_rfc(rfc_number, "Internet Protocol")?;
+        assert!(cache.cached_rfc_age(rfc_number)? < MONTH);
+        assert_eq!(
+            cache
+                .get_cached_rfc_fresh(rfc_number, MONTH)?
+                .as_deref(),
+            Some("Internet Protocol")
+        );
+
+        File::options()
+            .write(true)
+            .open(cache.format_cache_path(rfc_number))?
+            .set_modified(UNIX_EPOCH)?;
+        assert!(cache.cached_rfc_age(rfc_number)? > MONTH);
+        assert_eq!(cache.get_cached_rfc_fresh(rfc_number, MONTH)?, None);
+
+        cache.mark_rfc_fresh(rfc_number)?;
+        assert!(cache.cached_rfc_age(rfc_number)? < MONTH);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_enforce_size_limit() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        cache.cache_index(&"index ".repeat(100))?;
+
+        // Used at staggered times, the lowest numbers longest ago
+        for number in 1..=4
+        {
+            let rfc_number = RfcNum::new(number).expect("its non-zero");
+            cache.cache_rfc(rfc_number, &"x".repeat(100))?;
+            cache.cache_etag(rfc_number, Some("\"v1\""))?;
+
+            let last_used = UNIX_EPOCH + Duration::from_hours(number.into());
+            File::options()
+                .write(true)
+                .open(cache.format_cache_path(rfc_number))?
+                .set_times(
+                    FileTimes::new()
+                        .set_accessed(last_used)
+                        .set_modified(last_used),
+                )?;
+        }
+
+        // Already fitting the limit
+        assert_eq!(cache.enforce_size_limit(400)?, 0);
+
+        assert_eq!(cache.enforce_size_limit(250)?, 200);
+        let rfc_numbers: Vec<u16> = cache
+            .cached_rfc_numbers()?
+            .into_iter()
+            .map(RfcNum::get)
+            .collect();
+        assert_eq!(rfc_numbers, [3, 4]);
+        assert_eq!(cache.get_etag(RfcNum::new(1).expect("its non-zero")), None);
+        assert!(cache.get_cached_index().is_ok());
+
+        assert_eq!(cache.enforce_size_limit(0)?, 200);
+        assert!(cache.cached_rfc_numbers()?.is_empty());
+        assert!(cache.get_cached_index().is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_rfc_is_atomic() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        let rfc_number = RfcNum::new(2119).expect("its non-zero");
+
+        cache.cache_rfc(rfc_number, "Old RFC")?;
+        cache.cache_rfc(rfc_number, "New RFC")?;
+        assert_eq!(&*cache.get_cached_rfc(rfc_number)?, "New RFC");
+        assert_eq!(fs::read_dir(temp_dir.path())?.count(), 1);
+
+        // A failed write leaves neither the temporary file nor a change
+        let other_number = RfcNum::new(2616).expect("its non-zero");
+        fs::create_dir(cache.format_cache_path(other_number))?;
+        assert!(cache.cache_rfc(other_number, "RFC").is_err());
+        assert!(
+            cache
+                .format_cache_path(other_number)
+                .is_dir()
+        );
+        assert_eq!(fs::read_dir(temp_dir.path())?.count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_cached() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        let titled_number = RfcNum::new(2119).expect("its non-zero");
+        let untitled_number = RfcNum::new(9).expect("its non-zero");
+        cache.cache_rfc(
+            titled_number,
+            "Network Working Group                                 S. \
+             Bradner\nRequest for Comments: 2119                     Harvard \
+             University\n\n\n        Key words for use in RFCs to Indicate \
+             Requirement Levels\n",
+        )?;
+        cache.cache_rfc(untitled_number, "")?;
+        cache.cache_index("index")?;
+
+        assert_eq!(
+            cache.list_cached()?,
+            [
+                (untitled_number, None),
+                (
+                    titled_number,
+                    Some(
+                        "Key words for use in RFCs to Indicate Requirement \
+                         Levels"
+                            .into()
+                    )
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_etag_of_cached_form() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        let rfc_number = RfcNum::new(8446).expect("its non-zero");
+        cache.cache_rfc(rfc_number, "TLS 1.3")?;
+        cache.cache_etag(rfc_number, Some("\"v1\""))?;
+        assert_eq!(cache.get_etag(rfc_number).as_deref(), Some("\"v1\""));
+
+        // Doesn't apply to a copy cached without the page breaks
+        let page_break_cache = cache.clone().with_original_text(false, true);
+        assert_eq!(page_break_cache.get_etag(rfc_number), None);
+
+        cache.cache_etag(rfc_number, None)?;
+        assert_eq!(cache.get_etag(rfc_number), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cached_entries() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        let rfc = |number| RfcNum::new(number).expect("its non-zero");
+        cache.cache_rfc(rfc(8446), "TLS 1.3")?;
+        cache.cache_etag(rfc(8446), Some("\"v1\""))?;
+        cache.cache_rfc(rfc(793), "TCP")?;
+        cache.cache_index("index")?;
+        fs::write(temp_dir.path().join("notes.txt"), "stray")?;
+        fs::create_dir(temp_dir.path().join("nested"))?;
+
+        let entries = cache.cached_entries()?;
+        let files: Vec<&CachedFile> = entries
+            .iter()
+            .map(|entry| &entry.file)
+            .collect();
+        assert_eq!(
+            files,
+            [
+                &CachedFile::Index,
+                &CachedFile::Rfc(rfc(793)),
+                &CachedFile::Rfc(rfc(8446)),
+                &CachedFile::Unknown("notes.txt".into()),
+            ]
+        );
+        assert_eq!(entries[2].size, 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reading_positions() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        let rfc = |number| RfcNum::new(number).expect("its non-zero");
+
+        assert_eq!(cache.load_position(rfc(2616))?, None);
+
+        cache.save_position(rfc(2616), 120)?;
+        cache.save_position(rfc(8446), 7)?;
+        cache.save_position(rfc(2616), 300)?;
+        assert_eq!(cache.load_position(rfc(2616))?, Some(300));
+        assert_eq!(cache.load_position(rfc(8446))?, Some(7));
+
+        // The positions are not a stray file
+        assert!(cache.cached_entries()?.is_empty());
+
+        let read_only = RfcCache::read_only(temp_dir.path());
+        assert!(read_only.save_position(rfc(1), 1).is_err());
+        assert_eq!(read_only.load_position(rfc(8446))?, Some(7));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bookmarks() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        let rfc = |number| RfcNum::new(number).expect("its non-zero");
+
+        assert!(cache.load_bookmarks(rfc(2616))?.is_empty());
+
+        cache.save_bookmarks(rfc(2616), &[3, 40])?;
+        cache.save_bookmarks(rfc(8446), &[1])?;
+        assert_eq!(cache.load_bookmarks(rfc(2616))?, [3, 40]);
+
+        cache.save_bookmarks(rfc(2616), &[])?;
+        assert!(cache.load_bookmarks(rfc(2616))?.is_empty());
+        assert_eq!(cache.load_bookmarks(rfc(8446))?, [1]);
+        assert!(cache.cached_entries()?.is_empty());
+
+        Ok(())
+    }
 }
@@ -1,35 +1,431 @@
 //! RFC client for fetching documents.
 //!
 //! Manages network requests to the RFC Editor's website.
-use std::io::Read as _;
+use std::error::Error;
+use std::io::{self, Read};
 use std::time::Duration;
+use std::{fmt, thread};
 
-use anyhow::{Context as _, Result};
+use anyhow::{Context as _, Result, bail, ensure};
 use log::debug;
-use ureq::Agent;
 use ureq::config::Config;
+use ureq::http::header::{
+    CONTENT_LENGTH, CONTENT_TYPE, ETAG, HeaderName, IF_NONE_MATCH,
+    LAST_MODIFIED,
+};
+use ureq::http::{HeaderMap, Response, StatusCode};
 use ureq::tls::{TlsConfig, TlsProvider};
+use ureq::{Agent, Body, Proxy, ResponseExt as _};
 
-use crate::types::RfcNum;
+use crate::types::{DocumentText, RfcNum};
 
 /// Base URL for fetching RFC documents in TXT format.
 const RFC_BASE_URL: &str = "https://www.rfc-editor.org/rfc/rfc";
 /// Specific URL for fetching the RFC index.
 const RFC_INDEX_URL: &str = "https://www.rfc-editor.org/rfc-index.txt";
+/// Status code of missing documents.
+const NOT_FOUND_STATUS: u16 = 404;
+/// Status code of requests the server timed out on.
+const REQUEST_TIMEOUT_STATUS: u16 = 408;
+/// Status code of requests refused for being too frequent.
+const TOO_MANY_REQUESTS_STATUS: u16 = 429;
+/// First status code of the server errors.
+const SERVER_ERROR_STATUS: u16 = 500;
+/// Number of bytes read between the progress reports of downloads.
+pub const PROGRESS_INTERVAL: u64 = 64 * 1024;
+/// Timeout of each request unless overridden.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// User-Agent sent unless overridden, identifying the app to the server.
+pub const DEFAULT_USER_AGENT: &str =
+    concat!("rfc_reader/", env!("CARGO_PKG_VERSION"));
+/// TLS backend used unless overridden, rustls if its feature is enabled.
+pub const DEFAULT_TLS_PROVIDER: TlsProvider = if cfg!(feature = "rustls")
+{
+    TlsProvider::Rustls
+}
+else
+{
+    TlsProvider::NativeTls
+};
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+compile_error!("Either the `native-tls` or the `rustls` feature is required");
+
+/// Errors specific to fetching RFCs.
+///
+/// Returned wrapped in [`anyhow::Error`], use `downcast_ref` to inspect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchError
+{
+    /// The server has no such RFC, either by status or by serving an HTML
+    /// error page in place of the text.
+    NotFound(RfcNum),
+    /// The RFC isn't cached and network access is disabled.
+    Offline(RfcNum),
+    /// The server answered with an empty or whitespace-only body.
+    Empty(RfcNum),
+    /// The server doesn't have the RFC in the requested format.
+    FormatNotFound(RfcNum, RfcFormat),
+}
+
+impl fmt::Display for FetchError
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Self::NotFound(rfc_number) =>
+            {
+                write!(formatter, "RFC {rfc_number} was not found")
+            },
+            Self::Offline(rfc_number) => write!(
+                formatter,
+                "Unable to access RFC {rfc_number} - network access disabled \
+                 in offline mode and RFC not cached locally"
+            ),
+            Self::Empty(rfc_number) =>
+            {
+                write!(formatter, "RFC {rfc_number} returned no content")
+            },
+            Self::FormatNotFound(rfc_number, format) => write!(
+                formatter,
+                "RFC {rfc_number} is not available as {format}"
+            ),
+        }
+    }
+}
+
+impl Error for FetchError
+{
+}
+
+impl FetchError
+{
+    /// Gets the exit code of the failure for scripts.
+    ///
+    /// # Returns
+    ///
+    /// The exit code of the failure class.
+    #[must_use]
+    pub const fn exit_code(self) -> u8
+    {
+        match self
+        {
+            Self::NotFound(_) | Self::FormatNotFound(..) => EXIT_NOT_FOUND,
+            Self::Offline(_) => EXIT_OFFLINE,
+            Self::Empty(_) => EXIT_EMPTY,
+        }
+    }
+}
+
+/// Exit code of failures without a more specific one.
+pub const EXIT_FAILURE: u8 = 1;
+/// Exit code of an RFC missing on the server.
+pub const EXIT_NOT_FOUND: u8 = 2;
+/// Exit code of a failed request, like a timeout or a server error.
+pub const EXIT_NETWORK: u8 = 3;
+/// Exit code of an RFC that isn't cached in offline mode.
+pub const EXIT_OFFLINE: u8 = 4;
+/// Exit code of an RFC served without any content.
+pub const EXIT_EMPTY: u8 = 5;
+
+/// Gets the exit code of an error for scripts.
+///
+/// # Arguments
+///
+/// * `error` - The error the app failed with
+///
+/// # Returns
+///
+/// The exit code of the first fetch or network error in the chain, or
+/// [`EXIT_FAILURE`] for any other error.
+#[must_use]
+pub fn exit_code(error: &anyhow::Error) -> u8
+{
+    error
+        .chain()
+        .find_map(|cause| {
+            if let Some(fetch_error) = cause.downcast_ref::<FetchError>()
+            {
+                Some(fetch_error.exit_code())
+            }
+            else
+            {
+                cause
+                    .is::<ureq::Error>()
+                    .then_some(EXIT_NETWORK)
+            }
+        })
+        .unwrap_or(EXIT_FAILURE)
+}
+
+/// Formats the RFC Editor serves RFCs in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RfcFormat
+{
+    /// Plain text, the format shown by the reader.
+    #[default]
+    Txt,
+    /// HTML rendering of the RFC.
+    Html,
+    /// The RFCXML source of the RFC, for RFCs published from it.
+    Xml,
+    /// PDF rendering of the RFC, fetched as bytes.
+    Pdf,
+}
+
+impl RfcFormat
+{
+    /// Gets the file extension of the format.
+    ///
+    /// # Returns
+    ///
+    /// The extension without the leading dot.
+    #[must_use]
+    pub const fn extension(self) -> &'static str
+    {
+        match self
+        {
+            Self::Txt => "txt",
+            Self::Html => "html",
+            Self::Xml => "xml",
+            Self::Pdf => "pdf",
+        }
+    }
+}
+
+impl fmt::Display for RfcFormat
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Self::Txt => formatter.write_str("text"),
+            Self::Html => formatter.write_str("HTML"),
+            Self::Xml => formatter.write_str("XML"),
+            Self::Pdf => formatter.write_str("PDF"),
+        }
+    }
+}
+
+/// Builds the URL of an RFC in a format.
+///
+/// # Arguments
+///
+/// * `rfc_number` - The number of the RFC
+/// * `format` - The format of the document
+///
+/// # Returns
+///
+/// The URL of the document on the RFC Editor's website.
+fn rfc_url(rfc_number: RfcNum, format: RfcFormat) -> String
+{
+    format!("{RFC_BASE_URL}{rfc_number}.{}", format.extension())
+}
+
+/// How failed requests are retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy
+{
+    /// Number of retries after the first attempt.
+    pub retries: u32,
+    /// Delay before the first retry, doubled on each following one.
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy
+{
+    /// Policy that gives up on the first failure.
+    pub const NONE: Self = Self {
+        retries: 0,
+        base_delay: Duration::ZERO,
+    };
+
+    /// Gets the delay before a retry.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - The number of the retry, starting from 0
+    ///
+    /// # Returns
+    ///
+    /// The base delay doubled for each earlier retry.
+    #[must_use]
+    pub const fn delay(&self, retry: u32) -> Duration
+    {
+        self.base_delay
+            .saturating_mul(2_u32.saturating_pow(retry))
+    }
+}
+
+impl Default for RetryPolicy
+{
+    fn default() -> Self
+    {
+        Self {
+            retries: 3,
+            base_delay: Duration::from_millis(200),
+        }
+    }
+}
+
+/// Checks whether a failed request may succeed if retried.
+///
+/// # Arguments
+///
+/// * `error` - The error of the request
+///
+/// # Returns
+///
+/// `true` for connection failures and server errors, `false` for permanent
+/// failures like a missing document. Timeouts aren't retried, each attempt
+/// would wait out the whole timeout again.
+const fn is_transient(error: &ureq::Error) -> bool
+{
+    match error
+    {
+        ureq::Error::StatusCode(status) => matches!(
+            *status,
+            REQUEST_TIMEOUT_STATUS |
+                TOO_MANY_REQUESTS_STATUS |
+                SERVER_ERROR_STATUS..
+        ),
+        ureq::Error::Io(_) |
+        ureq::Error::HostNotFound |
+        ureq::Error::ConnectionFailed |
+        ureq::Error::BodyStalled => true,
+        _ => false,
+    }
+}
+
+/// An RFC fetched along with the details of the response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedRfc
+{
+    /// The RFC content as a text.
+    pub content: Box<str>,
+    /// The URL the content was served from, after following redirects.
+    pub url: Box<str>,
+    /// The HTTP status code of the response.
+    pub status: u16,
+    /// The length of the body as reported by the server.
+    pub content_length: Option<u64>,
+    /// The entity tag of the content, for conditional requests.
+    pub etag: Option<Box<str>>,
+    /// When the content was last modified, as formatted by the server.
+    pub last_modified: Option<Box<str>>,
+}
+
+/// Source RFCs can be fetched from.
+///
+/// Implemented by [`RfcClient`], allows substituting the network in tests.
+pub trait RfcSource
+{
+    /// Fetch a specific RFC.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The number of the RFC to fetch.
+    ///
+    /// # Returns
+    ///
+    /// The RFC content as a text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RFC is not found or unavailable.
+    fn fetch_rfc(&self, rfc_number: RfcNum) -> Result<Box<str>>;
+
+    /// Fetch a specific RFC unless the copy at hand is current.
+    ///
+    /// Sources without entity tags always fetch the RFC.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The number of the RFC to fetch
+    /// * `etag` - The entity tag of the copy at hand, if any
+    ///
+    /// # Returns
+    ///
+    /// Whether the RFC changed since the entity tag, with its content and
+    /// new entity tag if so.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RFC is not found or unavailable.
+    fn fetch_rfc_if_modified(
+        &self,
+        rfc_number: RfcNum,
+        _etag: Option<&str>,
+    ) -> Result<ConditionalFetch>
+    {
+        self.fetch_rfc(rfc_number)
+            .map(|content| ConditionalFetch::Updated(content, None))
+    }
+}
+
+/// Result of fetching an RFC conditionally.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConditionalFetch
+{
+    /// The copy at hand is current.
+    NotModified,
+    /// The RFC changed, with its content and entity tag.
+    Updated(Box<str>, Option<String>),
+}
 
 /// Client for fetching RFCs.
 ///
 /// This client is used to fetch RFCs from the RFC Editor's website.
 /// It is responsible for fetching the RFC index and RFCs.
+#[derive(Clone)]
 pub struct RfcClient
 {
     /// HTTP client for making requests to the the website.
     client: Agent,
+    /// Whether the form feeds between pages are kept in fetched RFCs.
+    keeps_page_breaks: bool,
+    /// Whether fetched documents are returned exactly as served.
+    keeps_original_text: bool,
+    /// How failed requests are retried.
+    retry_policy: RetryPolicy,
 }
 
 impl RfcClient
 {
-    /// Create a new RFC client.
+    /// Create a new RFC client using the default TLS backend.
+    ///
+    /// Requests go through the proxy of the `HTTP_PROXY`, `HTTPS_PROXY` or
+    /// `ALL_PROXY` environment variables if set, except for the hosts listed
+    /// in `NO_PROXY`.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The timeout of each request
+    /// * `user_agent` - The User-Agent sent with the RFC and index requests
+    ///
+    /// # Returns
+    ///
+    /// A new RFC client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be created.
+    #[must_use]
+    pub fn new(duration: Duration, user_agent: &str) -> Self
+    {
+        Self::with_tls_provider(duration, user_agent, DEFAULT_TLS_PROVIDER)
+    }
+
+    /// Create a new RFC client using the given TLS backend.
+    ///
+    /// Requests fail if the backend's feature isn't enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The timeout of each request
+    /// * `user_agent` - The User-Agent sent with the RFC and index requests
+    /// * `provider` - The TLS backend of the requests
     ///
     /// # Returns
     ///
@@ -39,22 +435,142 @@ impl RfcClient
     ///
     /// Panics if the HTTP client cannot be created.
     #[must_use]
-    pub fn new(duration: Duration) -> Self
+    pub fn with_tls_provider(
+        duration: Duration,
+        user_agent: &str,
+        provider: TlsProvider,
+    ) -> Self
+    {
+        Self::build(duration, user_agent, provider, Proxy::try_from_env())
+    }
+
+    /// Create a new RFC client sending the requests through a proxy.
+    ///
+    /// The proxy overrides the one of the `HTTP_PROXY`, `HTTPS_PROXY` and
+    /// `ALL_PROXY` environment variables, and `NO_PROXY` isn't applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The timeout of each request
+    /// * `proxy_url` - The URL of the proxy, like `http://proxy:8080`
+    ///
+    /// # Returns
+    ///
+    /// A new RFC client with the default User-Agent and TLS backend.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proxy URL is invalid.
+    pub fn with_proxy(duration: Duration, proxy_url: &str) -> Result<Self>
+    {
+        let proxy = Proxy::new(proxy_url)
+            .with_context(|| format!("Invalid proxy URL '{proxy_url}'"))?;
+
+        Ok(Self::build(
+            duration,
+            DEFAULT_USER_AGENT,
+            DEFAULT_TLS_PROVIDER,
+            Some(proxy),
+        ))
+    }
+
+    /// Create a new RFC client from all of its options.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The timeout of each request
+    /// * `user_agent` - The User-Agent sent with the RFC and index requests
+    /// * `provider` - The TLS backend of the requests
+    /// * `proxy` - The proxy to send the requests through, if any
+    ///
+    /// # Returns
+    ///
+    /// A new RFC client.
+    fn build(
+        duration: Duration,
+        user_agent: &str,
+        provider: TlsProvider,
+        proxy: Option<Proxy>,
+    ) -> Self
     {
         let config = Config::builder()
             .timeout_global(Some(duration))
+            .user_agent(user_agent)
             .tls_config(
                 TlsConfig::builder()
-                    .provider(TlsProvider::NativeTls)
+                    .provider(provider)
                     .build(),
             )
+            .proxy(proxy)
             .build();
 
         Self {
             client: config.new_agent(),
+            keeps_page_breaks: false,
+            keeps_original_text: false,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Create a new RFC client retrying failed requests by the given policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The timeout of each request
+    /// * `policy` - How failed requests are retried
+    ///
+    /// # Returns
+    ///
+    /// A new RFC client with the default User-Agent and TLS backend.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be created.
+    #[must_use]
+    pub fn with_retry(duration: Duration, policy: RetryPolicy) -> Self
+    {
+        Self {
+            retry_policy: policy,
+            ..Self::new(duration, DEFAULT_USER_AGENT)
         }
     }
 
+    /// Sets whether the form feeds between pages are kept in fetched RFCs.
+    ///
+    /// # Arguments
+    ///
+    /// * `keeps_page_breaks` - Whether to keep the form feeds
+    ///
+    /// # Returns
+    ///
+    /// The client with the option set.
+    #[must_use]
+    pub const fn with_page_breaks(mut self, keeps_page_breaks: bool) -> Self
+    {
+        self.keeps_page_breaks = keeps_page_breaks;
+        self
+    }
+
+    /// Sets whether fetched documents are returned exactly as served.
+    ///
+    /// They're still checked like normalized ones, and can be normalized for
+    /// display with [`normalize_body`].
+    ///
+    /// # Arguments
+    ///
+    /// * `keeps_original_text` - Whether to skip the normalization
+    ///
+    /// # Returns
+    ///
+    /// The client with the option set.
+    #[must_use]
+    pub const fn with_original_text(mut self, keeps_original_text: bool)
+    -> Self
+    {
+        self.keeps_original_text = keeps_original_text;
+        self
+    }
+
     /// Fetch a specific RFC.
     ///
     /// # Arguments
@@ -67,66 +583,461 @@ impl RfcClient
     ///
     /// # Errors
     ///
-    /// Returns an error if the RFC is not found or unavailable.
+    /// Returns an error if the RFC is not found or unavailable, a
+    /// [`FetchError::NotFound`] if the server reports it missing.
     pub fn fetch_rfc(&self, rfc_number: RfcNum) -> Result<Box<str>>
     {
-        // RFC documents are available in TXT format
-        let rfc_url = format!("{RFC_BASE_URL}{rfc_number}.txt");
+        self.fetch_rfc_format(rfc_number, RfcFormat::Txt)
+    }
 
-        let response = self
-            .client
-            .get(rfc_url)
-            .call()
-            .with_context(|| format!("Failed to fetch RFC {rfc_number}"))?;
+    /// Fetch a specific RFC in a text format.
+    ///
+    /// Only plain text is normalized like [`Self::fetch_rfc`] does, the other
+    /// formats are returned as served.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The number of the RFC to fetch
+    /// * `format` - The format to fetch, use [`Self::fetch_rfc_bytes`] for
+    ///   [`RfcFormat::Pdf`]
+    ///
+    /// # Returns
+    ///
+    /// The RFC content in the format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RFC is unavailable or not valid UTF-8, a
+    /// [`FetchError::FormatNotFound`] if the server has no such document, or
+    /// if the format is binary.
+    pub fn fetch_rfc_format(
+        &self,
+        rfc_number: RfcNum,
+        format: RfcFormat,
+    ) -> Result<Box<str>>
+    {
+        match format
+        {
+            RfcFormat::Txt => self
+                .fetch_rfc_detailed(rfc_number)
+                .map(|fetched| fetched.content),
+            RfcFormat::Pdf =>
+            {
+                bail!("{format} is binary, fetch RFC {rfc_number} as bytes")
+            },
+            RfcFormat::Html | RfcFormat::Xml =>
+            {
+                let bytes = self.fetch_rfc_bytes(rfc_number, format)?;
+
+                String::from_utf8(bytes)
+                    .map(String::into_boxed_str)
+                    .with_context(|| {
+                        format!("RFC {rfc_number} {format} is not valid UTF-8")
+                    })
+            },
+        }
+    }
+
+    /// Fetch a specific RFC in a format as served, like PDF.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The number of the RFC to fetch
+    /// * `format` - The format to fetch
+    ///
+    /// # Returns
+    ///
+    /// The bytes of the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RFC is unavailable, a
+    /// [`FetchError::FormatNotFound`] if the server has no such document.
+    pub fn fetch_rfc_bytes(
+        &self,
+        rfc_number: RfcNum,
+        format: RfcFormat,
+    ) -> Result<Vec<u8>>
+    {
+        let response = match self.get(&rfc_url(rfc_number, format))
+        {
+            Ok(response) => response,
+            Err(ureq::Error::StatusCode(NOT_FOUND_STATUS)) =>
+            {
+                return Err(
+                    FetchError::FormatNotFound(rfc_number, format).into()
+                );
+            },
+            Err(error) =>
+            {
+                return Err(error).with_context(|| {
+                    format!("Failed to fetch RFC {rfc_number} as {format}")
+                });
+            },
+        };
 
         debug!("Got response: {response:?}");
 
-        let mut response_body = String::new();
+        let mut bytes = Vec::new();
         response
             .into_body()
             .into_reader()
-            .read_to_string(&mut response_body)
+            .read_to_end(&mut bytes)
             .with_context(|| {
-                format!("Failed to read RFC {rfc_number} content")
+                format!("Failed to read RFC {rfc_number} {format} content")
             })?;
 
-        Ok(
-            // Remove the unnecesary form feed.
-            response_body
-                .trim()
-                .replace('\x0c', "")
-                .into_boxed_str(),
-        )
+        Ok(bytes)
     }
 
-    /// Fetch the RFC index.
+    /// Fetch a specific RFC along with the details of the response.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The number of the RFC to fetch.
     ///
     /// # Returns
     ///
-    /// The RFC index as a text.
+    /// The RFC content with the final URL, status and caching headers of the
+    /// response.
     ///
     /// # Errors
     ///
-    /// Returns an error if the RFC index is not available or if the request
-    /// fails.
-    pub fn fetch_rfc_index(&self) -> Result<Box<str>>
+    /// Returns an error if the RFC is not found or unavailable, a
+    /// [`FetchError::NotFound`] if the server reports it missing.
+    pub fn fetch_rfc_detailed(&self, rfc_number: RfcNum) -> Result<FetchedRfc>
     {
+        self.fetch_rfc_detailed_if_modified(rfc_number, None)?
+            .with_context(|| {
+                format!(
+                    "RFC {rfc_number} was reported unmodified without an ETag"
+                )
+            })
+    }
+
+    /// Fetch a specific RFC unless the copy at hand is current.
+    ///
+    /// Sends the entity tag as `If-None-Match`, so the server answers with
+    /// `304 Not Modified` instead of the content if it didn't change.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The number of the RFC to fetch
+    /// * `etag` - The entity tag of the copy at hand, the RFC is always fetched
+    ///   without one
+    ///
+    /// # Returns
+    ///
+    /// [`ConditionalFetch::NotModified`] if the copy is current, otherwise
+    /// the content with its new entity tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RFC is unavailable, a [`FetchError::NotFound`]
+    /// if the server reports it missing.
+    pub fn fetch_rfc_if_modified(
+        &self,
+        rfc_number: RfcNum,
+        etag: Option<&str>,
+    ) -> Result<ConditionalFetch>
+    {
+        let fetched = self.fetch_rfc_detailed_if_modified(rfc_number, etag)?;
+
+        Ok(fetched.map_or(ConditionalFetch::NotModified, |fetched| {
+            ConditionalFetch::Updated(
+                fetched.content,
+                fetched.etag.map(String::from),
+            )
+        }))
+    }
+
+    /// Fetch a specific RFC along with the details of the response, unless
+    /// the copy at hand is current.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The number of the RFC to fetch
+    /// * `etag` - The entity tag of the copy at hand, if any
+    ///
+    /// # Returns
+    ///
+    /// The fetched RFC, or `None` if the server answered with
+    /// `304 Not Modified`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RFC is not found or unavailable, a
+    /// [`FetchError::NotFound`] if the server reports it missing.
+    fn fetch_rfc_detailed_if_modified(
+        &self,
+        rfc_number: RfcNum,
+        etag: Option<&str>,
+    ) -> Result<Option<FetchedRfc>>
+    {
+        let url = rfc_url(rfc_number, RfcFormat::Txt);
+        let response = match self.get_if_none_match(&url, etag)
+        {
+            Ok(response) if response.status() == StatusCode::NOT_MODIFIED =>
+            {
+                debug!("RFC {rfc_number} was not modified");
+                return Ok(None);
+            },
+            Ok(response) => response,
+            Err(ureq::Error::StatusCode(NOT_FOUND_STATUS)) =>
+            {
+                return Err(FetchError::NotFound(rfc_number).into());
+            },
+            Err(error) =>
+            {
+                return Err(error).with_context(|| {
+                    format!("Failed to fetch RFC {rfc_number}")
+                });
+            },
+        };
+
+        debug!("Got response: {response:?}");
+
+        let url = response
+            .get_uri()
+            .to_string()
+            .into_boxed_str();
+        let status = response.status().as_u16();
+        let headers = response.headers();
+        let content_length = header_value(headers, &CONTENT_LENGTH)
+            .and_then(|length| length.parse().ok());
+        let etag = header_value(headers, &ETAG).map(Box::from);
+        let last_modified =
+            header_value(headers, &LAST_MODIFIED).map(Box::from);
+
+        let mut response_body = String::new();
+        response
+            .into_body()
+            .into_reader()
+            .read_to_string(&mut response_body)
+            .with_context(|| {
+                format!("Failed to read RFC {rfc_number} content")
+            })?;
+
+        let content =
+            parse_rfc_body(rfc_number, &response_body, self.keeps_page_breaks)?;
+
+        Ok(Some(FetchedRfc {
+            content: if self.keeps_original_text
+            {
+                response_body.into_boxed_str()
+            }
+            else
+            {
+                content
+            },
+            url,
+            status,
+            content_length,
+            etag,
+            last_modified,
+        }))
+    }
+
+    /// Fetch a plain text document from a URL, like a draft hosted elsewhere.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The `http` or `https` URL of the document
+    ///
+    /// # Returns
+    ///
+    /// The document content as a text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL has another scheme, the request fails, or
+    /// the server responds with something other than non-empty plain text.
+    pub fn fetch_url(&self, url: &str) -> Result<Box<str>>
+    {
+        validate_url(url)?;
+
         let response = self
-            .client
-            .get(RFC_INDEX_URL)
-            .call()
-            .context("Failed to fetch RFC index")?;
+            .get(url)
+            .with_context(|| format!("Failed to fetch {url}"))?;
 
         debug!("Got response: {response:?}");
 
+        if let Some(content_type) =
+            header_value(response.headers(), &CONTENT_TYPE)
+        {
+            ensure!(
+                is_plain_text(content_type),
+                "{url} is not plain text but {content_type}"
+            );
+        }
+
         let mut response_body = String::new();
         response
             .into_body()
             .into_reader()
             .read_to_string(&mut response_body)
-            .context("Failed to read RFC index content")?;
+            .with_context(|| format!("Failed to read content of {url}"))?;
+
+        if looks_like_html(&response_body)
+        {
+            bail!("{url} served an HTML page instead of plain text");
+        }
 
-        Ok(response_body.into_boxed_str())
+        let content = normalize_body(&response_body, self.keeps_page_breaks);
+        ensure!(!content.is_empty(), "{url} returned no content");
+
+        if self.keeps_original_text
+        {
+            return Ok(response_body.into_boxed_str());
+        }
+
+        Ok(content)
+    }
+
+    /// Fetch the RFC index.
+    ///
+    /// # Returns
+    ///
+    /// The RFC index as a text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RFC index is not available or if the request
+    /// fails.
+    pub fn fetch_rfc_index(&self) -> Result<Box<str>>
+    {
+        self.fetch_rfc_index_with_progress(|_, _| {})
+    }
+
+    /// Fetch the RFC index, reporting the progress of the download.
+    ///
+    /// # Arguments
+    ///
+    /// * `on_progress` - Called with the bytes read so far and the length
+    ///   reported by the server, every [`PROGRESS_INTERVAL`] bytes and once the
+    ///   download completes
+    ///
+    /// # Returns
+    ///
+    /// The RFC index as a text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RFC index is not available, the request fails
+    /// or the index is not valid UTF-8.
+    pub fn fetch_rfc_index_with_progress(
+        &self,
+        on_progress: impl FnMut(u64, Option<u64>),
+    ) -> Result<Box<str>>
+    {
+        let response = self
+            .get(RFC_INDEX_URL)
+            .context("Failed to fetch RFC index")?;
+
+        debug!("Got response: {response:?}");
+
+        let total = header_value(response.headers(), &CONTENT_LENGTH)
+            .and_then(|length| length.parse().ok());
+
+        let bytes = read_with_progress(
+            response.into_body().into_reader(),
+            total,
+            on_progress,
+        )
+        .context("Failed to read RFC index content")?;
+
+        String::from_utf8(bytes)
+            .map(String::into_boxed_str)
+            .context("RFC index is not valid UTF-8")
+    }
+
+    /// Sends a GET request, retrying transient failures by the policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to request
+    ///
+    /// # Returns
+    ///
+    /// The response of the first successful attempt.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the last attempt, or the first permanent one like
+    /// a missing document.
+    fn get(&self, url: &str) -> Result<Response<Body>, ureq::Error>
+    {
+        self.get_if_none_match(url, None)
+    }
+
+    /// Sends a GET request like [`Self::get`], conditional on an entity tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to request
+    /// * `etag` - The entity tag of the copy at hand, sent as `If-None-Match`
+    ///
+    /// # Returns
+    ///
+    /// The response of the first successful attempt, `304 Not Modified` if
+    /// the copy is current.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the last attempt, or the first permanent one like
+    /// a missing document.
+    fn get_if_none_match(
+        &self,
+        url: &str,
+        etag: Option<&str>,
+    ) -> Result<Response<Body>, ureq::Error>
+    {
+        let mut retry = 0;
+
+        loop
+        {
+            let request = self.client.get(url);
+            let request = match etag
+            {
+                Some(etag) => request.header(IF_NONE_MATCH, etag),
+                None => request,
+            };
+
+            match request.call()
+            {
+                Err(error)
+                    if retry < self.retry_policy.retries &&
+                        is_transient(&error) =>
+                {
+                    let delay = self.retry_policy.delay(retry);
+                    retry = retry.saturating_add(1);
+                    debug!(
+                        "Request to {url} failed: {error}, retrying in \
+                         {delay:?} ({retry}/{})",
+                        self.retry_policy.retries
+                    );
+                    thread::sleep(delay);
+                },
+                result => return result,
+            }
+        }
+    }
+}
+
+impl RfcSource for RfcClient
+{
+    fn fetch_rfc(&self, rfc_number: RfcNum) -> Result<Box<str>>
+    {
+        Self::fetch_rfc(self, rfc_number)
+    }
+
+    fn fetch_rfc_if_modified(
+        &self,
+        rfc_number: RfcNum,
+        etag: Option<&str>,
+    ) -> Result<ConditionalFetch>
+    {
+        Self::fetch_rfc_if_modified(self, rfc_number, etag)
     }
 }
 
@@ -134,6 +1045,545 @@ impl Default for RfcClient
 {
     fn default() -> Self
     {
-        Self::new(Duration::from_secs(30))
+        Self::new(DEFAULT_TIMEOUT, DEFAULT_USER_AGENT)
+    }
+}
+
+/// Reads a body to the end, reporting the progress along the way.
+///
+/// # Arguments
+///
+/// * `reader` - The body to read
+/// * `total` - The length of the body, if known
+/// * `on_progress` - Called with the bytes read so far and `total`, every
+///   [`PROGRESS_INTERVAL`] bytes and once the end is reached
+///
+/// # Returns
+///
+/// The bytes of the body.
+///
+/// # Errors
+///
+/// Returns an error if reading fails.
+fn read_with_progress(
+    mut reader: impl Read,
+    total: Option<u64>,
+    mut on_progress: impl FnMut(u64, Option<u64>),
+) -> io::Result<Vec<u8>>
+{
+    let mut bytes = Vec::new();
+    let mut chunk = [0; 16 * 1024];
+    let mut reported = 0;
+
+    loop
+    {
+        let read = match reader.read(&mut chunk)
+        {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted =>
+            {
+                continue;
+            },
+            Err(error) => return Err(error),
+        };
+        bytes.extend_from_slice(chunk.get(..read).unwrap_or_default());
+
+        let progress = bytes.len() as u64;
+        if progress.saturating_sub(reported) >= PROGRESS_INTERVAL
+        {
+            on_progress(progress, total);
+            reported = progress;
+        }
+    }
+
+    on_progress(bytes.len() as u64, total);
+
+    Ok(bytes)
+}
+
+/// Gets the value of a header if it's valid text.
+///
+/// # Arguments
+///
+/// * `headers` - The headers of the response
+/// * `name` - The name of the header
+///
+/// # Returns
+///
+/// The value of the header, or `None` if missing or not valid text.
+fn header_value<'headers>(
+    headers: &'headers HeaderMap,
+    name: &HeaderName,
+) -> Option<&'headers str>
+{
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+}
+
+/// Extracts the RFC text from a response body.
+///
+/// # Arguments
+///
+/// * `rfc_number` - The RFC the body was fetched for
+/// * `body` - The response body
+/// * `keeps_page_breaks` - Whether to keep the form feeds between pages
+///
+/// # Returns
+///
+/// The trimmed text, without form feeds unless they're kept.
+///
+/// # Errors
+///
+/// Returns a [`FetchError::NotFound`] for HTML error pages and a
+/// [`FetchError::Empty`] for bodies without any text, so neither gets cached
+/// as the RFC.
+fn parse_rfc_body(
+    rfc_number: RfcNum,
+    body: &str,
+    keeps_page_breaks: bool,
+) -> Result<Box<str>>
+{
+    if looks_like_html(body)
+    {
+        return Err(FetchError::NotFound(rfc_number).into());
+    }
+
+    // Form feeds count as whitespace, so blank bodies are trimmed to nothing
+    let content = normalize_body(body, keeps_page_breaks);
+
+    if content.is_empty()
+    {
+        return Err(FetchError::Empty(rfc_number).into());
+    }
+
+    Ok(content)
+}
+
+/// Trims a response body, removing the form feeds unless they're kept.
+///
+/// # Arguments
+///
+/// * `body` - The response body
+/// * `keeps_page_breaks` - Whether to keep the form feeds between pages
+///
+/// # Returns
+///
+/// The normalized text.
+#[must_use]
+pub fn normalize_body(body: &str, keeps_page_breaks: bool) -> Box<str>
+{
+    if keeps_page_breaks
+    {
+        body.trim().into()
+    }
+    else
+    {
+        // Remove the unnecesary form feed.
+        body.trim()
+            .replace('\x0c', "")
+            .into_boxed_str()
+    }
+}
+
+/// Normalizes a document kept as served, like fetched RFCs are otherwise.
+///
+/// # Arguments
+///
+/// * `text` - The document text
+/// * `keeps_page_breaks` - Whether to keep the form feeds between pages
+///
+/// # Returns
+///
+/// The normalized text, the text itself if it needs no changes.
+#[must_use]
+pub fn normalize_document(
+    text: DocumentText,
+    keeps_page_breaks: bool,
+) -> DocumentText
+{
+    let normalized = normalize_body(&text, keeps_page_breaks);
+    // Keeps the memory map of a cached file
+    if *normalized == *text
+    {
+        return text;
+    }
+
+    normalized.into()
+}
+
+/// Checks that a URL can be fetched as a document.
+///
+/// # Arguments
+///
+/// * `url` - The URL to check
+///
+/// # Errors
+///
+/// Returns an error if the URL doesn't use the `http` or `https` scheme.
+fn validate_url(url: &str) -> Result<()>
+{
+    let scheme = url
+        .split_once("://")
+        .map(|(scheme, _)| scheme.to_ascii_lowercase());
+
+    ensure!(
+        matches!(scheme.as_deref(), Some("http" | "https")),
+        "Unsupported URL {url}, only http and https URLs can be read"
+    );
+
+    Ok(())
+}
+
+/// Checks whether a `Content-Type` header value is plain text.
+///
+/// # Arguments
+///
+/// * `content_type` - The header value, like `text/plain; charset=utf-8`
+///
+/// # Returns
+///
+/// `true` if the media type is `text/plain`.
+fn is_plain_text(content_type: &str) -> bool
+{
+    content_type
+        .split(';')
+        .next()
+        .is_some_and(|media_type| {
+            media_type
+                .trim()
+                .eq_ignore_ascii_case("text/plain")
+        })
+}
+
+/// Checks whether a response body is an HTML page rather than plain text.
+///
+/// Some mirrors serve their "not found" page with a success status.
+///
+/// # Arguments
+///
+/// * `body` - The response body
+///
+/// # Returns
+///
+/// `true` if the body starts like an HTML or XML document.
+fn looks_like_html(body: &str) -> bool
+{
+    /// Openings of markup documents, compared case-insensitively.
+    const MARKUP_PREFIXES: [&str; 4] = ["<!doctype", "<html", "<?xml", "<head"];
+
+    let start = body.trim_start();
+
+    MARKUP_PREFIXES.iter().any(|prefix| {
+        start
+            .get(..prefix.len())
+            .is_some_and(|opening| opening.eq_ignore_ascii_case(prefix))
+    })
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::io::Write as _;
+    use std::net::TcpListener;
+
+    use super::*;
+
+    #[test]
+    fn test_html_error_page_is_detected()
+    {
+        let error_page = "\n<!DOCTYPE html>\n<html><head><title>404 Not \
+                          Found</title></head><body>Not Found</body></html>";
+
+        assert!(looks_like_html(error_page));
+        assert!(looks_like_html("<HTML><BODY>Gone</BODY></HTML>"));
+        assert!(!looks_like_html(
+            "Network Working Group            J. Postel\nRequest for \
+             Comments: 791"
+        ));
+        // Markup quoted inside an RFC doesn't count
+        assert!(!looks_like_html("Abstract\n\n   <html> is an element."));
+    }
+
+    #[test]
+    fn test_empty_body_is_rejected()
+    {
+        let rfc_number = RfcNum::new(2119).expect("its non-zero");
+
+        for body in ["", "  \r\n\t", "\n\x0c\n\x0c"]
+        {
+            let error = parse_rfc_body(rfc_number, body, true)
+                .expect_err("empty body accepted");
+
+            assert_eq!(
+                error.downcast_ref::<FetchError>(),
+                Some(&FetchError::Empty(rfc_number))
+            );
+            assert_eq!(error.to_string(), "RFC 2119 returned no content");
+        }
+
+        assert_eq!(
+            parse_rfc_body(rfc_number, "\n RFC text\x0c\n", false)
+                .ok()
+                .as_deref(),
+            Some("RFC text")
+        );
+    }
+
+    #[test]
+    fn test_page_breaks_are_kept()
+    {
+        let rfc_number = RfcNum::new(2119).expect("its non-zero");
+        let body = "page 1\n\x0c\npage 2\n";
+
+        assert_eq!(
+            parse_rfc_body(rfc_number, body, false)
+                .ok()
+                .as_deref(),
+            Some("page 1\n\npage 2")
+        );
+        assert_eq!(
+            parse_rfc_body(rfc_number, body, true)
+                .ok()
+                .as_deref(),
+            Some("page 1\n\x0c\npage 2")
+        );
+    }
+
+    #[test]
+    fn test_header_value()
+    {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, "\"5f2b-abc\"".parse().expect("valid header"));
+        headers.insert(CONTENT_LENGTH, "1024".parse().expect("valid header"));
+
+        assert_eq!(header_value(&headers, &ETAG), Some("\"5f2b-abc\""));
+        assert_eq!(header_value(&headers, &CONTENT_LENGTH), Some("1024"));
+        assert_eq!(header_value(&headers, &LAST_MODIFIED), None);
+    }
+
+    #[test]
+    fn test_url_validation()
+    {
+        assert!(validate_url("https://example.com/draft-foo-00.txt").is_ok());
+        assert!(validate_url("HTTP://example.com/rfc1.txt").is_ok());
+        assert!(validate_url("ftp://example.com/rfc1.txt").is_err());
+        assert!(validate_url("example.com/rfc1.txt").is_err());
+
+        assert!(is_plain_text("text/plain; charset=utf-8"));
+        assert!(is_plain_text("Text/Plain"));
+        assert!(!is_plain_text("text/html"));
+    }
+
+    #[test]
+    fn test_tls_providers()
+    {
+        for provider in [TlsProvider::NativeTls, TlsProvider::Rustls]
+        {
+            let client = RfcClient::with_tls_provider(
+                DEFAULT_TIMEOUT,
+                DEFAULT_USER_AGENT,
+                provider,
+            );
+            let config = client.client.config();
+
+            assert_eq!(config.tls_config().provider(), provider);
+            assert_eq!(config.timeouts().global, Some(DEFAULT_TIMEOUT));
+        }
+
+        assert_eq!(
+            RfcClient::default()
+                .client
+                .config()
+                .tls_config()
+                .provider(),
+            DEFAULT_TLS_PROVIDER
+        );
+    }
+
+    #[test]
+    fn test_exit_codes()
+    {
+        let rfc_number = RfcNum::new(9999).expect("its non-zero");
+
+        let not_found = anyhow::Error::from(FetchError::NotFound(rfc_number))
+            .context("Failed to load the RFC");
+        assert_eq!(exit_code(&not_found), EXIT_NOT_FOUND);
+
+        let offline = anyhow::Error::from(FetchError::Offline(rfc_number));
+        assert_eq!(exit_code(&offline), EXIT_OFFLINE);
+
+        let network = anyhow::Error::from(ureq::Error::StatusCode(503))
+            .context("Failed to fetch the RFC");
+        assert_eq!(exit_code(&network), EXIT_NETWORK);
+
+        assert_eq!(exit_code(&anyhow::anyhow!("Bad config")), EXIT_FAILURE);
+    }
+
+    #[test]
+    fn test_rfc_format_urls()
+    {
+        let rfc_number = RfcNum::new(8446).expect("its non-zero");
+
+        assert_eq!(
+            rfc_url(rfc_number, RfcFormat::Txt),
+            "https://www.rfc-editor.org/rfc/rfc8446.txt"
+        );
+        assert_eq!(
+            rfc_url(rfc_number, RfcFormat::Xml),
+            "https://www.rfc-editor.org/rfc/rfc8446.xml"
+        );
+        assert_eq!(
+            rfc_url(rfc_number, RfcFormat::Pdf),
+            "https://www.rfc-editor.org/rfc/rfc8446.pdf"
+        );
+
+        assert_eq!(
+            FetchError::FormatNotFound(rfc_number, RfcFormat::Xml).to_string(),
+            "RFC 8446 is not available as XML"
+        );
+    }
+
+    /// Serves the statuses in order on a local port, one per connection.
+    ///
+    /// Returns the URL of the server and a handle joining to the number of
+    /// requests served.
+    fn serve_statuses(
+        statuses: &'static [u16],
+    ) -> (String, thread::JoinHandle<usize>)
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("free port");
+        let url = format!(
+            "http://{}/rfc1.txt",
+            listener.local_addr().expect("bound address")
+        );
+
+        let server = thread::spawn(move || {
+            for status in statuses
+            {
+                let (mut stream, _) = listener.accept().expect("a request");
+                // Read the request before answering it
+                let _ = stream.read(&mut [0; 1024]);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status} Status\r\nContent-Length: \
+                     2\r\nConnection: close\r\n\r\nok"
+                );
+            }
+
+            statuses.len()
+        });
+
+        (url, server)
+    }
+
+    #[test]
+    fn test_retry_policy()
+    {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.retries, 3);
+        assert_eq!(policy.delay(0), Duration::from_millis(200));
+        assert_eq!(policy.delay(2), Duration::from_millis(800));
+        assert_eq!(RetryPolicy::NONE.delay(5), Duration::ZERO);
+
+        assert!(is_transient(&ureq::Error::StatusCode(503)));
+        assert!(is_transient(&ureq::Error::StatusCode(429)));
+        assert!(is_transient(&ureq::Error::ConnectionFailed));
+        assert!(!is_transient(&ureq::Error::Timeout(ureq::Timeout::Global)));
+        assert!(!is_transient(&ureq::Error::StatusCode(404)));
+        assert!(!is_transient(&ureq::Error::StatusCode(403)));
+
+        let policy = RetryPolicy {
+            retries: 3,
+            base_delay: Duration::from_millis(1),
+        };
+        let client = RfcClient::with_retry(DEFAULT_TIMEOUT, policy);
+        assert_eq!(client.retry_policy, policy);
+
+        // Server errors are retried until one succeeds
+        let (url, server) = serve_statuses(&[503, 500, 200]);
+        assert!(client.get(&url).is_ok());
+        assert_eq!(server.join().ok(), Some(3));
+
+        // Missing documents aren't
+        let (url, server) = serve_statuses(&[404]);
+        assert!(matches!(
+            client.get(&url),
+            Err(ureq::Error::StatusCode(NOT_FOUND_STATUS))
+        ));
+        assert_eq!(server.join().ok(), Some(1));
+
+        // A current copy is not an error
+        let (url, server) = serve_statuses(&[304]);
+        let response = client.get_if_none_match(&url, Some("\"v1\""));
+        assert_eq!(
+            response
+                .ok()
+                .map(|response| response.status()),
+            Some(StatusCode::NOT_MODIFIED)
+        );
+        assert_eq!(server.join().ok(), Some(1));
+    }
+
+    #[test]
+    fn test_read_with_progress() -> io::Result<()>
+    {
+        let body = vec![b'x'; 200 * 1024];
+        let mut reports = Vec::new();
+
+        let bytes = read_with_progress(
+            body.as_slice(),
+            Some(body.len() as u64),
+            |read, total| reports.push((read, total)),
+        )?;
+
+        assert_eq!(bytes, body);
+        // Reported per interval, not per chunk, and at the end
+        assert_eq!(reports.len(), 4);
+        assert!(
+            reports
+                .windows(2)
+                .all(|pair| pair[1].0 - pair[0].0 >= PROGRESS_INTERVAL ||
+                    pair[1].0 == body.len() as u64)
+        );
+        assert_eq!(reports.last(), Some(&(200 * 1024, Some(200 * 1024))));
+
+        let mut reports = Vec::new();
+        read_with_progress(&b""[..], None, |read, total| {
+            reports.push((read, total));
+        })?;
+        assert_eq!(reports, [(0, None)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_proxy() -> Result<()>
+    {
+        let client = RfcClient::with_proxy(
+            DEFAULT_TIMEOUT,
+            "http://proxy.example:3128",
+        )?;
+        let proxy = client
+            .client
+            .config()
+            .proxy()
+            .expect("the proxy is set");
+
+        assert_eq!(proxy.host(), "proxy.example");
+        assert_eq!(proxy.port(), 3128);
+        assert!(!proxy.is_from_env());
+
+        let error = RfcClient::with_proxy(DEFAULT_TIMEOUT, "gopher://proxy:70")
+            .err()
+            .expect("invalid proxy accepted");
+        assert!(
+            error
+                .to_string()
+                .contains("gopher://proxy:70")
+        );
+
+        Ok(())
     }
 }
@@ -0,0 +1,754 @@
+//! User configuration loaded from disk.
+//!
+//! The configuration is stored as TOML in the platform specific config
+//! directory. A missing file or missing keys fall back to the defaults.
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr as _;
+
+use anyhow::{Context as _, Error, Result, ensure};
+use directories::ProjectDirs;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use toml_edit::{DocumentMut, Item, Table, TableLike};
+
+use crate::client::normalize_document;
+use crate::types::DocumentText;
+use crate::ui::theme::ThemeName;
+
+/// Name of the configuration file inside the config directory.
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Default width of the `ToC` panel as percentage of the terminal width.
+pub const DEFAULT_TOC_WIDTH: u16 = 25;
+
+/// Default interval between auto-scrolled lines in milliseconds.
+pub const DEFAULT_AUTOSCROLL_INTERVAL_MS: u64 = 1000;
+
+/// Default maximum number of search matches collected.
+pub const DEFAULT_MAX_SEARCH_MATCHES: usize = 10_000;
+
+/// Number of bytes in a MiB, the unit of the cache size limit.
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+
+/// User configuration of the application.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "Independent on/off settings of the config file"
+)]
+pub struct Config
+{
+    /// Color theme of the user interface.
+    pub theme: ThemeName,
+    /// Width of the `ToC` panel as percentage of the terminal width.
+    pub toc_width: u16,
+    /// Side of the content the `ToC` panel is shown on.
+    pub toc_position: TocPosition,
+    /// How `ToC` titles longer than the panel width are shown.
+    pub toc_title_overflow: TocTitleOverflow,
+    /// Maximum number of search matches collected, further matches are
+    /// ignored.
+    pub max_search_matches: usize,
+    /// Where jumps place their target line in the viewport.
+    pub jump_alignment: JumpAlignment,
+    /// What the position of the current search match is shown relative to.
+    pub match_percent: MatchPercent,
+    /// Whether the current line is highlighted.
+    pub highlight_current_line: bool,
+    /// Whether section titles are highlighted.
+    pub highlight_titles: bool,
+    /// Whether the terminal window title is set to the current RFC.
+    pub set_window_title: bool,
+    /// Whether a row of fuller keybinding hints is shown above the
+    /// statusbar.
+    pub show_legend: bool,
+    /// Whether the statusbar is shown.
+    pub show_statusbar: bool,
+    /// Whether the rows past the end of the document are marked with `~`.
+    pub show_end_markers: bool,
+    /// Whether the form feeds between pages are kept in fetched RFCs.
+    pub keep_page_breaks: bool,
+    /// Whether fetched RFCs are cached exactly as served, normalized only
+    /// for display.
+    pub keep_original_text: bool,
+    /// Whether the view follows the nearest match while typing a search.
+    pub search_preview: bool,
+    /// Whether the matches are highlighted while typing a search.
+    pub incremental_search: bool,
+    /// Whether cache hits and fetches are counted locally.
+    pub track_usage: bool,
+    /// Milliseconds between the lines scrolled while auto-scrolling.
+    pub autoscroll_interval_ms: u64,
+    /// Number of `ToC` entries past which the `ToC` panel is opened when a
+    /// document is loaded, never opened if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_toc_min_entries: Option<usize>,
+    /// Size in MiB the cached RFCs may take up, unlimited if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_cache_size_mb: Option<u64>,
+    /// User-Agent sent to the server, the app name and version if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<Box<str>>,
+    /// Background of the overlays, the terminal's own if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overlay_background: Option<ConfigColor>,
+    /// Color of the search matches, the theme's own if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub match_color: Option<ConfigColor>,
+    /// Fields shown in the statusbar.
+    pub statusbar: StatusbarConfig,
+}
+
+impl Default for Config
+{
+    fn default() -> Self
+    {
+        Self {
+            theme: ThemeName::default(),
+            toc_width: DEFAULT_TOC_WIDTH,
+            toc_position: TocPosition::default(),
+            toc_title_overflow: TocTitleOverflow::default(),
+            max_search_matches: DEFAULT_MAX_SEARCH_MATCHES,
+            jump_alignment: JumpAlignment::default(),
+            match_percent: MatchPercent::default(),
+            highlight_current_line: false,
+            highlight_titles: true,
+            set_window_title: true,
+            show_legend: false,
+            show_statusbar: true,
+            show_end_markers: false,
+            keep_page_breaks: false,
+            keep_original_text: false,
+            search_preview: false,
+            incremental_search: false,
+            track_usage: false,
+            autoscroll_interval_ms: DEFAULT_AUTOSCROLL_INTERVAL_MS,
+            auto_toc_min_entries: None,
+            max_cache_size_mb: None,
+            user_agent: None,
+            overlay_background: None,
+            match_color: None,
+            statusbar: StatusbarConfig::default(),
+        }
+    }
+}
+
+/// Fields shown in each section of the statusbar.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatusbarConfig
+{
+    /// Fields of the left-aligned section.
+    pub left: Vec<StatusField>,
+    /// Fields of the centered section.
+    pub middle: Vec<StatusField>,
+    /// Fields of the right-aligned section.
+    pub right: Vec<StatusField>,
+}
+
+impl Default for StatusbarConfig
+{
+    fn default() -> Self
+    {
+        Self {
+            left: vec![
+                StatusField::Rfc,
+                StatusField::Line,
+                StatusField::Percent,
+                StatusField::Page,
+                StatusField::Matches,
+            ],
+            middle: vec![StatusField::Mode],
+            right: vec![StatusField::Help],
+        }
+    }
+}
+
+/// A color given by name, like `"black"`, index or `"#rrggbb"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ConfigColor(pub Color);
+
+impl TryFrom<String> for ConfigColor
+{
+    type Error = Error;
+
+    fn try_from(name: String) -> Result<Self>
+    {
+        Color::from_str(&name)
+            .map(Self)
+            .map_err(|_| Error::msg(format!("Unknown color '{name}'")))
+    }
+}
+
+impl From<ConfigColor> for String
+{
+    fn from(color: ConfigColor) -> Self
+    {
+        color.0.to_string()
+    }
+}
+
+/// Where jumps to search results, `ToC` entries and definitions place their
+/// target line in the viewport.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum JumpAlignment
+{
+    /// At the top of the viewport.
+    #[default]
+    Top,
+    /// At the center of the viewport.
+    Center,
+    /// The given number of lines below the top of the viewport.
+    ScrollOff(usize),
+}
+
+/// What the position of the current search match is shown as a percentage
+/// of in the statusbar.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum MatchPercent
+{
+    /// Not shown.
+    #[default]
+    Off,
+    /// Of the matches.
+    Matches,
+    /// Of the document, by the line of the match.
+    Document,
+}
+
+/// Side of the content the `ToC` panel is shown on.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum TocPosition
+{
+    /// Left of the content.
+    #[default]
+    Left,
+    /// Right of the content.
+    Right,
+}
+
+/// How `ToC` titles longer than the panel width are shown.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum TocTitleOverflow
+{
+    /// Wrapped onto multiple lines.
+    #[default]
+    Wrap,
+    /// Cut on a single line, ending with an ellipsis.
+    Truncate,
+}
+
+/// A piece of information that can be shown in the statusbar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatusField
+{
+    /// The number of the current RFC.
+    Rfc,
+    /// The current line and the total line count.
+    Line,
+    /// The reading progress as percentage.
+    Percent,
+    /// The current page and the page count.
+    Page,
+    /// The current search match and the total match count.
+    Matches,
+    /// The word count of the document.
+    WordCount,
+    /// The category of the RFC, like standards track.
+    Category,
+    /// Where the document was loaded from.
+    Source,
+    /// The current local time.
+    Time,
+    /// The current application mode.
+    Mode,
+    /// Hints for the available keybindings.
+    Help,
+}
+
+impl StatusField
+{
+    /// Returns the separator placed before this field when it follows
+    /// another one.
+    ///
+    /// # Returns
+    ///
+    /// The separator text.
+    #[must_use]
+    pub const fn separator(self) -> &'static str
+    {
+        match self
+        {
+            // Reads as `L 10/200 (5%)`
+            Self::Percent => " ",
+            _ => " | ",
+        }
+    }
+}
+
+impl Config
+{
+    /// Gets the size the cached RFCs may take up.
+    ///
+    /// # Returns
+    ///
+    /// The limit in bytes, or `None` if the cache is unlimited.
+    #[must_use]
+    pub fn max_cache_bytes(&self) -> Option<u64>
+    {
+        self.max_cache_size_mb
+            .map(|size_mb| size_mb.saturating_mul(BYTES_PER_MIB))
+    }
+
+    /// Normalizes a document for display if documents are kept as served.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The document text
+    ///
+    /// # Returns
+    ///
+    /// The normalized text, the text itself if it needs no changes or
+    /// original texts aren't kept.
+    #[must_use]
+    pub fn presented_text(&self, text: DocumentText) -> DocumentText
+    {
+        if !self.keep_original_text
+        {
+            return text;
+        }
+
+        normalize_document(text, self.keep_page_breaks)
+    }
+
+    /// Loads the configuration from the default location.
+    ///
+    /// # Returns
+    ///
+    /// The loaded configuration, or the default one if the file doesn't
+    /// exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory cannot be determined or the
+    /// file cannot be read or parsed.
+    pub fn load() -> Result<Self>
+    {
+        Self::load_from(&Self::path()?)
+    }
+
+    /// Loads the configuration from the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the configuration file
+    ///
+    /// # Returns
+    ///
+    /// The loaded configuration, or the default one if the file doesn't
+    /// exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or if
+    /// `max_search_matches` is 0.
+    pub fn load_from(path: &Path) -> Result<Self>
+    {
+        if !path.exists()
+        {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path).with_context(|| {
+            format!("Failed to read config file {}", path.display())
+        })?;
+
+        let config: Self = toml::from_str(&content).with_context(|| {
+            format!("Failed to parse config file {}", path.display())
+        })?;
+
+        // No search could find anything, yet every one would be capped
+        ensure!(
+            config.max_search_matches > 0,
+            "max_search_matches must be at least 1 in config file {}",
+            path.display()
+        );
+
+        Ok(config)
+    }
+
+    /// Saves the settings changed since `initial` to the default location.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - The configuration as it was loaded
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory cannot be determined or the
+    /// file cannot be written.
+    pub fn save_changes(&self, initial: &Self) -> Result<()>
+    {
+        self.save_changes_to(initial, &Self::path()?)
+    }
+
+    /// Saves the settings changed since `initial` to the given path.
+    ///
+    /// Only the changed keys are written, the rest of the file, like its
+    /// comments, is kept as is. Creates the parent directory if it doesn't
+    /// exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - The configuration as it was loaded
+    /// * `path` - The path of the configuration file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration cannot be serialized or the file
+    /// cannot be read, parsed or written.
+    pub fn save_changes_to(&self, initial: &Self, path: &Path) -> Result<()>
+    {
+        if let Some(parent) = path.parent()
+        {
+            fs::create_dir_all(parent).with_context(|| {
+                format!(
+                    "Failed to create config directory {}",
+                    parent.display()
+                )
+            })?;
+        }
+
+        let content = if path.exists()
+        {
+            fs::read_to_string(path).with_context(|| {
+                format!("Failed to read config file {}", path.display())
+            })?
+        }
+        else
+        {
+            String::new()
+        };
+        let mut document: DocumentMut = content.parse().with_context(|| {
+            format!("Failed to parse config file {}", path.display())
+        })?;
+
+        let current = serialize_document(self)?;
+        let initial = serialize_document(initial)?;
+        merge_changes(
+            document.as_table_mut(),
+            current.as_table(),
+            initial.as_table(),
+        );
+
+        fs::write(path, document.to_string()).with_context(|| {
+            format!("Failed to write config file {}", path.display())
+        })
+    }
+
+    /// Gets the path of the configuration file.
+    ///
+    /// # Returns
+    ///
+    /// The path where the configuration file is expected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory cannot be determined.
+    pub fn path() -> Result<PathBuf>
+    {
+        let project_dirs = ProjectDirs::from("", "", env!("CARGO_PKG_NAME"))
+            .context("Failed to determine project directories")?;
+
+        Ok(project_dirs
+            .config_dir()
+            .join(CONFIG_FILE_NAME))
+    }
+}
+
+/// Serializes a configuration to an editable TOML document.
+///
+/// # Arguments
+///
+/// * `config` - The configuration to serialize
+///
+/// # Returns
+///
+/// The document with every set key of the configuration.
+///
+/// # Errors
+///
+/// Returns an error if the configuration cannot be serialized.
+fn serialize_document(config: &Config) -> Result<DocumentMut>
+{
+    toml::to_string(config)
+        .context("Failed to serialize config")?
+        .parse()
+        .context("Failed to serialize config")
+}
+
+/// Writes the keys that differ between two serialized configurations to a
+/// table of the config file.
+///
+/// Keys unset in `current` are removed, and tables present in both are
+/// merged key by key.
+///
+/// # Arguments
+///
+/// * `target` - The table of the config file to update
+/// * `current` - The table of the current configuration
+/// * `initial` - The table of the configuration as it was loaded
+fn merge_changes(
+    target: &mut dyn TableLike,
+    current: &dyn TableLike,
+    initial: &dyn TableLike,
+)
+{
+    let keys: BTreeSet<&str> = current
+        .iter()
+        .chain(initial.iter())
+        .map(|(key, _)| key)
+        .collect();
+
+    for key in keys
+    {
+        match (current.get(key), initial.get(key))
+        {
+            (Some(current_item), Some(initial_item))
+                if current_item.to_string() == initial_item.to_string() =>
+            {},
+            (Some(current_item), Some(initial_item))
+                if current_item.is_table_like() &&
+                    initial_item.is_table_like() =>
+            {
+                let nested = target
+                    .entry(key)
+                    .or_insert_with(|| Item::Table(Table::new()));
+
+                if let (Some(nested), Some(current), Some(initial)) = (
+                    nested.as_table_like_mut(),
+                    current_item.as_table_like(),
+                    initial_item.as_table_like(),
+                )
+                {
+                    merge_changes(nested, current, initial);
+                }
+                // Not a table in the file, to be replaced as a whole
+                else
+                {
+                    *nested = current_item.clone();
+                }
+            },
+            (Some(current_item), _) =>
+            {
+                match target.get_mut(key)
+                {
+                    // Keeps the comments around an existing value
+                    Some(Item::Value(value)) if current_item.is_value() =>
+                    {
+                        let decor = value.decor().clone();
+                        if let Some(current_value) = current_item.as_value()
+                        {
+                            *value = current_value.clone();
+                            *value.decor_mut() = decor;
+                        }
+                    },
+                    _ =>
+                    {
+                        target.insert(key, current_item.clone());
+                    },
+                }
+            },
+            (None, _) =>
+            {
+                target.remove(key);
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_missing_config_is_default() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+
+        let config = Config::load_from(&temp_dir.path().join("none.toml"))?;
+        assert_eq!(config, Config::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_max_search_matches() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("config.toml");
+
+        fs::write(&path, "max_search_matches = 0\n")?;
+        assert!(Config::load_from(&path).is_err());
+
+        fs::write(&path, "max_search_matches = 1\n")?;
+        assert_eq!(Config::load_from(&path)?.max_search_matches, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_round_trip() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir
+            .path()
+            .join("nested")
+            .join(CONFIG_FILE_NAME);
+        let config = Config {
+            toc_width: 40,
+            jump_alignment: JumpAlignment::ScrollOff(3),
+            user_agent: Some("mirror-monitor/1.0".into()),
+            overlay_background: Some(ConfigColor(Color::Rgb(0x1e, 0x1e, 0x2e))),
+            ..Config::default()
+        };
+
+        config.save_changes_to(&Config::default(), &path)?;
+        assert_eq!(Config::load_from(&path)?, config);
+
+        // Changed back to the defaults
+        Config::default().save_changes_to(&config, &path)?;
+        assert_eq!(Config::load_from(&path)?, Config::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_keeps_comments() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join(CONFIG_FILE_NAME);
+        fs::write(
+            &path,
+            r#"# Wider than the default
+toc_width = 30 # percent
+
+match_color = "cyan"
+
+[statusbar]
+# Short
+left = ["rfc"]
+"#,
+        )?;
+        let initial = Config::load_from(&path)?;
+
+        let config = Config {
+            toc_width: 40,
+            highlight_current_line: true,
+            match_color: None,
+            statusbar: StatusbarConfig {
+                right: vec![StatusField::Time],
+                ..initial.statusbar.clone()
+            },
+            ..initial.clone()
+        };
+        config.save_changes_to(&initial, &path)?;
+
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            r#"# Wider than the default
+toc_width = 40 # percent
+highlight_current_line = true
+
+[statusbar]
+# Short
+left = ["rfc"]
+right = ["time"]
+"#
+        );
+        assert_eq!(Config::load_from(&path)?, config);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_overlay_background() -> Result<()>
+    {
+        let config: Config = toml::from_str(r#"overlay_background = "blue""#)?;
+        assert_eq!(config.overlay_background, Some(ConfigColor(Color::Blue)));
+
+        assert!(
+            toml::from_str::<Config>(r#"overlay_background = "nope""#).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_jump_alignment() -> Result<()>
+    {
+        let config: Config = toml::from_str(r#"jump_alignment = "center""#)?;
+        assert_eq!(config.jump_alignment, JumpAlignment::Center);
+
+        let config: Config =
+            toml::from_str("jump_alignment = { scroll-off = 3 }")?;
+        assert_eq!(config.jump_alignment, JumpAlignment::ScrollOff(3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_toc_position() -> Result<()>
+    {
+        let config: Config = toml::from_str(r#"toc_position = "right""#)?;
+        assert_eq!(config.toc_position, TocPosition::Right);
+        assert_eq!(Config::default().toc_position, TocPosition::Left);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_statusbar_fields() -> Result<()>
+    {
+        let config: Config = toml::from_str(
+            r#"
+            [statusbar]
+            left = ["rfc", "word-count"]
+            right = ["source", "time"]
+            "#,
+        )?;
+
+        assert_eq!(
+            config.statusbar.left,
+            [StatusField::Rfc, StatusField::WordCount]
+        );
+        // Unspecified sections keep their defaults
+        assert_eq!(config.statusbar.middle, [StatusField::Mode]);
+        assert_eq!(
+            config.statusbar.right,
+            [StatusField::Source, StatusField::Time]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,894 @@
+//! Full-text search across the cached RFCs.
+//!
+//! Works offline on the local cache and writes the hits either as
+//! `rfc<N>:<line>: <text>` lines, as understood by editor quickfix lists, or
+//! as JSON. Context lines around the hits are written as
+//! `rfc<N>-<line>- <text>`, with `--` between separate groups of lines.
+use std::io::Write;
+
+use anyhow::{Context as _, Result};
+use log::warn;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::cache::RfcCache;
+use crate::references::find_rfc_references;
+use crate::types::{LineNumber, RfcNum};
+use crate::ui::TocPanel;
+
+/// A line of a cached RFC matching the search pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GrepHit
+{
+    /// The RFC containing the line.
+    pub rfc: RfcNum,
+    /// The line number, starting from 1.
+    pub line: LineNumber,
+    /// The text of the line.
+    pub text: Box<str>,
+    /// The lines before the line, as many as requested and available.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub before: Vec<Box<str>>,
+    /// The lines after the line, as many as requested and available.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<Box<str>>,
+}
+
+/// Number of lines around each hit to include.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GrepContext
+{
+    /// Number of lines before each hit.
+    pub before: usize,
+    /// Number of lines after each hit.
+    pub after: usize,
+}
+
+/// The number of lines of a cached RFC matching the search pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GrepCount
+{
+    /// The RFC containing the lines.
+    pub rfc: RfcNum,
+    /// The number of matching lines.
+    pub count: usize,
+}
+
+/// A section of a cached RFC whose title matches the search pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SectionHit
+{
+    /// The RFC containing the section.
+    pub rfc: RfcNum,
+    /// The section label, like `3.1` or `Appendix A.`, empty if the entry
+    /// has none.
+    pub section: Box<str>,
+    /// The title of the section.
+    pub title: Box<str>,
+    /// The line number of the section heading, starting from 1.
+    pub line: LineNumber,
+}
+
+/// A cached RFC referencing another RFC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Citation
+{
+    /// The RFC containing the references.
+    pub rfc: RfcNum,
+    /// The line number of the first reference, starting from 1.
+    pub line: LineNumber,
+    /// The number of references.
+    pub count: usize,
+}
+
+/// Output format of the search hits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrepFormat
+{
+    /// One `rfc<N>:<line>: <text>` line per hit.
+    Quickfix,
+    /// A JSON array of hits.
+    Json,
+}
+
+/// Searches all cached RFCs for lines matching the pattern.
+///
+/// RFCs that fail to read are skipped with a warning.
+///
+/// # Arguments
+///
+/// * `cache` - The cache to search
+/// * `pattern` - The pattern to match lines against
+/// * `context_lines` - The number of lines around each hit to include
+///
+/// # Returns
+///
+/// The hits ordered by RFC and line number.
+///
+/// # Errors
+///
+/// Returns an error if the cache directory cannot be read.
+pub fn grep_cache(
+    cache: &RfcCache,
+    pattern: &Regex,
+    context_lines: GrepContext,
+) -> Result<Vec<GrepHit>>
+{
+    let mut hits = Vec::new();
+
+    for rfc_number in cache.cached_rfc_numbers()?
+    {
+        let content = match cache.get_presented_rfc_text(rfc_number)
+        {
+            Ok(content) => content,
+            Err(error) =>
+            {
+                warn!("Skipping RFC {rfc_number}: {error:#}");
+                continue;
+            },
+        };
+
+        hits.extend(grep_text(rfc_number, &content, pattern, context_lines));
+    }
+
+    Ok(hits)
+}
+
+/// Searches the text of an RFC for lines matching the pattern.
+///
+/// The context of the hits is limited to the text, it never continues into
+/// another RFC.
+///
+/// # Arguments
+///
+/// * `rfc_number` - The RFC the text belongs to
+/// * `content` - The text to search
+/// * `pattern` - The pattern to match lines against
+/// * `context_lines` - The number of lines around each hit to include
+///
+/// # Returns
+///
+/// The hits in line order.
+#[must_use]
+pub fn grep_text(
+    rfc_number: RfcNum,
+    content: &str,
+    pattern: &Regex,
+    context_lines: GrepContext,
+) -> Vec<GrepHit>
+{
+    let lines: Vec<&str> = content.lines().collect();
+
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| pattern.is_match(line))
+        .map(|(index, line)| {
+            let before_start = index.saturating_sub(context_lines.before);
+            let after_end = index
+                .saturating_add(1)
+                .saturating_add(context_lines.after)
+                .min(lines.len());
+            let to_owned = |lines: &[&str]| {
+                lines
+                    .iter()
+                    .map(|&line| Box::from(line))
+                    .collect()
+            };
+
+            GrepHit {
+                rfc: rfc_number,
+                line: index.saturating_add(1),
+                text: (*line).into(),
+                before: to_owned(&lines[before_start..index]),
+                after: to_owned(&lines[index.saturating_add(1)..after_end]),
+            }
+        })
+        .collect()
+}
+
+/// Searches the tables of contents of all cached RFCs for sections whose
+/// titles match the pattern.
+///
+/// RFCs that fail to read are skipped with a warning.
+///
+/// # Arguments
+///
+/// * `cache` - The cache to search
+/// * `pattern` - The pattern to match section titles against
+///
+/// # Returns
+///
+/// The matching sections ordered by RFC and line number.
+///
+/// # Errors
+///
+/// Returns an error if the cache directory cannot be read.
+pub fn find_sections(
+    cache: &RfcCache,
+    pattern: &Regex,
+) -> Result<Vec<SectionHit>>
+{
+    let mut hits = Vec::new();
+
+    for rfc_number in cache.cached_rfc_numbers()?
+    {
+        let content = match cache.get_presented_rfc_text(rfc_number)
+        {
+            Ok(content) => content,
+            Err(error) =>
+            {
+                warn!("Skipping RFC {rfc_number}: {error:#}");
+                continue;
+            },
+        };
+
+        hits.extend(find_sections_in_text(rfc_number, &content, pattern));
+    }
+
+    Ok(hits)
+}
+
+/// Searches all cached RFCs for references to an RFC, like `RFC 8446` or
+/// `[RFC8446]`.
+///
+/// RFCs that fail to read are skipped with a warning, and the referenced
+/// RFC itself is left out.
+///
+/// # Arguments
+///
+/// * `cache` - The cache to search
+/// * `cited` - The RFC to find references to
+///
+/// # Returns
+///
+/// The RFCs referencing it in ascending order.
+///
+/// # Errors
+///
+/// Returns an error if the cache directory cannot be read.
+pub fn find_citations(cache: &RfcCache, cited: RfcNum)
+-> Result<Vec<Citation>>
+{
+    let mut citations = Vec::new();
+
+    for rfc_number in cache.cached_rfc_numbers()?
+    {
+        if rfc_number == cited
+        {
+            continue;
+        }
+
+        let content = match cache.get_presented_rfc_text(rfc_number)
+        {
+            Ok(content) => content,
+            Err(error) =>
+            {
+                warn!("Skipping RFC {rfc_number}: {error:#}");
+                continue;
+            },
+        };
+
+        citations.extend(find_citation_in_text(rfc_number, &content, cited));
+    }
+
+    Ok(citations)
+}
+
+/// Counts the references of an RFC to another one.
+///
+/// # Arguments
+///
+/// * `rfc_number` - The RFC the text belongs to
+/// * `content` - The text of the RFC
+/// * `cited` - The RFC to count the references to
+///
+/// # Returns
+///
+/// The references, or `None` if there are none.
+#[must_use]
+pub fn find_citation_in_text(
+    rfc_number: RfcNum,
+    content: &str,
+    cited: RfcNum,
+) -> Option<Citation>
+{
+    let mut citing_lines = content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let count = find_rfc_references(line)
+                .filter(|&reference| reference == cited)
+                .count();
+
+            (index, count)
+        })
+        .filter(|&(_, count)| count > 0);
+
+    let (first_index, first_count) = citing_lines.next()?;
+    let count = citing_lines
+        .fold(first_count, |total, (_, count)| total.saturating_add(count));
+
+    Some(Citation {
+        rfc: rfc_number,
+        line: first_index.saturating_add(1),
+        count,
+    })
+}
+
+/// Searches the table of contents of an RFC for sections whose titles
+/// match the pattern.
+///
+/// # Arguments
+///
+/// * `rfc_number` - The RFC the text belongs to
+/// * `content` - The text of the RFC
+/// * `pattern` - The pattern to match section titles against
+///
+/// # Returns
+///
+/// The matching sections in line order.
+#[must_use]
+pub fn find_sections_in_text(
+    rfc_number: RfcNum,
+    content: &str,
+    pattern: &Regex,
+) -> Vec<SectionHit>
+{
+    TocPanel::new(content)
+        .entries()
+        .iter()
+        .filter_map(|entry| {
+            let (section, title) = split_section_label(entry.title.trim());
+
+            pattern.is_match(title).then(|| SectionHit {
+                rfc: rfc_number,
+                section: section.into(),
+                title: title.into(),
+                line: entry.line_number.saturating_add(1),
+            })
+        })
+        .collect()
+}
+
+/// Splits the section label off a `ToC` title.
+///
+/// # Arguments
+///
+/// * `title` - The title, like `3.1 Overview` or `Appendix A. Examples`
+///
+/// # Returns
+///
+/// The label and the rest of the title, the label is empty if the title
+/// doesn't start with one.
+fn split_section_label(title: &str) -> (&str, &str)
+{
+    /// Word starting the labels of appendices.
+    const APPENDIX_PREFIX: &str = "Appendix ";
+
+    // Labels are numbers like `3.1.`, letters like `A.1.` or appendices like
+    // `Appendix A.`
+    let label_end = if let Some(rest) = title.strip_prefix(APPENDIX_PREFIX)
+    {
+        rest.find(char::is_whitespace)
+            .map(|index| index.saturating_add(APPENDIX_PREFIX.len()))
+    }
+    else if title.starts_with(|ch: char| ch.is_ascii_digit()) ||
+        is_lettered_label(title)
+    {
+        title.find(char::is_whitespace)
+    }
+    else
+    {
+        None
+    };
+
+    match label_end
+    {
+        Some(index) =>
+        {
+            let (label, rest) = title.split_at(index);
+            (label, rest.trim_start())
+        },
+        None => ("", title),
+    }
+}
+
+/// Checks whether a title starts with a lettered label like `A.` or `A.1`.
+///
+/// # Arguments
+///
+/// * `title` - The title to check
+///
+/// # Returns
+///
+/// `true` if the title starts with an uppercase letter and a dot.
+fn is_lettered_label(title: &str) -> bool
+{
+    let mut chars = title.chars();
+
+    chars
+        .next()
+        .is_some_and(|ch| ch.is_ascii_uppercase()) &&
+        chars.next() == Some('.')
+}
+
+/// Counts the hits of each RFC.
+///
+/// # Arguments
+///
+/// * `hits` - The hits ordered by RFC
+///
+/// # Returns
+///
+/// The counts ordered by count descending, ties by RFC number.
+#[must_use]
+pub fn count_hits(hits: &[GrepHit]) -> Vec<GrepCount>
+{
+    let mut counts: Vec<GrepCount> = hits
+        .chunk_by(|hit, next| hit.rfc == next.rfc)
+        .filter_map(|rfc_hits| {
+            rfc_hits.first().map(|hit| GrepCount {
+                rfc: hit.rfc,
+                count: rfc_hits.len(),
+            })
+        })
+        .collect();
+
+    counts.sort_by(|count, other| {
+        other
+            .count
+            .cmp(&count.count)
+            .then(count.rfc.cmp(&other.rfc))
+    });
+
+    counts
+}
+
+/// Writes the hits in the given format.
+///
+/// # Arguments
+///
+/// * `writer` - The writer to write the hits to
+/// * `hits` - The hits to write
+/// * `format` - The output format
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+pub fn write_hits(
+    mut writer: impl Write,
+    hits: &[GrepHit],
+    format: GrepFormat,
+) -> Result<()>
+{
+    match format
+    {
+        GrepFormat::Quickfix => write_quickfix_hits(&mut writer, hits)?,
+        GrepFormat::Json =>
+        {
+            serde_json::to_writer_pretty(&mut writer, hits)
+                .context("Failed to write search hits as JSON")?;
+            writeln!(writer).context("Failed to write search hits")?;
+        },
+    }
+
+    writer
+        .flush()
+        .context("Failed to flush search hits")
+}
+
+/// Writes the hits as quickfix lines along with their context.
+///
+/// Overlapping context is written once, and context reaching into the next
+/// hit stops before it.
+///
+/// # Arguments
+///
+/// * `writer` - The writer to write the hits to
+/// * `hits` - The hits to write, ordered by RFC and line number
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+fn write_quickfix_hits(writer: &mut impl Write, hits: &[GrepHit])
+-> Result<()>
+{
+    // The RFC and line number of the last written line
+    let mut last_written: Option<(RfcNum, LineNumber)> = None;
+    let mut hits = hits.iter().peekable();
+
+    while let Some(hit) = hits.next()
+    {
+        let first_line = hit.line.saturating_sub(hit.before.len());
+        let written_line = match last_written
+        {
+            Some((rfc, line)) if rfc == hit.rfc => line,
+            _ => 0,
+        };
+        let is_separate = last_written.is_some_and(|(rfc, line)| {
+            rfc != hit.rfc || first_line > line.saturating_add(1)
+        });
+
+        // Groups are only separated once there's context to tell apart
+        if is_separate && !(hit.before.is_empty() && hit.after.is_empty())
+        {
+            writeln!(writer, "--").context("Failed to write search hit")?;
+        }
+
+        for (line, text) in (first_line..).zip(&hit.before)
+        {
+            if line > written_line
+            {
+                writeln!(writer, "rfc{}-{line}- {text}", hit.rfc)
+                    .context("Failed to write search context")?;
+            }
+        }
+
+        writeln!(writer, "rfc{}:{}: {}", hit.rfc, hit.line, hit.text)
+            .context("Failed to write search hit")?;
+
+        let next_hit_line = hits
+            .peek()
+            .filter(|next| next.rfc == hit.rfc)
+            .map_or(LineNumber::MAX, |next| next.line);
+        let mut last_line = hit.line;
+
+        for (line, text) in (hit.line.saturating_add(1)..)
+            .zip(&hit.after)
+            .take_while(|&(line, _)| line < next_hit_line)
+        {
+            writeln!(writer, "rfc{}-{line}- {text}", hit.rfc)
+                .context("Failed to write search context")?;
+            last_line = line;
+        }
+
+        last_written = Some((hit.rfc, last_line));
+    }
+
+    Ok(())
+}
+
+/// Writes the hit counts in the given format.
+///
+/// # Arguments
+///
+/// * `writer` - The writer to write the counts to
+/// * `counts` - The counts to write
+/// * `format` - The output format, one `rfc<N>: <count>` line per RFC for
+///   [`GrepFormat::Quickfix`]
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+pub fn write_counts(
+    mut writer: impl Write,
+    counts: &[GrepCount],
+    format: GrepFormat,
+) -> Result<()>
+{
+    match format
+    {
+        GrepFormat::Quickfix =>
+        {
+            for count in counts
+            {
+                writeln!(writer, "rfc{}: {}", count.rfc, count.count)
+                    .context("Failed to write hit count")?;
+            }
+        },
+        GrepFormat::Json =>
+        {
+            serde_json::to_writer_pretty(&mut writer, counts)
+                .context("Failed to write hit counts as JSON")?;
+            writeln!(writer).context("Failed to write hit counts")?;
+        },
+    }
+
+    writer
+        .flush()
+        .context("Failed to flush hit counts")
+}
+
+/// Writes the matching sections in the given format.
+///
+/// # Arguments
+///
+/// * `writer` - The writer to write the sections to
+/// * `sections` - The sections to write
+/// * `format` - The output format, one `rfc<N> <section> <title>` line per
+///   section for [`GrepFormat::Quickfix`]
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+pub fn write_sections(
+    mut writer: impl Write,
+    sections: &[SectionHit],
+    format: GrepFormat,
+) -> Result<()>
+{
+    match format
+    {
+        GrepFormat::Quickfix =>
+        {
+            for section in sections
+            {
+                // Without a label, the title follows the RFC directly
+                let label = if section.section.is_empty()
+                {
+                    String::new()
+                }
+                else
+                {
+                    format!(" {}", section.section)
+                };
+
+                writeln!(writer, "rfc{}{label} {}", section.rfc, section.title)
+                    .context("Failed to write section")?;
+            }
+        },
+        GrepFormat::Json =>
+        {
+            serde_json::to_writer_pretty(&mut writer, sections)
+                .context("Failed to write sections as JSON")?;
+            writeln!(writer).context("Failed to write sections")?;
+        },
+    }
+
+    writer
+        .flush()
+        .context("Failed to flush sections")
+}
+
+/// Writes the citing RFCs in the given format.
+///
+/// # Arguments
+///
+/// * `writer` - The writer to write the citations to
+/// * `citations` - The citations to write
+/// * `format` - The output format, one `rfc<N>:<line>: <count> references` line
+///   per RFC for [`GrepFormat::Quickfix`]
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+pub fn write_citations(
+    mut writer: impl Write,
+    citations: &[Citation],
+    format: GrepFormat,
+) -> Result<()>
+{
+    match format
+    {
+        GrepFormat::Quickfix =>
+        {
+            for citation in citations
+            {
+                let noun = if citation.count == 1
+                {
+                    "reference"
+                }
+                else
+                {
+                    "references"
+                };
+
+                writeln!(
+                    writer,
+                    "rfc{}:{}: {} {noun}",
+                    citation.rfc, citation.line, citation.count
+                )
+                .context("Failed to write citation")?;
+            }
+        },
+        GrepFormat::Json =>
+        {
+            serde_json::to_writer_pretty(&mut writer, citations)
+                .context("Failed to write citations as JSON")?;
+            writeln!(writer).context("Failed to write citations")?;
+        },
+    }
+
+    writer
+        .flush()
+        .context("Failed to flush citations")
+}
+
+#[cfg(test)]
+mod tests
+{
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_write_hits_quickfix() -> Result<()>
+    {
+        let rfc_number = RfcNum::new(2119).expect("its non-zero");
+        let pattern = Regex::new("MUST")?;
+        let hits = grep_text(
+            rfc_number,
+            "Key words\n1. MUST   This word\n2. SHOULD",
+            &pattern,
+            GrepContext::default(),
+        );
+
+        let mut output = Vec::new();
+        write_hits(&mut output, &hits, GrepFormat::Quickfix)?;
+
+        assert_eq!(
+            String::from_utf8(output)?,
+            "rfc2119:2: 1. MUST   This word\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_hits_json() -> Result<()>
+    {
+        let hits = [GrepHit {
+            rfc: RfcNum::new(9110).expect("its non-zero"),
+            line: 42,
+            text: "HTTP Semantics".into(),
+            before: Vec::new(),
+            after: Vec::new(),
+        }];
+
+        let mut output = Vec::new();
+        write_hits(&mut output, &hits, GrepFormat::Json)?;
+
+        let value: serde_json::Value = serde_json::from_slice(&output)?;
+        assert_eq!(
+            value,
+            serde_json::json!([
+                { "rfc": 9110, "line": 42, "text": "HTTP Semantics" }
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_hits_sorted_by_count() -> Result<()>
+    {
+        let pattern = Regex::new("MUST")?;
+        let rfc = |number| RfcNum::new(number).expect("its non-zero");
+        let grep = |number, content| {
+            grep_text(rfc(number), content, &pattern, GrepContext::default())
+        };
+        let hits = [
+            grep(2119, "MUST\nMUST NOT\nSHOULD"),
+            grep(8174, "MUST"),
+            grep(9110, "MUST\nMUST\nMUST"),
+        ]
+        .concat();
+
+        let mut output = Vec::new();
+        write_counts(&mut output, &count_hits(&hits), GrepFormat::Quickfix)?;
+
+        assert_eq!(
+            String::from_utf8(output)?,
+            "rfc9110: 3\nrfc2119: 2\nrfc8174: 1\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_hits_with_context() -> Result<()>
+    {
+        let pattern = Regex::new("MUST")?;
+        let rfc = |number| RfcNum::new(number).expect("its non-zero");
+        let context = GrepContext {
+            before: 1,
+            after: 1,
+        };
+        let hits = [
+            grep_text(
+                rfc(2119),
+                "a\nMUST\nb\nMUST\nc\nd\ne\nMUST",
+                &pattern,
+                context,
+            ),
+            // Starts with a hit, the context of the previous RFC stays there
+            grep_text(rfc(8174), "MUST\nf", &pattern, context),
+        ]
+        .concat();
+
+        let mut output = Vec::new();
+        write_hits(&mut output, &hits, GrepFormat::Quickfix)?;
+
+        assert_eq!(
+            String::from_utf8(output)?,
+            [
+                "rfc2119-1- a",
+                "rfc2119:2: MUST",
+                "rfc2119-3- b",
+                "rfc2119:4: MUST",
+                "rfc2119-5- c",
+                "--",
+                "rfc2119-7- e",
+                "rfc2119:8: MUST",
+                "--",
+                "rfc8174:1: MUST",
+                "rfc8174-2- f",
+                "",
+            ]
+            .join("\n")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_sections() -> Result<()>
+    {
+        let content = [
+            "Table of Contents",
+            "",
+            "   1.  Introduction ................................    2",
+            "   2.  Security Considerations .....................    3",
+            "   Appendix A.  Security Examples ..................    4",
+            "",
+            "1.  Introduction",
+            "",
+            "2.  Security Considerations",
+            "",
+            "Appendix A.  Security Examples",
+        ]
+        .join("\n");
+        let pattern = Regex::new("Security")?;
+        let sections = find_sections_in_text(
+            RfcNum::new(3552).expect("its non-zero"),
+            &content,
+            &pattern,
+        );
+
+        let mut output = Vec::new();
+        write_sections(&mut output, &sections, GrepFormat::Quickfix)?;
+
+        assert_eq!(
+            String::from_utf8(output)?,
+            "rfc3552 2. Security Considerations\nrfc3552 Appendix A. Security \
+             Examples\n"
+        );
+        assert_eq!(sections[0].line, 9);
+        assert_eq!(split_section_label("A.1.  Examples"), ("A.1.", "Examples"));
+        assert_eq!(split_section_label("A method"), ("", "A method"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_citations() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        let rfc = |number| RfcNum::new(number).expect("its non-zero");
+        cache.cache_rfc(
+            rfc(9110),
+            "Uses TLS [RFC8446].\n\nSee RFC 8446 and RFC 84460.\n\n   \
+             [RFC8446]  Rescorla, E.",
+        )?;
+        cache.cache_rfc(rfc(7230), "Uses TLS 1.2 [RFC5246].")?;
+        cache.cache_rfc(rfc(8446), "This is RFC 8446.")?;
+
+        let citations = find_citations(&cache, rfc(8446))?;
+        assert_eq!(
+            citations,
+            [Citation {
+                rfc: rfc(9110),
+                line: 1,
+                count: 3,
+            }]
+        );
+
+        let mut output = Vec::new();
+        write_citations(&mut output, &citations, GrepFormat::Quickfix)?;
+        assert_eq!(String::from_utf8(output)?, "rfc9110:1: 3 references\n");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,503 @@
+//! Parsing of the RFC index.
+//!
+//! The index (`rfc-index.txt`) lists every RFC with its title, authors and
+//! publication details, one blank-line separated entry per RFC.
+use std::mem;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::types::RfcNum;
+
+/// Matches the first line of an index entry, capturing the number and the
+/// start of the description.
+static INDEX_ENTRY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\d{4,5}) (.+)$").expect("Invalid index entry regex")
+});
+
+/// Matches the publication date ending the authors of an index entry, like
+/// `August 2018` or `1 April 2019`.
+static INDEX_DATE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\. ((?:\d{1,2} )?(?:January|February|March|April|May|June|July|August|September|October|November|December) \d{4})\.",
+    )
+    .expect("Invalid index date regex")
+});
+
+/// Matches the parenthesized details of an index entry, like
+/// `(Status: PROPOSED STANDARD)`.
+static INDEX_DETAIL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\(([^()]*)\)").expect("Invalid index detail regex")
+});
+
+/// An RFC as listed in the index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IndexEntry
+{
+    /// The number of the RFC.
+    pub number: RfcNum,
+    /// The title of the RFC.
+    pub title: Box<str>,
+    /// The authors of the RFC.
+    pub authors: Vec<Box<str>>,
+    /// When the RFC was published, like `August 2018`.
+    pub date: Option<Box<str>>,
+    /// The status of the RFC, like `PROPOSED STANDARD`.
+    pub status: Option<Box<str>>,
+    /// The RFCs this one obsoletes.
+    pub obsoletes: Vec<RfcNum>,
+    /// The RFCs obsoleting this one.
+    pub obsoleted_by: Vec<RfcNum>,
+    /// The RFCs this one updates.
+    pub updates: Vec<RfcNum>,
+    /// The RFCs updating this one.
+    pub updated_by: Vec<RfcNum>,
+}
+
+impl IndexEntry
+{
+    /// Creates an entry with only a number and a title.
+    ///
+    /// # Arguments
+    ///
+    /// * `number` - The number of the RFC
+    /// * `title` - The title of the RFC
+    ///
+    /// # Returns
+    ///
+    /// The entry without any other details.
+    #[must_use]
+    pub fn new(number: RfcNum, title: &str) -> Self
+    {
+        Self {
+            number,
+            title: title.into(),
+            authors: Vec::new(),
+            date: None,
+            status: None,
+            obsoletes: Vec::new(),
+            obsoleted_by: Vec::new(),
+            updates: Vec::new(),
+            updated_by: Vec::new(),
+        }
+    }
+}
+
+/// Parses the RFC index into its entries.
+///
+/// Lines outside of entries, like the header of the index, are skipped.
+///
+/// # Arguments
+///
+/// * `content` - The content of the index
+///
+/// # Returns
+///
+/// The entries in the order they appear in the index.
+#[must_use]
+pub fn parse_index(content: &str) -> Vec<IndexEntry>
+{
+    let mut entries = Vec::new();
+
+    parse_index_with_progress(content, usize::MAX, |batch, _| {
+        entries.extend(batch);
+    });
+
+    entries
+}
+
+/// Checks whether the RFC index is obviously truncated, like by an
+/// interrupted download, and should be fetched again.
+///
+/// Every entry ends with its DOI or with `Not Issued.`, so an index whose
+/// last entry doesn't is cut off.
+///
+/// # Arguments
+///
+/// * `content` - The content of the index
+///
+/// # Returns
+///
+/// `true` if the index has no entries or its last entry is incomplete.
+#[must_use]
+pub fn is_index_truncated(content: &str) -> bool
+{
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(last_entry_start) = lines
+        .iter()
+        .rposition(|line| INDEX_ENTRY_REGEX.is_match(line))
+    else
+    {
+        return true;
+    };
+
+    let last_entry = lines[last_entry_start..]
+        .iter()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let last_entry = last_entry.trim_end();
+    let ends_with_doi = INDEX_DETAIL_REGEX
+        .find_iter(last_entry)
+        .last()
+        .is_some_and(|detail| {
+            detail.end() == last_entry.len() &&
+                detail.as_str().starts_with("(DOI:")
+        });
+
+    !(ends_with_doi || last_entry.ends_with("Not Issued."))
+}
+
+/// Parses the RFC index, handing over the entries in batches as they are
+/// parsed.
+///
+/// # Arguments
+///
+/// * `content` - The content of the index
+/// * `batch_size` - The number of entries per batch, the last one may be
+///   smaller
+/// * `on_batch` - Called with each batch and the percentage of the index parsed
+///   so far, 100 for the last one
+pub fn parse_index_with_progress(
+    content: &str,
+    batch_size: usize,
+    mut on_batch: impl FnMut(Vec<IndexEntry>, u8),
+)
+{
+    let mut batch = Vec::new();
+    // Number and description of the entry being read
+    let mut current: Option<(RfcNum, String)> = None;
+    let mut parsed_bytes: usize = 0;
+
+    for line in content.lines()
+    {
+        // Including the line break
+        parsed_bytes = parsed_bytes
+            .saturating_add(line.len())
+            .saturating_add(1);
+
+        if line.trim().is_empty()
+        {
+            batch.extend(current.take().map(to_entry));
+        }
+        else if let Some(caps) = INDEX_ENTRY_REGEX.captures(line)
+        {
+            batch.extend(current.take().map(to_entry));
+            current = caps[1]
+                .parse()
+                .ok()
+                .map(|number| (number, caps[2].to_owned()));
+        }
+        else if let Some((_, description)) = &mut current
+        {
+            // Continuation lines are indented
+            description.push(' ');
+            description.push_str(line.trim());
+        }
+
+        if batch.len() >= batch_size
+        {
+            on_batch(
+                mem::take(&mut batch),
+                progress_percent(parsed_bytes, content.len()),
+            );
+        }
+    }
+
+    batch.extend(current.map(to_entry));
+    on_batch(batch, 100);
+}
+
+/// Joins RFC numbers into a readable list.
+///
+/// # Arguments
+///
+/// * `rfc_numbers` - The RFC numbers to join
+///
+/// # Returns
+///
+/// The numbers prefixed with `RFC` and separated by commas.
+#[must_use]
+pub fn join_rfc_numbers(rfc_numbers: &[RfcNum]) -> String
+{
+    rfc_numbers
+        .iter()
+        .map(|rfc_number| format!("RFC {rfc_number}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Computes the percentage of the content parsed, below 100 until done.
+///
+/// # Arguments
+///
+/// * `parsed_bytes` - The number of bytes parsed
+/// * `total_bytes` - The size of the content
+///
+/// # Returns
+///
+/// The percentage, at most 99.
+fn progress_percent(parsed_bytes: usize, total_bytes: usize) -> u8
+{
+    /// Upper bound of the percentage before parsing is done.
+    const MAX_IN_PROGRESS_PERCENT: usize = 99;
+
+    let percent = parsed_bytes
+        .saturating_mul(100)
+        .checked_div(total_bytes)
+        .unwrap_or_default()
+        .min(MAX_IN_PROGRESS_PERCENT);
+
+    u8::try_from(percent).unwrap_or_default()
+}
+
+/// Builds an index entry from its number and description.
+///
+/// The description lists the title, the authors and the date separated by
+/// periods, followed by parenthesized details like the status.
+///
+/// # Arguments
+///
+/// * `(number, description)` - The number and the full description text
+///
+/// # Returns
+///
+/// The entry with the details extracted from the description.
+fn to_entry((number, description): (RfcNum, String)) -> IndexEntry
+{
+    let date_match = INDEX_DATE_REGEX.captures(&description);
+    let byline = date_match
+        .as_ref()
+        .and_then(|caps| caps.get(0))
+        .map_or(description.as_str(), |date| {
+            description
+                .get(..date.start())
+                .unwrap_or_default()
+        });
+
+    // The title is followed by the authors, separated by a period
+    let (title, authors) = byline
+        .split_once(". ")
+        .unwrap_or((byline, ""));
+    let title = title.trim_end_matches('.');
+
+    let mut entry = IndexEntry::new(number, title);
+    entry.date = date_match
+        .as_ref()
+        .and_then(|caps| caps.get(1))
+        .map(|date| date.as_str().into());
+    if date_match.is_some()
+    {
+        entry.authors = parse_authors(authors);
+    }
+
+    for detail in INDEX_DETAIL_REGEX
+        .captures_iter(&description)
+        .filter_map(|caps| caps.get(1))
+    {
+        let detail = detail.as_str();
+
+        if let Some(status) = detail.strip_prefix("Status: ")
+        {
+            entry.status = Some(status.trim().into());
+        }
+        else if let Some(rfcs) = detail.strip_prefix("Obsoleted by ")
+        {
+            entry.obsoleted_by = parse_rfc_list(rfcs);
+        }
+        else if let Some(rfcs) = detail.strip_prefix("Obsoletes ")
+        {
+            entry.obsoletes = parse_rfc_list(rfcs);
+        }
+        else if let Some(rfcs) = detail.strip_prefix("Updated by ")
+        {
+            entry.updated_by = parse_rfc_list(rfcs);
+        }
+        else if let Some(rfcs) = detail.strip_prefix("Updates ")
+        {
+            entry.updates = parse_rfc_list(rfcs);
+        }
+    }
+
+    entry
+}
+
+/// Splits the authors of an index entry.
+///
+/// # Arguments
+///
+/// * `authors` - The comma separated authors, like `R. Fielding, Ed., J.
+///   Reschke`
+///
+/// # Returns
+///
+/// The authors, each with their role like `Ed.` attached.
+fn parse_authors(authors: &str) -> Vec<Box<str>>
+{
+    let mut parsed: Vec<String> = Vec::new();
+
+    for author in authors
+        .split(", ")
+        .map(str::trim)
+        .filter(|author| !author.is_empty())
+    {
+        match parsed.last_mut()
+        {
+            // Roles follow the name they belong to
+            Some(previous) if author == "Ed." =>
+            {
+                previous.push_str(", Ed.");
+            },
+            _ => parsed.push(author.to_owned()),
+        }
+    }
+
+    parsed
+        .into_iter()
+        .map(String::into_boxed_str)
+        .collect()
+}
+
+/// Parses a list of RFCs given in the index, like `RFC5077, RFC5246`.
+///
+/// # Arguments
+///
+/// * `rfcs` - The comma separated documents
+///
+/// # Returns
+///
+/// The RFC numbers, skipping other documents like `BCP0014`.
+fn parse_rfc_list(rfcs: &str) -> Vec<RfcNum>
+{
+    rfcs.split(',')
+        .filter_map(|document| document.trim().strip_prefix("RFC"))
+        .filter_map(|number| number.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_parse_index()
+    {
+        let content = "\
+                RFC INDEX
+             -------------
+
+0001 Host Software. S. Crocker. April 1969. (Format: TXT, HTML) (Status:
+     UNKNOWN) (DOI: 10.17487/RFC0001)
+
+0003 Not Issued.
+
+8446 The Transport Layer Security (TLS) Protocol Version 1.3. E.
+     Rescorla. August 2018. (Format: HTML, TXT, PDF, XML) (Obsoletes
+     RFC5077, RFC5246, RFC6961) (Status: PROPOSED STANDARD)
+";
+
+        let entries = parse_index(content);
+        let entries: Vec<(u16, &str)> = entries
+            .iter()
+            .map(|entry| (entry.number.get(), entry.title.as_ref()))
+            .collect();
+
+        assert_eq!(
+            entries,
+            [
+                (1, "Host Software"),
+                (3, "Not Issued"),
+                (
+                    8446,
+                    "The Transport Layer Security (TLS) Protocol Version 1.3"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_index_details()
+    {
+        let rfc = |number| RfcNum::new(number).expect("its non-zero");
+        let content = "\
+9110 HTTP Semantics. R. Fielding, Ed., M. Nottingham, Ed., J. Reschke,
+     Ed.. June 2022. (Format: HTML, TXT, PDF, XML) (Obsoletes RFC2818,
+     RFC7230, RFC7231) (Updates RFC3864) (Also STD0097) (Status: INTERNET
+     STANDARD) (DOI: 10.17487/RFC9110)
+
+2616 Hypertext Transfer Protocol -- HTTP/1.1. R. Fielding, J. Gettys.
+     June 1999. (Obsoleted by RFC7230, RFC7231) (Updated by RFC2817)
+     (Status: DRAFT STANDARD)
+";
+
+        let entries = parse_index(content);
+
+        let http = &entries[0];
+        assert_eq!(http.title.as_ref(), "HTTP Semantics");
+        assert_eq!(
+            http.authors,
+            [
+                Box::from("R. Fielding, Ed."),
+                "M. Nottingham, Ed.".into(),
+                "J. Reschke, Ed.".into()
+            ]
+        );
+        assert_eq!(http.date.as_deref(), Some("June 2022"));
+        assert_eq!(http.status.as_deref(), Some("INTERNET STANDARD"));
+        assert_eq!(http.obsoletes, [2818, 7230, 7231].map(rfc));
+        assert_eq!(http.updates, [rfc(3864)]);
+        assert!(http.obsoleted_by.is_empty());
+
+        let http_1_1 = &entries[1];
+        assert_eq!(
+            http_1_1.title.as_ref(),
+            "Hypertext Transfer Protocol -- HTTP/1.1"
+        );
+        assert_eq!(
+            http_1_1.authors,
+            [Box::from("R. Fielding"), "J. Gettys".into()]
+        );
+        assert_eq!(http_1_1.obsoleted_by, [7230, 7231].map(rfc));
+        assert_eq!(http_1_1.updated_by, [rfc(2817)]);
+    }
+
+    #[test]
+    fn test_parse_index_in_batches()
+    {
+        let content = "0001 Title 1.\n\n0002 Title 2.\n\n0003 Title \
+                       3.\n\n0004 Title 4.\n\n0005 Title 5.\n\n";
+
+        let mut batches = Vec::new();
+        parse_index_with_progress(content, 2, |batch, percent| {
+            batches.push((batch.len(), percent));
+        });
+
+        assert_eq!(batches, [(2, 40), (2, 80), (1, 100)]);
+    }
+
+    #[test]
+    fn test_truncated_index()
+    {
+        let content = [
+            "0001 Host Software. S. Crocker. April 1969. (Format: TXT, HTML)",
+            "     (Status: UNKNOWN) (DOI: 10.17487/RFC0001)",
+            "",
+            "0003 Not Issued.",
+            "",
+            "0004 Network timetable. E.B. Shapiro. March 1969. (Format: TXT,",
+            "     HTML) (Status: UNKNOWN) (DOI: 10.17487/RFC0004)",
+            "",
+        ]
+        .join("\n");
+        assert!(!is_index_truncated(&content));
+
+        // Cut off in the middle of the details of the last entry
+        let (cut, _) = content
+            .split_once("(Status: UNKNOWN) (DOI: 10.17487/RFC0004)")
+            .expect("the fixture has it");
+        assert!(is_index_truncated(cut));
+        assert!(is_index_truncated("RFC INDEX\n\n"));
+    }
+}
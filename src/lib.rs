@@ -11,12 +11,36 @@
 //!
 //! # Modules
 //!
+//! - `abnf`: Index of ABNF rule definitions.
+//! - `archive`: Tar archives for exporting and importing the cache.
+//! - `authors`: Parsing of the authors' addresses section.
 //! - `client`: HTTP client for remote RFC fetching.
 //! - `cache`: Local storage for performance improvement.
+//! - `config`: User configuration loaded from disk.
+//! - `grep`: Full-text search across the cached RFCs.
+//! - `index`: Parsing of the RFC index.
+//! - `loader`: Loading of RFCs from the cache or the network.
+//! - `metadata`: Extraction of metadata from the RFC text.
+//! - `phrase`: Search of phrases across the line breaks.
+//! - `reflow`: Reflowing of prose to the viewport width.
+//! - `references`: Detection of references to other RFCs.
+//! - `stats`: Local usage counters of the cache.
 //! - `ui`: Terminal user interface components and event handling.
 //! - `types`: Common types and aliases used across the library.
+pub mod abnf;
+pub mod archive;
+pub mod authors;
 pub mod cache;
 pub mod client;
+pub mod config;
+pub mod grep;
+pub mod index;
+pub mod loader;
+pub mod metadata;
+pub mod phrase;
+pub mod references;
+pub mod reflow;
+pub mod stats;
 pub mod types;
 pub mod ui;
 
@@ -0,0 +1,607 @@
+//! Loading of RFCs from the cache or the network.
+//!
+//! Decides between the local cache and fetching, honoring offline mode, and
+//! reads RFCs from local files.
+use std::borrow::Cow;
+use std::fs;
+use std::io::Read as _;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context as _, Result, bail};
+use flate2::read::GzDecoder;
+use log::{debug, error, info, warn};
+
+use crate::cache::RfcCache;
+use crate::client::{ConditionalFetch, FetchError, RfcSource};
+use crate::types::{DocumentSource, DocumentText, RfcNum};
+
+/// Magic bytes starting gzip streams.
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+/// Magic bytes starting bzip2 streams.
+const BZIP2_MAGIC: &[u8] = b"BZh";
+/// Magic bytes starting xz streams.
+const XZ_MAGIC: &[u8] = &[0xfd, b'7', b'z', b'X', b'Z', 0];
+/// Age from which cached RFCs are checked for updates, like errata fixes.
+pub const MAX_CACHE_AGE: Duration = Duration::from_hours(30 * 24);
+
+/// Reads an RFC from a local file, decompressing it if needed.
+///
+/// Compression is detected by the magic bytes rather than the file name.
+/// Files without known magic bytes are read as plain text.
+///
+/// # Arguments
+///
+/// * `path` - The file to read
+///
+/// # Returns
+///
+/// The text of the RFC.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read or decompressed, uses an
+/// unsupported compression or isn't UTF-8 text.
+pub fn read_rfc_file(path: &Path) -> Result<Box<str>>
+{
+    let bytes = fs::read(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let text = decompress(&bytes)
+        .with_context(|| format!("Failed to decompress {}", path.display()))?;
+
+    let text = String::from_utf8(text.into_owned())
+        .with_context(|| format!("{} is not a text file", path.display()))?;
+
+    Ok(text.into_boxed_str())
+}
+
+/// Decompresses the bytes of a file by their magic bytes.
+///
+/// # Arguments
+///
+/// * `bytes` - The content of the file
+///
+/// # Returns
+///
+/// The decompressed bytes, or the bytes as is if they aren't compressed.
+///
+/// # Errors
+///
+/// Returns an error if the stream is corrupt or its compression isn't
+/// supported.
+fn decompress(bytes: &[u8]) -> Result<Cow<'_, [u8]>>
+{
+    if bytes.starts_with(GZIP_MAGIC)
+    {
+        let mut text = Vec::new();
+        GzDecoder::new(bytes)
+            .read_to_end(&mut text)
+            .context("Corrupt gzip stream")?;
+
+        return Ok(Cow::Owned(text));
+    }
+
+    if bytes.starts_with(BZIP2_MAGIC)
+    {
+        return decompress_bzip2(bytes).map(Cow::Owned);
+    }
+
+    if bytes.starts_with(XZ_MAGIC)
+    {
+        return decompress_xz(bytes).map(Cow::Owned);
+    }
+
+    Ok(Cow::Borrowed(bytes))
+}
+
+/// Decompresses a bzip2 stream.
+///
+/// # Errors
+///
+/// Returns an error if the stream is corrupt.
+#[cfg(feature = "bzip2")]
+fn decompress_bzip2(bytes: &[u8]) -> Result<Vec<u8>>
+{
+    let mut text = Vec::new();
+    bzip2::read::MultiBzDecoder::new(bytes)
+        .read_to_end(&mut text)
+        .context("Corrupt bzip2 stream")?;
+
+    Ok(text)
+}
+
+/// Rejects bzip2 streams, as the `bzip2` feature is disabled.
+///
+/// # Errors
+///
+/// Always returns an error.
+#[cfg(not(feature = "bzip2"))]
+fn decompress_bzip2(_bytes: &[u8]) -> Result<Vec<u8>>
+{
+    bail!("bzip2 compression is not supported, decompress the file first");
+}
+
+/// Decompresses an xz stream.
+///
+/// # Errors
+///
+/// Returns an error if the stream is corrupt.
+#[cfg(feature = "xz2")]
+fn decompress_xz(bytes: &[u8]) -> Result<Vec<u8>>
+{
+    let mut text = Vec::new();
+    xz2::read::XzDecoder::new_multi_decoder(bytes)
+        .read_to_end(&mut text)
+        .context("Corrupt xz stream")?;
+
+    Ok(text)
+}
+
+/// Rejects xz streams, as the `xz2` feature is disabled.
+///
+/// # Errors
+///
+/// Always returns an error.
+#[cfg(not(feature = "xz2"))]
+fn decompress_xz(_bytes: &[u8]) -> Result<Vec<u8>>
+{
+    bail!("xz compression is not supported, decompress the file first");
+}
+
+/// Gets an RFC, from the cache if present, otherwise fetched and cached.
+///
+/// Online, a cached RFC older than [`MAX_CACHE_AGE`] is fetched again if the
+/// server has a newer version, keeping the cached copy if that fails.
+///
+/// # Arguments
+///
+/// * `cache` - The cache to look up the RFC in first
+/// * `source` - The source to fetch uncached RFCs from
+/// * `rfc_number` - The RFC to get
+/// * `is_offline` - Whether fetching is disabled
+///
+/// # Returns
+///
+/// The content of the RFC and where it was loaded from.
+///
+/// # Errors
+///
+/// Returns a [`FetchError::Offline`] if the RFC isn't cached in offline
+/// mode, or an error if it can't be fetched or the fetched RFC can't be
+/// cached.
+pub fn get_rfc(
+    cache: &RfcCache,
+    source: &impl RfcSource,
+    rfc_number: RfcNum,
+    is_offline: bool,
+) -> Result<(DocumentText, DocumentSource)>
+{
+    // Refresh before reading the cached copy, as it may be memory-mapped
+    if !is_offline &&
+        cache
+            .cached_rfc_age(rfc_number)
+            .is_ok_and(|age| age > MAX_CACHE_AGE)
+    {
+        let etag = cache.get_etag(rfc_number);
+
+        match refresh_cached_rfc(cache, source, rfc_number, etag.as_deref())
+        {
+            Ok(Some(content)) =>
+            {
+                return Ok((content.into(), DocumentSource::Network));
+            },
+            Ok(None) =>
+            {
+                debug!("Cached RFC {rfc_number} is current");
+                // Otherwise it's only checked again on the next open
+                if let Err(error) = cache.mark_rfc_fresh(rfc_number)
+                {
+                    warn!("{error:#}");
+                }
+            },
+            // The cached copy is still better than nothing
+            Err(error) =>
+            {
+                warn!(
+                    "Could not check RFC {rfc_number} for updates: {error:#}"
+                );
+            },
+        }
+    }
+
+    if let Ok(cached_content) = cache.get_cached_rfc_text(rfc_number)
+    {
+        info!("Using cached version of RFC {rfc_number}");
+        return Ok((cached_content, DocumentSource::Cache));
+    }
+
+    if is_offline
+    {
+        error!(
+            "RFC {rfc_number} unavailable: offline mode active and no cached \
+             copy found"
+        );
+
+        return Err(FetchError::Offline(rfc_number).into());
+    }
+    // Fetch RFC from network since it's not in cache
+    debug!("Fetching RFC {rfc_number} from network...");
+
+    let ConditionalFetch::Updated(content, etag) = source
+        .fetch_rfc_if_modified(rfc_number, None)
+        .with_context(|| format!("Failed to fetch RFC {rfc_number}"))?
+    else
+    {
+        bail!("RFC {rfc_number} was reported unmodified without an ETag");
+    };
+
+    // Cache the fetched content for future use.
+    cache
+        .cache_rfc(rfc_number, &content)
+        .with_context(|| format!("Could not cache RFC {rfc_number}"))?;
+    cache_etag(cache, rfc_number, etag.as_deref());
+
+    debug!("Cached RFC {rfc_number}");
+    Ok((content.into(), DocumentSource::Network))
+}
+
+/// Fetches a cached RFC again if it changed since it was cached.
+///
+/// # Arguments
+///
+/// * `cache` - The cache the RFC is in
+/// * `source` - The source to fetch the RFC from
+/// * `rfc_number` - The RFC to refresh
+/// * `etag` - The entity tag of the cached copy, if known
+///
+/// # Returns
+///
+/// The new content of the RFC, already cached, or `None` if the cached copy
+/// is current.
+///
+/// # Errors
+///
+/// Returns an error if the RFC can't be fetched or cached.
+fn refresh_cached_rfc(
+    cache: &RfcCache,
+    source: &impl RfcSource,
+    rfc_number: RfcNum,
+    etag: Option<&str>,
+) -> Result<Option<Box<str>>>
+{
+    match source.fetch_rfc_if_modified(rfc_number, etag)?
+    {
+        ConditionalFetch::NotModified => Ok(None),
+        ConditionalFetch::Updated(content, etag) =>
+        {
+            info!("RFC {rfc_number} changed since it was cached");
+            cache
+                .cache_rfc(rfc_number, &content)
+                .with_context(|| format!("Could not cache RFC {rfc_number}"))?;
+            cache_etag(cache, rfc_number, etag.as_deref());
+
+            Ok(Some(content))
+        },
+    }
+}
+
+/// Stores the entity tag of a cached RFC, only warning on failure.
+///
+/// # Arguments
+///
+/// * `cache` - The cache the RFC is in
+/// * `rfc_number` - The RFC the tag belongs to
+/// * `etag` - The entity tag sent along with the RFC
+fn cache_etag(cache: &RfcCache, rfc_number: RfcNum, etag: Option<&str>)
+{
+    // Without a tag the RFC is only not checked for updates
+    if let Err(error) = cache.cache_etag(rfc_number, etag)
+    {
+        warn!("{error:#}");
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::cell::Cell;
+    use std::fs::File;
+    use std::io::Write as _;
+    use std::time::SystemTime;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// Source serving a fixed text, counting the fetches.
+    #[derive(Default)]
+    struct MockSource
+    {
+        /// Number of fetches made.
+        fetches: Cell<usize>,
+    }
+
+    impl RfcSource for MockSource
+    {
+        fn fetch_rfc(&self, _rfc_number: RfcNum) -> Result<Box<str>>
+        {
+            self.fetches
+                .set(self.fetches.get().saturating_add(1));
+            Ok("Fetched RFC".into())
+        }
+    }
+
+    /// Source that fails if it's used at all.
+    struct UnreachableSource;
+
+    impl RfcSource for UnreachableSource
+    {
+        fn fetch_rfc(&self, _rfc_number: RfcNum) -> Result<Box<str>>
+        {
+            bail!("The network must not be used")
+        }
+    }
+
+    /// The RFC used across the tests.
+    fn rfc_number() -> RfcNum
+    {
+        RfcNum::new(2119).expect("its non-zero")
+    }
+
+    #[test]
+    fn test_offline_uses_cache() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        cache.cache_rfc(rfc_number(), "Cached RFC")?;
+
+        let (content, source) =
+            get_rfc(&cache, &UnreachableSource, rfc_number(), true)?;
+
+        assert_eq!(&*content, "Cached RFC");
+        assert_eq!(source, DocumentSource::Cache);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_offline_without_cache_fails() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+
+        let error = get_rfc(&cache, &UnreachableSource, rfc_number(), true)
+            .expect_err("uncached RFC loaded offline");
+
+        assert_eq!(
+            error.downcast_ref::<FetchError>(),
+            Some(&FetchError::Offline(rfc_number()))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_only_cache_creates_nothing() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache_dir = temp_dir.path().join("cache");
+        let cache = RfcCache::read_only(&cache_dir);
+
+        let error = get_rfc(&cache, &UnreachableSource, rfc_number(), true)
+            .expect_err("uncached RFC loaded from a missing cache");
+
+        assert_eq!(
+            error.downcast_ref::<FetchError>(),
+            Some(&FetchError::Offline(rfc_number()))
+        );
+        assert_eq!(fs::read_dir(temp_dir.path())?.count(), 0);
+
+        Ok(())
+    }
+
+    /// Source with the RFC at a fixed version, tagged by it.
+    struct VersionedSource
+    {
+        /// The entity tag of the current version.
+        etag: &'static str,
+    }
+
+    impl RfcSource for VersionedSource
+    {
+        fn fetch_rfc(&self, _rfc_number: RfcNum) -> Result<Box<str>>
+        {
+            Ok("Current RFC".into())
+        }
+
+        fn fetch_rfc_if_modified(
+            &self,
+            rfc_number: RfcNum,
+            etag: Option<&str>,
+        ) -> Result<ConditionalFetch>
+        {
+            if etag == Some(self.etag)
+            {
+                return Ok(ConditionalFetch::NotModified);
+            }
+
+            Ok(ConditionalFetch::Updated(
+                self.fetch_rfc(rfc_number)?,
+                Some(self.etag.to_owned()),
+            ))
+        }
+    }
+
+    /// Makes the cached copy of an RFC look older than [`MAX_CACHE_AGE`].
+    fn age_cached_rfc(cache: &RfcCache, rfc_number: RfcNum) -> Result<()>
+    {
+        File::options()
+            .write(true)
+            .open(cache.format_cache_path(rfc_number))?
+            .set_modified(SystemTime::UNIX_EPOCH)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cached_rfc_is_refreshed_if_modified() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        let source = VersionedSource { etag: "\"v2\"" };
+
+        // Fetched RFCs keep their tag
+        get_rfc(&cache, &source, rfc_number(), false)?;
+        assert_eq!(cache.get_etag(rfc_number()).as_deref(), Some("\"v2\""));
+
+        // A current copy is used as is, and not checked again for a while
+        cache.cache_rfc(rfc_number(), "Cached RFC")?;
+        age_cached_rfc(&cache, rfc_number())?;
+        let (content, document_source) =
+            get_rfc(&cache, &source, rfc_number(), false)?;
+        assert_eq!(&*content, "Cached RFC");
+        assert_eq!(document_source, DocumentSource::Cache);
+        assert!(cache.cached_rfc_age(rfc_number())? < MAX_CACHE_AGE);
+
+        // A recent copy isn't checked
+        cache.cache_etag(rfc_number(), Some("\"v1\""))?;
+        let (content, document_source) =
+            get_rfc(&cache, &source, rfc_number(), false)?;
+        assert_eq!(&*content, "Cached RFC");
+        assert_eq!(document_source, DocumentSource::Cache);
+
+        // An old outdated one is replaced
+        age_cached_rfc(&cache, rfc_number())?;
+        let (content, document_source) =
+            get_rfc(&cache, &source, rfc_number(), false)?;
+        assert_eq!(&*content, "Current RFC");
+        assert_eq!(document_source, DocumentSource::Network);
+        assert_eq!(&*cache.get_cached_rfc(rfc_number())?, "Current RFC");
+        assert_eq!(cache.get_etag(rfc_number()).as_deref(), Some("\"v2\""));
+
+        // Offline, nothing is checked
+        cache.cache_etag(rfc_number(), Some("\"v1\""))?;
+        age_cached_rfc(&cache, rfc_number())?;
+        let (_, document_source) =
+            get_rfc(&cache, &UnreachableSource, rfc_number(), true)?;
+        assert_eq!(document_source, DocumentSource::Cache);
+
+        // Failing checks fall back to the cached copy
+        let (_, document_source) =
+            get_rfc(&cache, &UnreachableSource, rfc_number(), false)?;
+        assert_eq!(document_source, DocumentSource::Cache);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_online_without_cache_fetches() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let cache = RfcCache::with_dir(temp_dir.path())?;
+        let source = MockSource::default();
+
+        let (content, document_source) =
+            get_rfc(&cache, &source, rfc_number(), false)?;
+
+        assert_eq!(&*content, "Fetched RFC");
+        assert_eq!(document_source, DocumentSource::Network);
+        assert_eq!(source.fetches.get(), 1);
+        // The fetched RFC is cached for the next time
+        assert_eq!(&*cache.get_cached_rfc(rfc_number())?, "Fetched RFC");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_rfc_file() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let text = "Key words for use in RFCs\n";
+
+        let plain_path = temp_dir.path().join("rfc2119.txt");
+        fs::write(&plain_path, text)?;
+        assert_eq!(&*read_rfc_file(&plain_path)?, text);
+
+        // Detected by content, not by the file name
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes())?;
+        let gzip_path = temp_dir.path().join("rfc2119");
+        fs::write(&gzip_path, encoder.finish()?)?;
+        assert_eq!(&*read_rfc_file(&gzip_path)?, text);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "bzip2")]
+    #[test]
+    fn test_read_bzip2_rfc_file() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let text = "Key words for use in RFCs\n";
+
+        let mut encoder = bzip2::write::BzEncoder::new(
+            Vec::new(),
+            bzip2::Compression::default(),
+        );
+        encoder.write_all(text.as_bytes())?;
+        let path = temp_dir.path().join("rfc2119.txt.bz2");
+        fs::write(&path, encoder.finish()?)?;
+        assert_eq!(&*read_rfc_file(&path)?, text);
+
+        // Truncated streams are reported, not read as text
+        fs::write(&path, BZIP2_MAGIC)?;
+        let error = read_rfc_file(&path).expect_err("decompressed");
+        assert!(format!("{error:#}").contains("Corrupt bzip2 stream"));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "xz2")]
+    #[test]
+    fn test_read_xz_rfc_file() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let text = "Key words for use in RFCs\n";
+
+        let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+        encoder.write_all(text.as_bytes())?;
+        let path = temp_dir.path().join("rfc2119.txt.xz");
+        fs::write(&path, encoder.finish()?)?;
+        assert_eq!(&*read_rfc_file(&path)?, text);
+
+        fs::write(&path, XZ_MAGIC)?;
+        let error = read_rfc_file(&path).expect_err("decompressed");
+        assert!(format!("{error:#}").contains("Corrupt xz stream"));
+
+        Ok(())
+    }
+
+    #[cfg(not(all(feature = "bzip2", feature = "xz2")))]
+    #[test]
+    fn test_read_unsupported_rfc_file() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("compressed");
+
+        for (magic, is_supported) in [
+            (BZIP2_MAGIC, cfg!(feature = "bzip2")),
+            (XZ_MAGIC, cfg!(feature = "xz2")),
+        ]
+        {
+            if is_supported
+            {
+                continue;
+            }
+
+            fs::write(&path, magic)?;
+            let error = read_rfc_file(&path).expect_err("decompressed");
+            assert!(format!("{error:#}").contains("not supported"));
+        }
+
+        Ok(())
+    }
+}
@@ -1,140 +1,1918 @@
-use std::time::Duration;
+use std::fs::File;
+use std::io::{self, BufWriter, ErrorKind, IsTerminal as _, Write, stdout};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode};
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use anyhow::{Context as _, Result, anyhow, bail};
-use clap::{ArgAction, ArgGroup, Command, arg, crate_version};
+use anyhow::{Context as _, Result, bail, ensure};
+use clap::{
+    Arg, ArgAction, ArgGroup, ArgMatches, Command, arg, crate_version,
+    value_parser,
+};
 use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use ratatui::Terminal;
 use ratatui::backend::Backend as RatatuiBackend;
-use rfc_reader::cache::RfcCache;
-use rfc_reader::client::RfcClient;
+use regex::Regex;
+use rfc_reader::authors::{AuthorAddress, parse_author_addresses};
+use rfc_reader::cache::{CACHE_DIR_ENV, CachedFile, RfcCache};
+use rfc_reader::client::{
+    ConditionalFetch, DEFAULT_TIMEOUT, DEFAULT_USER_AGENT, RfcClient, exit_code,
+};
+use rfc_reader::config::Config;
+use rfc_reader::grep::{
+    GrepContext, GrepFormat, count_hits, find_citations, find_sections,
+    grep_cache, write_citations, write_counts, write_hits, write_sections,
+};
+use rfc_reader::index::{
+    IndexEntry, is_index_truncated, join_rfc_numbers, parse_index,
+    parse_index_with_progress,
+};
+use rfc_reader::loader::{get_rfc, read_rfc_file};
 use rfc_reader::logging::{
     clear_log_files, get_log_files_dir_path, init_logging,
 };
-use rfc_reader::types::RfcNum;
+use rfc_reader::metadata::RfcMetadata;
+use rfc_reader::stats::UsageStats;
+use rfc_reader::types::{
+    DocumentSource, DocumentText, LineNumber, RfcNum, parse_rfc_number,
+};
 use rfc_reader::ui::guard::{init_panic_hook, init_tui};
-use rfc_reader::ui::{App, AppMode, AppStateFlags, Event, EventHandler};
+use rfc_reader::ui::theme::{Theme, ThemeName};
+use rfc_reader::ui::{
+    App, AppMode, AppStateFlags, CacheBrowser, CachedRfc, Event, EventHandler,
+    FetchPurpose, IndexBrowser, ReferenceList, TocPanel,
+};
+use serde::Serialize;
+
+/// Exit code of invalid command line arguments, like `EX_USAGE` of
+/// `sysexits.h`.
+const EXIT_USAGE: i32 = 64;
+
+fn main() -> ExitCode
+{
+    match run()
+    {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) =>
+        {
+            // Same as returning the error, with a code telling its class
+            eprintln!("Error: {error:?}");
+            ExitCode::from(exit_code(&error))
+        },
+    }
+}
 
-fn main() -> Result<()>
+/// Runs the app with the command line arguments.
+///
+/// # Errors
+///
+/// Returns an error if the app fails, mapped to an exit code by
+/// [`exit_code`].
+fn run() -> Result<()>
 {
     init_panic_hook();
+
+    let cache_dir = RfcCache::default_dir()?;
+
+    // Parse command line arguments, off the exit codes of the failures
+    let matches = match build_cli(&cache_dir)?.try_get_matches()
+    {
+        Ok(matches) => matches,
+        Err(error) if error.use_stderr() =>
+        {
+            error.print()?;
+            process::exit(EXIT_USAGE);
+        },
+        // Help and version
+        Err(error) => error.exit(),
+    };
+
+    let cache_dir = matches
+        .get_one::<PathBuf>("cache-dir")
+        .cloned()
+        .unwrap_or(cache_dir);
+    let is_safe = matches.get_flag("safe");
+    let cache = open_cache(&cache_dir, is_safe)?;
+
+    // Handle maintenance actions: clear cache, clear log, list cached RFCs
+    if let Some(result) = run_maintenance(&cache, &matches)
+    {
+        return result;
+    }
+
+    let config = Config::load().context("Failed to load config")?;
+    // Everything but `--cat` reads the cached RFCs as shown in the app
+    let cache = cache
+        .with_original_text(config.keep_original_text, config.keep_page_breaks);
+
+    if let Some(result) = run_cache_search(&cache, &matches)
+    {
+        return result;
+    }
+
+    let client = build_client(&matches, &config);
+    let is_offline = is_safe || matches.get_flag("offline");
+
+    let stats_path = stats_path(&config, is_safe);
+
+    let loader = RfcLoader {
+        cache: &cache,
+        client: &client,
+        is_offline,
+        is_safe,
+        stats_path: stats_path.as_deref(),
+        max_cache_bytes: config.max_cache_bytes(),
+    };
+
+    let (mut terminal, mut app) = if matches.get_flag("browse")
+    {
+        // Filled in the background once the event handler is running
+        (
+            init_tui()?,
+            App::with_index_browser(IndexBrowser::loading(), config),
+        )
+    }
+    else
+    {
+        if matches.get_flag("info")
+        {
+            return run_info(&matches, &loader, &config);
+        }
+
+        let (rfc_number, rfc_content, rfc_source) =
+            load_document(&matches, &loader)?;
+
+        if matches.get_flag("cat")
+        {
+            return print_rfc(&rfc_content);
+        }
+        let rfc_content = config.presented_text(rfc_content);
+
+        if matches.get_flag("toc")
+        {
+            return print_toc(
+                &rfc_content,
+                matches.get_flag("ranges"),
+                matches.get_flag("json"),
+            );
+        }
+
+        // Setup necessary components for the app
+        let terminal = init_tui()?;
+
+        let mut app = App::new(rfc_number, rfc_content, rfc_source, config);
+        loader.restore_position(&mut app);
+        loader.load_bookmarks(&mut app);
+
+        (terminal, app)
+    };
+
+    // Explicit theme takes precedence over the config, without persisting it
+    if let Some(theme) = matches.get_one::<String>("theme")
+    {
+        app.theme =
+            Theme::from_name(theme.parse()?).with_overrides(&app.config);
+    }
+
+    let event_handler = EventHandler::new(Duration::from_millis(200));
+
+    if matches.get_flag("browse")
+    {
+        loader.load_index_in_background(event_handler.sender());
+    }
+
+    // Just propagate any error from run_app
+    run_app(&mut terminal, app, &event_handler, &loader)
+}
+
+/// Gets the file to count the opened RFCs in.
+///
+/// # Arguments
+///
+/// * `config` - The user configuration
+/// * `is_safe` - Whether nothing may be written to disk
+///
+/// # Returns
+///
+/// The path of the usage stats, or `None` if usage isn't tracked or the
+/// path can't be determined.
+fn stats_path(config: &Config, is_safe: bool) -> Option<PathBuf>
+{
+    if !config.track_usage || is_safe
+    {
+        return None;
+    }
+
+    UsageStats::path()
+        .inspect_err(|error| warn!("Usage stats unavailable: {error:#}"))
+        .ok()
+}
+
+/// Sets up logging and the cache, both untouched in safe mode.
+///
+/// # Arguments
+///
+/// * `cache_dir` - The directory of the cache
+/// * `is_safe` - Whether nothing may be written to disk
+///
+/// # Returns
+///
+/// The cache, read-only in safe mode.
+///
+/// # Errors
+///
+/// Returns an error if logging can't be initialized or the cache directory
+/// can't be created.
+fn open_cache(cache_dir: &Path, is_safe: bool) -> Result<RfcCache>
+{
+    // Safe mode reads the existing cache only, without creating anything
+    if is_safe
+    {
+        return Ok(RfcCache::read_only(cache_dir));
+    }
+
     init_logging()?;
 
-    // Initialize cache
-    let cache = RfcCache::new().context("Failed to initialize cache")?;
+    RfcCache::with_dir(cache_dir).context("Failed to initialize cache")
+}
 
-    // Parse command line arguments
-    let matches = Command::new("rfc_reader")
+/// Loads the document given on the command line.
+///
+/// # Arguments
+///
+/// * `matches` - The parsed command line arguments
+/// * `loader` - The loader to get RFCs given by number with
+///
+/// # Returns
+///
+/// The RFC number, `None` for a URL without one, the content of the
+/// document and where it was loaded from.
+///
+/// # Errors
+///
+/// Returns an error if no RFC number is given or found for an RFC or file,
+/// or the document can't be read or fetched.
+fn load_document(
+    matches: &ArgMatches,
+    loader: &RfcLoader<'_>,
+) -> Result<(Option<RfcNum>, DocumentText, DocumentSource)>
+{
+    if let Some(file) = matches.get_one::<PathBuf>("file")
+    {
+        let rfc_number = document_rfc_number(matches)?;
+
+        return Ok((
+            Some(rfc_number),
+            DocumentText::from(read_rfc_file(file)?),
+            DocumentSource::File,
+        ));
+    }
+
+    if let Some(url) = matches.get_one::<String>("url")
+    {
+        ensure!(!loader.is_offline, "Unable to read {url} in offline mode");
+        // Drafts on the web have no RFC number
+        let rfc_number = document_rfc_number(matches).ok();
+
+        return Ok((
+            rfc_number,
+            DocumentText::from(loader.client.fetch_url(url)?),
+            DocumentSource::Url,
+        ));
+    }
+
+    // Get the RFC content - first check cache, then fetch from network if
+    // needed
+    let rfc_number = document_rfc_number(matches)?;
+    let (rfc_content, rfc_source) = loader.load_initial(rfc_number)?;
+
+    Ok((Some(rfc_number), rfc_content, rfc_source))
+}
+
+/// Gets the RFC number of the document given on the command line.
+///
+/// # Arguments
+///
+/// * `matches` - The parsed command line arguments
+///
+/// # Returns
+///
+/// The given RFC number, or the one in the name of the file or URL.
+///
+/// # Errors
+///
+/// Returns an error if no RFC number is given or found.
+fn document_rfc_number(matches: &ArgMatches) -> Result<RfcNum>
+{
+    let file = matches.get_one::<PathBuf>("file");
+    let url = matches.get_one::<String>("url");
+
+    match (matches.get_one("rfc"), file, url)
+    {
+        (Some(&rfc_number), ..) => Ok(rfc_number),
+        (None, Some(file), _) => rfc_number_from_path(file),
+        (None, _, Some(url)) => rfc_number_from_path(Path::new(url)),
+        (None, None, None) => bail!("RFC number is required"),
+    }
+}
+
+/// Takes the RFC number from a file name like `rfc8446.txt.gz`.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file
+///
+/// # Returns
+///
+/// The RFC number in the file name.
+///
+/// # Errors
+///
+/// Returns an error if the file name doesn't start with an RFC number.
+fn rfc_number_from_path(path: &Path) -> Result<RfcNum>
+{
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.split('.').next())
+        .and_then(|stem| parse_rfc_number(stem).ok())
+        .with_context(|| {
+            format!(
+                "No RFC number in the name of {}, give it as well",
+                path.display()
+            )
+        })
+}
+
+/// Runs the maintenance action given on the command line, if any.
+///
+/// # Arguments
+///
+/// * `cache` - The cache to maintain
+/// * `matches` - The parsed command line arguments
+///
+/// # Returns
+///
+/// The result of the action, or `None` if no action was given.
+fn run_maintenance(cache: &RfcCache, matches: &ArgMatches)
+-> Option<Result<()>>
+{
+    let result = if matches.get_flag("clear-cache")
+    {
+        cache
+            .clear()
+            .map(|()| println!("Cache cleared successfully"))
+    }
+    else if let Some(&rfc_number) = matches.get_one::<RfcNum>("remove")
+    {
+        cache
+            .remove_rfc(rfc_number)
+            .map(|is_removed| {
+                if is_removed
+                {
+                    println!("RFC {rfc_number} removed from the cache");
+                }
+                else
+                {
+                    println!("RFC {rfc_number} is not cached");
+                }
+            })
+    }
+    else if matches.get_flag("clear-logs")
+    {
+        clear_log_files().map(|()| println!("Log files cleared successfully"))
+    }
+    else if let Some(archive_path) =
+        matches.get_one::<PathBuf>("export-cache")
+    {
+        cache
+            .export_archive(archive_path)
+            .map(|count| {
+                println!(
+                    "Exported {count} files to {}",
+                    archive_path.display()
+                );
+            })
+    }
+    else if let Some(archive_path) =
+        matches.get_one::<PathBuf>("import-cache")
+    {
+        cache
+            .import_archive(archive_path)
+            .map(|count| {
+                println!(
+                    "Imported {count} files from {}",
+                    archive_path.display()
+                );
+            })
+    }
+    else if matches.get_flag("stats")
+    {
+        UsageStats::path()
+            .and_then(|path| UsageStats::load_from(&path))
+            .map(|stats| println!("{stats}"))
+    }
+    else if matches.get_flag("reset-stats")
+    {
+        UsageStats::path()
+            .and_then(|path| UsageStats::reset(&path))
+            .map(|()| println!("Usage stats reset successfully"))
+    }
+    else if matches.get_flag("theme-list")
+    {
+        // Only the built-in themes exist, taken by name
+        for name in ThemeName::VARIANTS
+        {
+            println!("{name}");
+        }
+        Ok(())
+    }
+    else if matches.get_flag("list")
+    {
+        // Print the list of all cached RFCs one per line
+        cache.print_list()
+    }
+    else
+    {
+        return None;
+    };
+
+    Some(result)
+}
+
+/// Runs the search across the cached RFCs given on the command line.
+///
+/// # Arguments
+///
+/// * `cache` - The cache to search
+/// * `matches` - The parsed command line arguments
+///
+/// # Returns
+///
+/// The result of the search, or `None` if no search was given.
+fn run_cache_search(
+    cache: &RfcCache,
+    matches: &ArgMatches,
+) -> Option<Result<()>>
+{
+    let result = if let Some(pattern) = matches.get_one::<String>("sections")
+    {
+        run_sections(cache, pattern, grep_format(matches))
+    }
+    else if let Some(&cited) = matches.get_one::<RfcNum>("referenced-by")
+    {
+        find_citations(cache, cited).and_then(|citations| {
+            write_citations(stdout().lock(), &citations, grep_format(matches))
+        })
+    }
+    else if let Some(pattern) = matches.get_one::<String>("grep")
+    {
+        let format = grep_format(matches);
+
+        // The specific counts take precedence over `-C`
+        let line_count = |id| {
+            matches
+                .get_one::<usize>(id)
+                .or_else(|| matches.get_one("context"))
+                .copied()
+                .unwrap_or(0)
+        };
+        let context_lines = GrepContext {
+            before: line_count("before-context"),
+            after: line_count("after-context"),
+        };
+
+        run_grep(
+            cache,
+            pattern,
+            matches.get_one::<PathBuf>("out"),
+            format,
+            matches.get_flag("count"),
+            context_lines,
+        )
+    }
+    else
+    {
+        return None;
+    };
+
+    Some(result)
+}
+
+/// Sets up the client by the config and the command line arguments.
+///
+/// # Arguments
+///
+/// * `matches` - The parsed command line arguments
+/// * `config` - The user configuration
+///
+/// # Returns
+///
+/// The client to fetch RFCs with.
+fn build_client(matches: &ArgMatches, config: &Config) -> RfcClient
+{
+    // The flag takes precedence over the config
+    let user_agent = matches
+        .get_one::<String>("user-agent")
+        .map(String::as_str)
+        .or(config.user_agent.as_deref())
+        .unwrap_or(DEFAULT_USER_AGENT);
+
+    RfcClient::new(DEFAULT_TIMEOUT, user_agent)
+        .with_page_breaks(config.keep_page_breaks)
+        .with_original_text(config.keep_original_text)
+}
+
+/// Builds the command line interface.
+///
+/// # Arguments
+///
+/// * `cache_dir` - The cache directory, to show its location in the help text
+///
+/// # Returns
+///
+/// The command to parse the arguments with.
+///
+/// # Errors
+///
+/// Returns an error if the config file location cannot be determined.
+fn build_cli(cache_dir: &Path) -> Result<Command>
+{
+    Ok(Command::new("rfc_reader")
         .about("A terminal-based RFC reader")
         .version(crate_version!())
         // Inform about the cache and log directory
         .after_help(format!(
             "This program caches RFCs to improve performance.\nThe cache is \
              stored in the following directory: {}\n\nThe log files are \
-             stored in: {}",
-            cache.cache_dir().display(),
-            get_log_files_dir_path().display()
+             stored in: {}\n\nThe config file is read from: {}",
+            cache_dir.display(),
+            get_log_files_dir_path().display(),
+            Config::path()?.display()
         ))
         // These args are irrelevant to `rfc`.
         .group(ArgGroup::new("maintenance").args([
             "clear-cache",
+            "remove",
             "clear-logs",
+            "export-cache",
+            "import-cache",
             "list",
+            "grep",
+            "sections",
+            "referenced-by",
+            "stats",
+            "reset-stats",
+            "theme-list",
         ]))
+        // Where the document comes from
+        .group(
+            ArgGroup::new("document")
+                .args(["rfc", "file", "url"])
+                .multiple(true),
+        )
+        // Commands that can output JSON
+        .group(
+            ArgGroup::new("json-output")
+                .args(["grep", "sections", "referenced-by", "toc", "info"])
+                .multiple(true),
+        )
         .args([
-            arg!([rfc] "RFC number to open")
+            arg!([rfc] "RFC number to open (e.g. 8446 or RFC8446)")
                 .value_name("NUMBER")
-                .value_parser(clap::value_parser!(RfcNum))
+                .value_parser(parse_rfc_number)
                 .index(1)
-                .required_unless_present("maintenance")
+                .required_unless_present_any([
+                    "maintenance",
+                    "browse",
+                    "file",
+                    "url",
+                ])
                 // Disallow giving a NUMBER together with those actions
-                .conflicts_with("maintenance"),
-            arg!(--"clear-cache" "Clear the RFC cache")
+                .conflicts_with_all(["maintenance", "browse"]),
+            arg!(--file <FILE> "Read the RFC from a file, compressed or not")
+                .long_help(
+                    "Read the RFC from a local file instead of the cache, \
+                     decompressing gzip, bzip2 and xz files. The RFC number \
+                     is taken from the file name unless given.",
+                )
+                .value_parser(value_parser!(PathBuf))
+                .conflicts_with_all(["maintenance", "browse"]),
+            arg!(--url <URL> "Read a plain text RFC or draft from a URL")
+                .long_help(
+                    "Read a plain text RFC or draft from an http or https \
+                     URL, without caching it. The RFC number is taken from \
+                     the file name in the URL unless given, drafts can go \
+                     without one.",
+                )
+                .conflicts_with_all(["maintenance", "browse", "file"]),
+            arg!(-l --list "List all cached RFCs").action(ArgAction::SetTrue),
+            arg!(--stats "Print the cache hit rate of the opened RFCs")
+                .long_help(
+                    "Print the cache hit rate of the opened RFCs, counted \
+                     locally if track_usage is enabled in the config",
+                )
                 .action(ArgAction::SetTrue),
-            arg!(--"clear-logs" "Clear the log files")
+            arg!(--"reset-stats" "Reset the cache hit counters")
                 .action(ArgAction::SetTrue),
-            arg!(-o --offline "Run in offline mode (only load cached RFCs)")
+            arg!(--browse "Browse the RFC index to pick an RFC to open")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("maintenance"),
+            arg!(--theme <THEME> "Color theme, auto detects the background")
+                .value_parser(ThemeName::VARIANTS),
+            arg!(--"theme-list" "List the names of the themes for --theme")
                 .action(ArgAction::SetTrue),
-            arg!(-l --list "List all cached RFCs").action(ArgAction::SetTrue),
+            arg!(--"user-agent" <USER_AGENT> "User-Agent sent to the server"),
+            arg!(--"cache-dir" <DIR> "Directory to keep the cache in")
+                .long_help(format!(
+                    "Directory to keep the cache in, created if needed. \
+                     Overrides the {CACHE_DIR_ENV} environment variable."
+                ))
+                .value_parser(value_parser!(PathBuf)),
         ])
-        .get_matches();
+        .args(cache_args())
+        .args(offline_args())
+        .args(grep_args())
+        .args(output_args()))
+}
 
-    // Handle maintenance actions: clear cache, clear log, list cached RFCs
-    if matches.get_flag("clear-cache")
+/// Builds the arguments maintaining the cache and the log files.
+///
+/// # Returns
+///
+/// The arguments to add to the command.
+fn cache_args() -> [Arg; 5]
+{
+    [
+        arg!(--"clear-cache" "Clear the RFC cache")
+            .long_help(
+                "Clear the RFC cache, keeping the reading positions and \
+                 bookmarks.",
+            )
+            .action(ArgAction::SetTrue),
+        arg!(--remove <NUMBER> "Remove an RFC from the cache")
+            .value_parser(parse_rfc_number),
+        arg!(--"clear-logs" "Clear the log files").action(ArgAction::SetTrue),
+        arg!(--"export-cache" <FILE> "Export the cache to a tar archive")
+            .long_help(
+                "Export the cache to a tar archive, gzipped if the file name \
+                 ends with .gz or .tgz",
+            )
+            .value_parser(value_parser!(PathBuf)),
+        arg!(--"import-cache" <FILE> "Import a tar archive into the cache")
+            .value_parser(value_parser!(PathBuf)),
+    ]
+}
+
+/// Builds the arguments limiting the network and disk access.
+///
+/// # Returns
+///
+/// The arguments to add to the command.
+fn offline_args() -> [Arg; 2]
+{
+    [
+        arg!(-o --offline "Run in offline mode (only load cached RFCs)")
+            .action(ArgAction::SetTrue),
+        arg!(--safe "Never write to disk or use the network")
+            .long_help(
+                "Never write to disk or use the network, only reading the \
+                 existing cache. Implies --offline, skips the log files, the \
+                 usage stats and saving the config.",
+            )
+            .action(ArgAction::SetTrue)
+            .conflicts_with_all([
+                "clear-cache",
+                "remove",
+                "clear-logs",
+                "export-cache",
+                "import-cache",
+                "reset-stats",
+                "url",
+                "out",
+            ]),
+    ]
+}
+
+/// Builds the arguments searching the cached RFCs.
+///
+/// # Returns
+///
+/// The arguments to add to the command.
+fn grep_args() -> [Arg; 8]
+{
+    [
+        arg!(--grep <PATTERN> "Search all cached RFCs for a regex"),
+        arg!(--count "Print the number of matching lines of each RFC")
+            .action(ArgAction::SetTrue)
+            .requires("grep"),
+        arg!(--out <FILE> "Write the search results to a file")
+            .value_parser(value_parser!(PathBuf))
+            .requires("grep"),
+        arg!(-C --context <NUM> "Print NUM lines around each search hit")
+            .value_parser(value_parser!(usize))
+            .requires("grep")
+            .conflicts_with("count"),
+        arg!(-A --"after-context" <NUM> "Print NUM lines after each hit")
+            .value_parser(value_parser!(usize))
+            .requires("grep")
+            .conflicts_with("count"),
+        arg!(-B --"before-context" <NUM> "Print NUM lines before each hit")
+            .value_parser(value_parser!(usize))
+            .requires("grep")
+            .conflicts_with("count"),
+        arg!(--sections <PATTERN> "List the sections of all cached RFCs")
+            .long_help(
+                "List the sections of all cached RFCs whose titles match a \
+                 regex, as found by the table of contents parser",
+            ),
+        arg!(--"referenced-by" <NUMBER> "List the cached RFCs referencing an RFC")
+            .long_help(
+                "List the cached RFCs referencing an RFC like RFC 8446 or \
+                 [RFC8446], with the line of the first reference and their \
+                 count",
+            )
+            .value_parser(parse_rfc_number),
+    ]
+}
+
+/// Builds the arguments printing a part of the document and exiting.
+///
+/// # Returns
+///
+/// The arguments to add to the command.
+fn output_args() -> [Arg; 5]
+{
+    [
+        arg!(--cat "Print the text of the RFC and exit")
+            .action(ArgAction::SetTrue)
+            .requires("document")
+            .conflicts_with("toc"),
+        arg!(--toc "Print the table of contents of the RFC")
+            .action(ArgAction::SetTrue)
+            .requires("document"),
+        arg!(--info "Print the metadata of the RFC from the index")
+            .action(ArgAction::SetTrue)
+            .requires("document")
+            .conflicts_with_all(["cat", "toc"]),
+        arg!(--ranges "Include the line range of each section in --toc")
+            .action(ArgAction::SetTrue)
+            .requires("toc"),
+        arg!(--json "Output the --grep, --sections, --referenced-by, --toc or --info results as JSON")
+            .action(ArgAction::SetTrue)
+            .requires("json-output"),
+    ]
+}
+
+/// Gets the output format of the cache searches.
+///
+/// # Arguments
+///
+/// * `matches` - The parsed command line arguments
+///
+/// # Returns
+///
+/// JSON if asked for, quickfix lines otherwise.
+fn grep_format(matches: &ArgMatches) -> GrepFormat
+{
+    if matches.get_flag("json")
+    {
+        GrepFormat::Json
+    }
+    else
+    {
+        GrepFormat::Quickfix
+    }
+}
+
+/// Searches the sections of the cached RFCs and writes the matching ones to
+/// `stdout`.
+///
+/// # Arguments
+///
+/// * `cache` - The cache to search
+/// * `pattern` - The regex to match the section titles against
+/// * `format` - The output format
+///
+/// # Errors
+///
+/// Returns an error if the pattern is invalid, the cache cannot be read or
+/// the sections cannot be written.
+fn run_sections(
+    cache: &RfcCache,
+    pattern: &str,
+    format: GrepFormat,
+) -> Result<()>
+{
+    let regex = Regex::new(pattern)
+        .with_context(|| format!("Invalid search pattern '{pattern}'"))?;
+    let sections = find_sections(cache, &regex)?;
+
+    write_sections(stdout().lock(), &sections, format)
+}
+
+/// Searches the cached RFCs and writes the hits.
+///
+/// # Arguments
+///
+/// * `cache` - The cache to search
+/// * `pattern` - The regex to search for
+/// * `out_path` - The file to write the hits to, `stdout` if `None`
+/// * `format` - The output format
+/// * `is_count` - Whether to write the hit count of each RFC instead of the
+///   hits
+/// * `context_lines` - The number of lines around each hit to write
+///
+/// # Errors
+///
+/// Returns an error if the pattern is invalid, the cache cannot be read or
+/// the hits cannot be written.
+fn run_grep(
+    cache: &RfcCache,
+    pattern: &str,
+    out_path: Option<&PathBuf>,
+    format: GrepFormat,
+    is_count: bool,
+    context_lines: GrepContext,
+) -> Result<()>
+{
+    let regex = Regex::new(pattern)
+        .with_context(|| format!("Invalid search pattern '{pattern}'"))?;
+    let hits = grep_cache(cache, &regex, context_lines)?;
+    let counts = is_count.then(|| count_hits(&hits));
+
+    // Writes either the counts or the hits, returning how many were written
+    let write = |writer: &mut dyn Write| -> Result<usize> {
+        if let Some(counts) = &counts
+        {
+            write_counts(writer, counts, format)?;
+            Ok(counts.len())
+        }
+        else
+        {
+            write_hits(writer, &hits, format)?;
+            Ok(hits.len())
+        }
+    };
+
+    if let Some(out_path) = out_path
+    {
+        let file = File::create(out_path).with_context(|| {
+            format!("Failed to create {}", out_path.display())
+        })?;
+        let written = write(&mut BufWriter::new(file))?;
+
+        println!("Wrote {written} results to {}", out_path.display());
+    }
+    else
+    {
+        write(&mut stdout().lock())?;
+    }
+
+    Ok(())
+}
+
+/// Version of the `--info --json` output, bumped on incompatible changes.
+const INFO_JSON_VERSION: u32 = 1;
+
+/// The metadata of an RFC as printed by `--info --json`.
+#[derive(Serialize)]
+struct RfcInfo<'entry>
+{
+    /// Version of the shape of this object.
+    version: u32,
+    /// The metadata, with the keys of [`IndexEntry`].
+    #[serde(flatten)]
+    entry: &'entry IndexEntry,
+    /// The authors found in the authors' addresses section.
+    addresses: &'entry [AuthorAddress],
+}
+
+/// Prints the progress of a download on a single line of `stderr`.
+///
+/// # Arguments
+///
+/// * `read` - The bytes downloaded so far
+/// * `total` - The size of the download, if known
+fn print_download_progress(read: u64, total: Option<u64>)
+{
+    /// Bytes in a KiB.
+    const KIB: u64 = 1024;
+
+    // Overwrite the previous report, clearing the rest of the line
+    match total
+    {
+        Some(total) => eprint!(
+            "\rDownloading the RFC index: {} / {} KiB\x1b[K",
+            read / KIB,
+            total / KIB
+        ),
+        None =>
+        {
+            eprint!("\rDownloading the RFC index: {} KiB\x1b[K", read / KIB);
+        },
+    }
+}
+
+/// Prints the metadata of the RFC given on the command line.
+///
+/// The index entry is preferred over the header block of the RFC, which
+/// lacks the authors and the RFCs updating or obsoleting this one, and gives
+/// the category as the status. The RFC is only loaded for the header block
+/// or if it's given as a file, otherwise the authors' addresses are read
+/// from its cached copy if there is one.
+///
+/// # Arguments
+///
+/// * `matches` - The parsed command line arguments
+/// * `loader` - The loader to look up the index and the RFC with
+/// * `config` - The config to present the loaded RFC by
+///
+/// # Errors
+///
+/// Returns an error if the RFC has to be loaded but can't be, or if writing
+/// to `stdout` fails.
+fn run_info(
+    matches: &ArgMatches,
+    loader: &RfcLoader<'_>,
+    config: &Config,
+) -> Result<()>
+{
+    let rfc_number = document_rfc_number(matches)?;
+
+    let (entry, content) = match find_index_entry(
+        loader.cache,
+        loader.client,
+        loader.is_offline,
+        rfc_number,
+    )
+    {
+        // Not fetched just for the addresses
+        Some(entry) if matches.get_one::<PathBuf>("file").is_none() => (
+            entry,
+            loader
+                .cache
+                .get_presented_rfc_text(rfc_number)
+                .ok(),
+        ),
+        entry =>
+        {
+            let (_, content, _) = load_document(matches, loader)?;
+            let content = config.presented_text(content);
+            let entry =
+                entry.unwrap_or_else(|| header_entry(rfc_number, &content));
+
+            (entry, Some(content))
+        },
+    };
+
+    let addresses = content
+        .as_deref()
+        .map(parse_author_addresses)
+        .unwrap_or_default();
+
+    print_info(&entry, &addresses, matches.get_flag("json"))
+}
+
+/// Finds the index entry of an RFC.
+///
+/// # Arguments
+///
+/// * `cache` - The cache to look up the index in
+/// * `client` - The client to fetch the index with if it isn't cached
+/// * `is_offline` - Whether fetching is disabled
+/// * `rfc_number` - The RFC to describe
+///
+/// # Returns
+///
+/// The entry of the RFC, or `None` if the index can't be loaded or lacks it.
+fn find_index_entry(
+    cache: &RfcCache,
+    client: &RfcClient,
+    is_offline: bool,
+    rfc_number: RfcNum,
+) -> Option<IndexEntry>
+{
+    let mut has_progress = false;
+    let index_text =
+        load_index_text(cache, client, is_offline, |read, total| {
+            if io::stderr().is_terminal()
+            {
+                print_download_progress(read, total);
+                has_progress = true;
+            }
+        });
+
+    // Leave no trace of the progress before the output
+    if has_progress
+    {
+        eprint!("\r\x1b[K");
+    }
+
+    index_text
+        .inspect_err(|error| warn!("Could not load the RFC index: {error:#}"))
+        .ok()
+        .and_then(|index| {
+            parse_index(&index)
+                .into_iter()
+                .find(|entry| entry.number == rfc_number)
+        })
+}
+
+/// Builds the metadata of an RFC from its header block.
+///
+/// # Arguments
+///
+/// * `rfc_number` - The RFC to describe
+/// * `content` - The text of the RFC
+///
+/// # Returns
+///
+/// The metadata found in the header block.
+fn header_entry(rfc_number: RfcNum, content: &str) -> IndexEntry
+{
+    let metadata = RfcMetadata::parse(content);
+    let mut entry = IndexEntry::new(
+        rfc_number,
+        metadata.title.as_deref().unwrap_or_default(),
+    );
+    entry.status = metadata
+        .category
+        .map(|category| category.to_string().into());
+    entry.obsoletes = metadata.obsoletes;
+    entry.updates = metadata.updates;
+    entry
+}
+
+/// Prints the metadata of an RFC.
+///
+/// # Arguments
+///
+/// * `entry` - The metadata to print
+/// * `addresses` - The authors found in the authors' addresses section
+/// * `as_json` - Whether to print it as a versioned JSON object
+///
+/// # Errors
+///
+/// Returns an error if writing to `stdout` fails.
+fn print_info(
+    entry: &IndexEntry,
+    addresses: &[AuthorAddress],
+    as_json: bool,
+) -> Result<()>
+{
+    let mut stdout = stdout().lock();
+
+    if as_json
+    {
+        let info = RfcInfo {
+            version: INFO_JSON_VERSION,
+            entry,
+            addresses,
+        };
+        serde_json::to_writer_pretty(&mut stdout, &info)
+            .context("Failed to write info as JSON")?;
+        writeln!(stdout)?;
+        return Ok(());
+    }
+
+    writeln!(stdout, "RFC {}: {}", entry.number, entry.title)?;
+    if !entry.authors.is_empty()
+    {
+        writeln!(stdout, "Authors: {}", entry.authors.join("; "))?;
+    }
+    for (label, value) in [("Date", &entry.date), ("Status", &entry.status)]
+    {
+        if let Some(value) = value
+        {
+            writeln!(stdout, "{label}: {value}")?;
+        }
+    }
+    for (label, rfc_numbers) in [
+        ("Obsoletes", &entry.obsoletes),
+        ("Obsoleted by", &entry.obsoleted_by),
+        ("Updates", &entry.updates),
+        ("Updated by", &entry.updated_by),
+    ]
+    {
+        if !rfc_numbers.is_empty()
+        {
+            writeln!(stdout, "{label}: {}", join_rfc_numbers(rfc_numbers))?;
+        }
+    }
+    if !addresses.is_empty()
+    {
+        writeln!(stdout, "Addresses:")?;
+    }
+    for address in addresses
+    {
+        write!(stdout, "  {}", address.name)?;
+        if let Some(organization) = &address.organization
+        {
+            write!(stdout, ", {organization}")?;
+        }
+        if let Some(email) = &address.email
+        {
+            write!(stdout, " <{email}>")?;
+        }
+        writeln!(stdout)?;
+    }
+
+    Ok(())
+}
+
+/// A `ToC` entry as printed by `--toc`.
+#[derive(Serialize)]
+struct TocOutlineEntry<'entry>
+{
+    /// The title of the section.
+    title: &'entry str,
+    /// The line of the section title, starting from 1.
+    line: LineNumber,
+    /// The lines of the section, from its title up to the next section.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    range: Option<Range<LineNumber>>,
+}
+
+/// Prints the table of contents of an RFC.
+///
+/// # Arguments
+///
+/// * `content` - The content of the RFC
+/// * `with_ranges` - Whether to include the line range of each section
+/// * `as_json` - Whether to print JSON instead of text
+///
+/// # Errors
+///
+/// Returns an error if the output cannot be written.
+fn print_toc(content: &str, with_ranges: bool, as_json: bool) -> Result<()>
+{
+    let toc_panel = TocPanel::new(content);
+    let entries: Vec<TocOutlineEntry> = toc_panel
+        .section_ranges(content.lines().count())
+        .map(|(entry, range)| TocOutlineEntry {
+            title: entry.title.trim(),
+            line: entry.line_number.saturating_add(1),
+            // Shown the same way as the line numbers
+            range: with_ranges.then(|| {
+                range.start.saturating_add(1)..range.end.saturating_add(1)
+            }),
+        })
+        .collect();
+
+    let mut stdout = stdout().lock();
+
+    if as_json
+    {
+        serde_json::to_writer_pretty(&mut stdout, &entries)
+            .context("Failed to write ToC as JSON")?;
+        writeln!(stdout)?;
+    }
+    else
+    {
+        for entry in &entries
+        {
+            match &entry.range
+            {
+                Some(range) => writeln!(
+                    stdout,
+                    "{}..{} {}",
+                    range.start, range.end, entry.title
+                )?,
+                None => writeln!(stdout, "{} {}", entry.line, entry.title)?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the text of an RFC as is, without adding a final newline.
+///
+/// A reader closing the pipe early, like `head`, isn't an error.
+///
+/// # Arguments
+///
+/// * `content` - The text of the RFC
+///
+/// # Errors
+///
+/// Returns an error if writing to `stdout` fails.
+fn print_rfc(content: &str) -> Result<()>
+{
+    let mut stdout = stdout().lock();
+
+    match write!(stdout, "{content}").and_then(|()| stdout.flush())
+    {
+        Err(error) if error.kind() != ErrorKind::BrokenPipe =>
+        {
+            Err(error).context("Failed to print the RFC")
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Number of index entries posted to the app at once while loading.
+const INDEX_BATCH_SIZE: usize = 500;
+
+/// Loads the text of the RFC index.
+///
+/// The cached index is used if present, otherwise it's fetched and cached.
+///
+/// # Arguments
+///
+/// * `cache` - The cache to look up the index in first
+/// * `client` - The client to fetch an uncached index with
+/// * `is_offline` - Whether fetching is disabled
+/// * `on_progress` - Called with the bytes downloaded so far and the total, if
+///   the index is fetched
+///
+/// # Returns
+///
+/// The text of the index.
+///
+/// # Errors
+///
+/// Returns an error if the index is not cached and can't be fetched.
+fn load_index_text(
+    cache: &RfcCache,
+    client: &RfcClient,
+    is_offline: bool,
+    on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<Box<str>>
+{
+    let cached_content = cache.get_cached_index().ok();
+
+    match cached_content
+    {
+        Some(content) if !is_index_truncated(&content) =>
+        {
+            info!("Using cached RFC index");
+            return Ok(content);
+        },
+        // Better than nothing without the network
+        Some(content) if is_offline =>
+        {
+            warn!("Using truncated cached RFC index in offline mode");
+            return Ok(content);
+        },
+        Some(_) => warn!("Cached RFC index is truncated, fetching it again"),
+        None if is_offline =>
+        {
+            bail!("RFC index is not cached and offline mode is active");
+        },
+        None =>
+        {},
+    }
+
+    debug!("Fetching RFC index from network...");
+    let content = client.fetch_rfc_index_with_progress(on_progress)?;
+
+    if is_index_truncated(&content)
+    {
+        warn!("Fetched RFC index is truncated, not caching it");
+    }
+    else if let Err(error) = cache.cache_index(&content)
+    {
+        warn!("Could not cache RFC index: {error:#}");
+    }
+
+    Ok(content)
+}
+
+/// Loads RFCs into the app while it's running.
+struct RfcLoader<'loader>
+{
+    /// Cache to look up RFCs in first.
+    cache: &'loader RfcCache,
+    /// Client to fetch uncached RFCs with.
+    client: &'loader RfcClient,
+    /// Whether network access is disabled.
+    is_offline: bool,
+    /// Whether writing to disk is disabled, including the config.
+    is_safe: bool,
+    /// File to count the opened RFCs in, `None` if not tracked.
+    stats_path: Option<&'loader Path>,
+    /// Size the cached RFCs may take up, `None` if unlimited.
+    max_cache_bytes: Option<u64>,
+}
+
+impl RfcLoader<'_>
+{
+    /// Loads the RFC to start the app with.
+    ///
+    /// Unlike [`Self::open`], an uncached RFC is fetched in the foreground,
+    /// before the terminal is set up.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC to load
+    ///
+    /// # Returns
+    ///
+    /// The content of the RFC and where it was loaded from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RFC is not cached and can't be fetched, or if
+    /// the fetched RFC can't be cached.
+    fn load_initial(
+        &self,
+        rfc_number: RfcNum,
+    ) -> Result<(DocumentText, DocumentSource)>
+    {
+        let loaded =
+            get_rfc(self.cache, self.client, rfc_number, self.is_offline)?;
+        self.record_open(loaded.1);
+        if loaded.1 == DocumentSource::Network
+        {
+            self.enforce_cache_limit();
+        }
+
+        Ok(loaded)
+    }
+
+    /// Counts an opened RFC if usage tracking is enabled, only warning on
+    /// failure.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Where the RFC was loaded from
+    fn record_open(&self, source: DocumentSource)
+    {
+        if let Some(path) = self.stats_path &&
+            let Err(error) = UsageStats::record(path, source)
+        {
+            warn!("Could not record usage stats: {error:#}");
+        }
+    }
+
+    /// Lists the cached RFCs.
+    ///
+    /// The titles are left to [`Self::load_titles_in_background`], reading
+    /// every cached RFC would block the UI on large caches.
+    ///
+    /// # Returns
+    ///
+    /// A browser of the cached RFCs in ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be read.
+    fn cache_browser(&self) -> Result<CacheBrowser>
+    {
+        let entries = self
+            .cache
+            .cached_entries()?
+            .into_iter()
+            .filter_map(|entry| match entry.file
+            {
+                CachedFile::Rfc(number) => Some(CachedRfc {
+                    number,
+                    title: None,
+                    is_title_loaded: false,
+                    size: entry.size,
+                }),
+                CachedFile::Index | CachedFile::Unknown(_) => None,
+            })
+            .collect();
+
+        Ok(CacheBrowser::new(entries))
+    }
+
+    /// Looks up the titles of the cached RFCs on a background thread.
+    ///
+    /// Each title is posted to the event channel as soon as it's read.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_numbers` - The cached RFCs to look up
+    /// * `event_sender` - The sender to post the titles to
+    fn load_titles_in_background(
+        &self,
+        rfc_numbers: Vec<RfcNum>,
+        event_sender: Sender<Event>,
+    )
+    {
+        let cache = self.cache.clone();
+
+        thread::spawn(move || {
+            for rfc_number in rfc_numbers
+            {
+                let title = cache.cached_title(rfc_number);
+
+                // Stop once the app has quit
+                if event_sender
+                    .send(Event::CachedTitleLoaded { rfc_number, title })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Loads the entries of the RFC index on a background thread.
+    ///
+    /// The entries are posted to the event channel in batches as they're
+    /// parsed, so the browser fills in incrementally.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_sender` - The sender to post the entries to
+    fn load_index_in_background(&self, event_sender: Sender<Event>)
+    {
+        let cache = self.cache.clone();
+        let client = self.client.clone();
+        let is_offline = self.is_offline;
+
+        thread::spawn(move || {
+            match load_index_text(&cache, &client, is_offline, |_, _| {})
+            {
+                Ok(content) =>
+                {
+                    parse_index_with_progress(
+                        &content,
+                        INDEX_BATCH_SIZE,
+                        |entries, percent| {
+                            // The app may have quit in the meantime
+                            let _ = event_sender
+                                .send(Event::IndexLoaded { entries, percent });
+                        },
+                    );
+                },
+                Err(error) =>
+                {
+                    let _ = event_sender.send(Event::IndexFailed { error });
+                },
+            }
+        });
+    }
+
+    /// Opens an RFC in the app.
+    ///
+    /// Cached RFCs are loaded right away, others are fetched on a background
+    /// thread which posts the result to the event channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `app` - The app to open the RFC in
+    /// * `rfc_number` - The RFC to open
+    /// * `event_sender` - The sender to post the fetch result to
+    fn open(
+        &self,
+        app: &mut App,
+        rfc_number: RfcNum,
+        event_sender: Sender<Event>,
+    )
+    {
+        if let Ok(cached_content) = self.cache.get_cached_rfc_text(rfc_number)
+        {
+            info!("Using cached version of RFC {rfc_number}");
+            self.show_document(
+                app,
+                rfc_number,
+                cached_content,
+                DocumentSource::Cache,
+            );
+            return;
+        }
+
+        self.fetch(app, rfc_number, FetchPurpose::Open, event_sender);
+    }
+
+    /// Replaces the document of the app with an opened RFC.
+    ///
+    /// The position in the previous RFC is stored, and the stored position
+    /// and bookmarks of the opened one are restored.
+    ///
+    /// # Arguments
+    ///
+    /// * `app` - The app to show the RFC in
+    /// * `rfc_number` - The opened RFC
+    /// * `content` - The content of the RFC
+    /// * `source` - Where the RFC was loaded from
+    fn show_document(
+        &self,
+        app: &mut App,
+        rfc_number: RfcNum,
+        content: DocumentText,
+        source: DocumentSource,
+    )
+    {
+        self.save_position(app);
+        app.load_document(rfc_number, content, source);
+        self.restore_position(app);
+        self.load_bookmarks(app);
+        self.record_open(source);
+    }
+
+    /// Scrolls to the stored position of the current RFC, only warning on
+    /// failure.
+    ///
+    /// # Arguments
+    ///
+    /// * `app` - The app showing the RFC
+    fn restore_position(&self, app: &mut App)
+    {
+        let Some(rfc_number) = app.rfc_number
+        else
+        {
+            return;
+        };
+
+        match self.cache.load_position(rfc_number)
+        {
+            Ok(Some(line)) => app.restore_scroll_pos(line),
+            Ok(None) =>
+            {},
+            Err(error) => warn!("Could not restore the position: {error:#}"),
+        }
+    }
+
+    /// Stores the position in the current RFC, only warning on failure.
+    ///
+    /// Not stored in safe mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `app` - The app showing the RFC
+    fn save_position(&self, app: &App)
+    {
+        if let Some(rfc_number) = app.rfc_number &&
+            !self.is_safe &&
+            let Err(error) = self
+                .cache
+                .save_position(rfc_number, app.current_scroll_pos)
+        {
+            warn!("Could not save the position: {error:#}");
+        }
+    }
+
+    /// Loads the stored bookmarks of the current RFC, only warning on
+    /// failure.
+    ///
+    /// # Arguments
+    ///
+    /// * `app` - The app showing the RFC
+    fn load_bookmarks(&self, app: &mut App)
     {
-        cache.clear()?;
-        println!("Cache cleared successfully");
-        return Ok(());
+        let Some(rfc_number) = app.rfc_number
+        else
+        {
+            return;
+        };
+
+        match self.cache.load_bookmarks(rfc_number)
+        {
+            Ok(bookmarks) => app.set_bookmarks(bookmarks),
+            Err(error) => warn!("Could not load the bookmarks: {error:#}"),
+        }
     }
-    else if matches.get_flag("clear-logs")
+
+    /// Stores the bookmarks of the current RFC, only warning on failure.
+    ///
+    /// Kept in memory only in safe mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `app` - The app showing the RFC
+    fn save_bookmarks(&self, app: &App)
     {
-        clear_log_files()?;
-        println!("Log files cleared successfully");
-        return Ok(());
+        if let Some(rfc_number) = app.rfc_number &&
+            !self.is_safe &&
+            let Err(error) = self
+                .cache
+                .save_bookmarks(rfc_number, &app.bookmarks)
+        {
+            warn!("Could not save the bookmarks: {error:#}");
+        }
     }
-    else if matches.get_flag("list")
+
+    /// Peeks at an RFC in the app, keeping the current document.
+    ///
+    /// Fetched like [`Self::open`] when not cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `app` - The app to show the peek in
+    /// * `rfc_number` - The RFC to peek at
+    /// * `event_sender` - The sender to post the fetch result to
+    fn peek(
+        &self,
+        app: &mut App,
+        rfc_number: RfcNum,
+        event_sender: Sender<Event>,
+    )
     {
-        // Print the list of all cached RFCs one per line
-        cache.print_list();
-        return Ok(());
+        if let Ok(cached_content) = self.cache.get_cached_rfc_text(rfc_number)
+        {
+            app.show_peek(rfc_number, &cached_content);
+            return;
+        }
+
+        self.fetch(app, rfc_number, FetchPurpose::Peek, event_sender);
     }
 
-    // Setup client
-    let client = RfcClient::default();
+    /// Fetches an RFC on a background thread.
+    ///
+    /// A reload sends the entity tag of the cached copy, so an unchanged RFC
+    /// isn't downloaded again.
+    ///
+    /// # Arguments
+    ///
+    /// * `app` - The app to wait for the fetch in
+    /// * `rfc_number` - The RFC to fetch
+    /// * `purpose` - What the RFC is fetched for
+    /// * `event_sender` - The sender to post the fetch result to
+    fn fetch(
+        &self,
+        app: &mut App,
+        rfc_number: RfcNum,
+        purpose: FetchPurpose,
+        event_sender: Sender<Event>,
+    )
+    {
+        if self.is_offline
+        {
+            app.show_message(match purpose
+            {
+                FetchPurpose::Open | FetchPurpose::Peek => format!(
+                    "RFC {rfc_number} is not cached and offline mode is active"
+                ),
+                FetchPurpose::Reload =>
+                {
+                    format!("Can't reload RFC {rfc_number} in offline mode")
+                },
+            });
+            return;
+        }
 
-    // Get RFC if specified
-    let rfc_number: RfcNum = *matches
-        .get_one("rfc")
-        .ok_or(anyhow!("RFC number is required"))?;
+        debug!("Fetching RFC {rfc_number} in the background...");
+        let fetch_id = app.start_fetch(rfc_number, purpose);
+        // Only a reload of the cached copy has one at hand to compare with
+        let is_cached_copy = matches!(
+            app.rfc_source,
+            DocumentSource::Cache | DocumentSource::Network
+        );
+        let etag = (purpose == FetchPurpose::Reload && is_cached_copy)
+            .then(|| self.cache.get_etag(rfc_number))
+            .flatten();
 
-    // Get the RFC content - first check cache, then fetch from network if
-    // needed
-    let rfc_content = if let Ok(cached_content) =
-        cache.get_cached_rfc(rfc_number)
+        // The thread is detached, a canceled fetch is left to finish on its
+        // own and its result is discarded.
+        let client = self.client.clone();
+        thread::spawn(move || {
+            let result =
+                client.fetch_rfc_if_modified(rfc_number, etag.as_deref());
+            // The app may have quit in the meantime
+            let _ = event_sender.send(Event::FetchFinished {
+                fetch_id,
+                rfc_number,
+                result,
+            });
+        });
+    }
+
+    /// Caches a fetched RFC, only warning on failure.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The fetched RFC
+    /// * `content` - The fetched content
+    /// * `etag` - The entity tag sent along with the RFC, if any
+    fn cache_fetched(
+        &self,
+        rfc_number: RfcNum,
+        content: &str,
+        etag: Option<&str>,
+    )
     {
-        info!("Using cached version of RFC {rfc_number}");
-        cached_content
+        if let Err(error) = self.cache.cache_rfc(rfc_number, content)
+        {
+            warn!("Could not cache RFC {rfc_number}: {error:#}");
+        }
+        // Also replaces the tag of an older copy
+        else if let Err(error) = self.cache.cache_etag(rfc_number, etag)
+        {
+            warn!("{error:#}");
+        }
+
+        self.enforce_cache_limit();
     }
-    else
+
+    /// Evicts the least recently used RFCs past the cache size limit, only
+    /// warning on failure.
+    fn enforce_cache_limit(&self)
     {
-        let is_offline = matches.get_flag("offline");
-        if is_offline
+        let Some(max_bytes) = self.max_cache_bytes
+        else
         {
-            error!(
-                "RFC {rfc_number} unavailable: offline mode active and no \
-                 cached copy found"
-            );
+            return;
+        };
 
-            bail!(
-                "Unable to access RFC {rfc_number} - network access disabled \
-                 in offline mode and RFC not cached locally"
-            );
+        match self.cache.enforce_size_limit(max_bytes)
+        {
+            Ok(0) =>
+            {},
+            Ok(freed_bytes) =>
+            {
+                info!("Evicted {freed_bytes} bytes of cached RFCs");
+            },
+            Err(error) => warn!("Could not limit the cache size: {error:#}"),
         }
-        // Fetch RFC from network since it's not in cache
-        debug!("Fetching RFC {rfc_number} from network...");
-
-        let content = client
-            .fetch_rfc(rfc_number)
-            .with_context(|| format!("Failed to fetch RFC {rfc_number}"))?;
+    }
 
-        // Cache the fetched content for future use.
-        cache
-            .cache_rfc(rfc_number, &content)
-            .with_context(|| format!("Could not cache RFC {rfc_number}"))?;
+    /// Handles the result of a background fetch.
+    ///
+    /// # Arguments
+    ///
+    /// * `app` - The app to load the RFC into
+    /// * `rfc_number` - The fetched RFC
+    /// * `purpose` - What the RFC was fetched for
+    /// * `result` - The fetched content or the error that occurred
+    fn finish(
+        &self,
+        app: &mut App,
+        rfc_number: RfcNum,
+        purpose: FetchPurpose,
+        result: Result<ConditionalFetch>,
+    )
+    {
+        // A reload isn't counted as an open, the RFC was counted when it was
+        // opened
+        match (purpose, result)
+        {
+            (FetchPurpose::Reload, Ok(ConditionalFetch::NotModified)) =>
+            {
+                // Otherwise it's checked again on the next open
+                if let Err(error) = self.cache.mark_rfc_fresh(rfc_number)
+                {
+                    warn!("{error:#}");
+                }
+                app.rfc_source = DocumentSource::Network;
+                app.show_message(format!("RFC {rfc_number} is unchanged"));
+            },
+            // The current document may be mapped from the cached file, so it's
+            // replaced before the file is rewritten
+            (
+                FetchPurpose::Reload,
+                Ok(ConditionalFetch::Updated(content, etag)),
+            ) =>
+            {
+                if app.reload_document(content.clone().into())
+                {
+                    self.cache_fetched(rfc_number, &content, etag.as_deref());
+                    app.show_message(format!("RFC {rfc_number} refreshed"));
+                }
+                // The body on screen is the cached copy, only its tag and age
+                // are brought up to date
+                else if matches!(
+                    app.rfc_source,
+                    DocumentSource::Cache | DocumentSource::Network
+                )
+                {
+                    if let Err(error) = self
+                        .cache
+                        .cache_etag(rfc_number, etag.as_deref())
+                    {
+                        warn!("{error:#}");
+                    }
+                    if let Err(error) = self.cache.mark_rfc_fresh(rfc_number)
+                    {
+                        warn!("{error:#}");
+                    }
+                    app.rfc_source = DocumentSource::Network;
+                    app.show_message(format!("RFC {rfc_number} is unchanged"));
+                }
+                else
+                {
+                    self.cache_fetched(rfc_number, &content, etag.as_deref());
+                    app.show_message(format!("RFC {rfc_number} is unchanged"));
+                }
+            },
+            (
+                FetchPurpose::Open,
+                Ok(ConditionalFetch::Updated(content, etag)),
+            ) =>
+            {
+                self.cache_fetched(rfc_number, &content, etag.as_deref());
+                self.show_document(
+                    app,
+                    rfc_number,
+                    content.into(),
+                    DocumentSource::Network,
+                );
+            },
+            // Also cached, so opening the RFC afterwards is instant
+            (
+                FetchPurpose::Peek,
+                Ok(ConditionalFetch::Updated(content, etag)),
+            ) =>
+            {
+                self.cache_fetched(rfc_number, &content, etag.as_deref());
+                app.show_peek(rfc_number, &content);
+            },
+            // Fetched without an entity tag, so the server had none to match
+            (_, Ok(ConditionalFetch::NotModified)) =>
+            {
+                error!(
+                    "RFC {rfc_number} was reported unmodified without an ETag"
+                );
+                app.show_message(format!("Failed to fetch RFC {rfc_number}"));
+            },
+            (_, Err(error)) =>
+            {
+                error!("Failed to fetch RFC {rfc_number}: {error:#}");
+                app.show_message(format!(
+                    "Failed to fetch RFC {rfc_number}: {error}"
+                ));
+            },
+        }
+    }
+}
 
-        debug!("Cached RFC {rfc_number}");
-        content
+/// Copies the lines with search matches to the clipboard, telling the
+/// outcome.
+///
+/// # Arguments
+///
+/// * `app` - The application with the search results
+fn copy_search_matches(app: &mut App)
+{
+    let Some(text) = app.search_matches_text()
+    else
+    {
+        app.show_message("No search results to copy");
+        return;
     };
 
-    // Setup necessary components for the app
-    let mut terminal = init_tui()?;
-
-    let app = App::new(rfc_number, rfc_content);
+    if !app.copy_to_clipboard(&text)
+    {
+        warn!("No clipboard available to copy the search matches");
+        app.show_message("Couldn't copy the search matches");
+        return;
+    }
 
-    let event_handler = EventHandler::new(Duration::from_millis(200));
+    let line_count = app.query_match_line_nums.len();
+    let message = if app
+        .app_state
+        .contains(AppStateFlags::HAS_CAPPED_RESULTS)
+    {
+        format!(
+            "Copied the first {line_count} matching lines, the search was \
+             capped"
+        )
+    }
+    else
+    {
+        format!("Copied {line_count} matching lines")
+    };
+    app.show_message(message);
+}
 
-    // Just propagate any error from run_app
-    run_app(&mut terminal, app, &event_handler)
+/// Runs a motion started by a prefix key.
+///
+/// # Arguments
+///
+/// * `app` - The application to move in
+/// * `prefix` - The prefix key, `[` for backward and `]` for forward motions
+/// * `key_code` - The key following the prefix
+fn run_motion(app: &mut App, prefix: char, key_code: KeyCode)
+{
+    match (prefix, key_code)
+    {
+        // Go to the start of the current or previous section
+        ('[', KeyCode::Char('[')) =>
+        {
+            app.jump_to_section_top();
+        },
+        (_, KeyCode::Char('p')) if app.rfc_page_starts.is_empty() =>
+        {
+            app.show_message("No pages found in this document");
+        },
+        ('[', KeyCode::Char('p')) =>
+        {
+            app.prev_page();
+        },
+        (']', KeyCode::Char('p')) =>
+        {
+            app.next_page();
+        },
+        ('[', KeyCode::Char('b')) if !app.prev_bookmark() =>
+        {
+            app.show_message("No previous bookmark");
+        },
+        (']', KeyCode::Char('b')) if !app.next_bookmark() =>
+        {
+            app.show_message("No next bookmark");
+        },
+        _ =>
+        {}, // Unknown motions are dropped
+    }
 }
 
 /// Run the main loop.
@@ -144,6 +1922,7 @@ fn main() -> Result<()>
 /// * `terminal` - The terminal to draw to
 /// * `app` - The app to run
 /// * `event_handler` - The event handler to handle events
+/// * `loader` - The loader to open other RFCs with
 ///
 /// # Errors
 ///
@@ -153,10 +1932,14 @@ fn run_app<T: RatatuiBackend>(
     terminal: &mut Terminal<T>,
     mut app: App,
     event_handler: &EventHandler,
+    loader: &RfcLoader,
 ) -> Result<()>
 where
     T::Error: std::error::Error + Send + Sync + 'static,
 {
+    // Preferences changed while running are persisted on exit
+    let initial_config = app.config.clone();
+
     terminal.draw(|frame| app.render(frame))?;
 
     while app
@@ -169,10 +1952,22 @@ where
         {
             // This is needed in Windows, otherwise both press and release
             // events are captured, leading to double input.
+            // Any key press dismisses a shown message
+            Event::Key(key)
+                if key.kind == KeyEventKind::Press && app.dismiss_message() =>
+            {
+                should_redraw = true;
+            },
             Event::Key(key) if key.kind == KeyEventKind::Press =>
             {
                 match (app.mode, key.code)
                 {
+                    // Finish a motion started by a prefix key
+                    (AppMode::Normal, key_code)
+                        if let Some(prefix) = app.pending_motion.take() =>
+                    {
+                        run_motion(&mut app, prefix, key_code);
+                    },
                     // Quit with 'q' in normal mode
                     (AppMode::Normal, KeyCode::Char('q')) =>
                     {
@@ -186,6 +1981,333 @@ where
                     {
                         app.toggle_help();
                     },
+                    // Scroll the help when it doesn't fit
+                    (AppMode::Help, KeyCode::Char('j') | KeyCode::Down) =>
+                    {
+                        app.scroll_help_down();
+                    },
+                    (AppMode::Help, KeyCode::Char('k') | KeyCode::Up) =>
+                    {
+                        app.scroll_help_up();
+                    },
+                    // Follow the RFC reference on the current line
+                    (AppMode::Normal, KeyCode::Char('r')) =>
+                    {
+                        match app.current_line_reference()
+                        {
+                            Some(rfc_number)
+                                if Some(rfc_number) == app.rfc_number =>
+                            {
+                                app.show_message(format!(
+                                    "Already viewing RFC {rfc_number}"
+                                ));
+                            },
+                            Some(rfc_number) =>
+                            {
+                                loader.open(
+                                    &mut app,
+                                    rfc_number,
+                                    event_handler.sender(),
+                                );
+                            },
+                            None =>
+                            {
+                                app.show_message(
+                                    "No RFC reference on the current line",
+                                );
+                            },
+                        }
+                    },
+                    // Peek at the RFC reference on the current line
+                    (AppMode::Normal, KeyCode::Char('p')) =>
+                    {
+                        match app.current_line_reference()
+                        {
+                            Some(rfc_number) =>
+                            {
+                                loader.peek(
+                                    &mut app,
+                                    rfc_number,
+                                    event_handler.sender(),
+                                );
+                            },
+                            None =>
+                            {
+                                app.show_message(
+                                    "No RFC reference on the current line",
+                                );
+                            },
+                        }
+                    },
+                    // Jump to a standard section
+                    (AppMode::Normal, KeyCode::Char('S')) =>
+                    {
+                        app.open_sections_menu();
+                    },
+                    (AppMode::Sections, KeyCode::Char(digit @ '1'..='9')) =>
+                    {
+                        let index = digit
+                            .to_digit(10)
+                            .and_then(|digit| digit.checked_sub(1))
+                            .and_then(|index| usize::try_from(index).ok());
+                        if let Some(index) = index
+                        {
+                            app.jump_to_standard_section(index);
+                        }
+                    },
+                    (AppMode::Sections, KeyCode::Esc) =>
+                    {
+                        app.close_sections_menu();
+                    },
+
+                    // Bookmark the current line
+                    (AppMode::Normal, KeyCode::Char('m')) =>
+                    {
+                        app.toggle_bookmark();
+                        loader.save_bookmarks(&app);
+                    },
+
+                    // Pick a cached RFC to open
+                    (AppMode::Normal, KeyCode::Char('o')) =>
+                    {
+                        match loader.cache_browser()
+                        {
+                            Ok(cache_browser) =>
+                            {
+                                loader.load_titles_in_background(
+                                    cache_browser.numbers(),
+                                    event_handler.sender(),
+                                );
+                                app.open_cache_browser(cache_browser);
+                            },
+                            Err(error) =>
+                            {
+                                warn!("Could not list the cache: {error:#}");
+                                app.show_message("Could not list the cache");
+                            },
+                        }
+                    },
+                    (AppMode::CacheBrowser, KeyCode::Esc) =>
+                    {
+                        app.close_cache_browser();
+                    },
+                    (AppMode::CacheBrowser, KeyCode::Enter) =>
+                    {
+                        let selected = app
+                            .cache_browser
+                            .as_ref()
+                            .and_then(CacheBrowser::selected);
+                        app.close_cache_browser();
+
+                        if let Some(rfc_number) = selected &&
+                            Some(rfc_number) != app.rfc_number
+                        {
+                            loader.open(
+                                &mut app,
+                                rfc_number,
+                                event_handler.sender(),
+                            );
+                        }
+                    },
+                    (AppMode::CacheBrowser, code) =>
+                    {
+                        let page = app.viewport_height;
+
+                        if let Some(cache_browser) = &mut app.cache_browser
+                        {
+                            match code
+                            {
+                                KeyCode::Down | KeyCode::Char('j') =>
+                                {
+                                    cache_browser.next(1);
+                                },
+                                KeyCode::Up | KeyCode::Char('k') =>
+                                {
+                                    cache_browser.previous(1);
+                                },
+                                KeyCode::PageDown => cache_browser.next(page),
+                                KeyCode::PageUp =>
+                                {
+                                    cache_browser.previous(page);
+                                },
+                                _ =>
+                                {},
+                            }
+                        }
+                    },
+
+                    // Jump to the references and pick one
+                    (AppMode::Normal, KeyCode::Char('C')) =>
+                    {
+                        app.open_reference_list();
+                    },
+                    (AppMode::References, KeyCode::Esc) =>
+                    {
+                        app.close_reference_list();
+                    },
+                    (AppMode::References, KeyCode::Enter) =>
+                    {
+                        let selected = app
+                            .reference_list
+                            .as_ref()
+                            .and_then(ReferenceList::selected)
+                            .map(|entry| (entry.rfc_number, entry.line_number));
+                        app.close_reference_list();
+
+                        match selected
+                        {
+                            Some((Some(rfc_number), _))
+                                if Some(rfc_number) != app.rfc_number =>
+                            {
+                                loader.open(
+                                    &mut app,
+                                    rfc_number,
+                                    event_handler.sender(),
+                                );
+                            },
+                            Some((_, line_num)) => app.jump_to_line(line_num),
+                            None =>
+                            {},
+                        }
+                    },
+                    (AppMode::References, code) =>
+                    {
+                        let page = app.viewport_height;
+
+                        if let Some(reference_list) = &mut app.reference_list
+                        {
+                            match code
+                            {
+                                KeyCode::Down | KeyCode::Char('j') =>
+                                {
+                                    reference_list.next(1);
+                                },
+                                KeyCode::Up | KeyCode::Char('k') =>
+                                {
+                                    reference_list.previous(1);
+                                },
+                                KeyCode::PageDown => reference_list.next(page),
+                                KeyCode::PageUp =>
+                                {
+                                    reference_list.previous(page);
+                                },
+                                _ =>
+                                {},
+                            }
+                        }
+                    },
+                    (AppMode::Peek, KeyCode::Esc) =>
+                    {
+                        app.close_peek();
+                    },
+                    // Promote the peek to opening the RFC
+                    (AppMode::Peek, KeyCode::Enter) =>
+                    {
+                        if let Some(rfc_number) = app.close_peek() &&
+                            Some(rfc_number) != app.rfc_number
+                        {
+                            loader.open(
+                                &mut app,
+                                rfc_number,
+                                event_handler.sender(),
+                            );
+                        }
+                    },
+                    // Follow the ABNF rule referenced on the current line
+                    (AppMode::Normal, KeyCode::Char('A')) =>
+                    {
+                        app.toggle_abnf_navigation();
+                        app.show_message(
+                            if app.app_state.contains(
+                                AppStateFlags::IS_ABNF_NAVIGATION_ENABLED,
+                            )
+                            {
+                                "ABNF rule navigation enabled"
+                            }
+                            else
+                            {
+                                "ABNF rule navigation disabled"
+                            },
+                        );
+                    },
+                    (AppMode::Normal, KeyCode::Char('d')) =>
+                    {
+                        let is_enabled = app.app_state.contains(
+                            AppStateFlags::IS_ABNF_NAVIGATION_ENABLED,
+                        );
+
+                        if is_enabled && !app.jump_to_abnf_definition()
+                        {
+                            app.show_message(
+                                "No ABNF rule reference on the current line",
+                            );
+                        }
+                    },
+                    (AppMode::Fetching, KeyCode::Esc) =>
+                    {
+                        app.cancel_fetch();
+                    },
+
+                    // Index browser, loaded on first use and reused after
+                    (AppMode::Normal, KeyCode::Char('I'))
+                        if !app.resume_index_browser() =>
+                    {
+                        app.open_index_browser(IndexBrowser::loading());
+                        loader.load_index_in_background(event_handler.sender());
+                    },
+                    (AppMode::Browse, KeyCode::Esc) =>
+                    {
+                        // Nothing to go back to without a document
+                        if !app.has_document()
+                        {
+                            app.app_state
+                                .remove(AppStateFlags::SHOULD_RUN);
+                        }
+                        app.close_index_browser();
+                    },
+                    (AppMode::Browse, KeyCode::Enter) =>
+                    {
+                        let selected = app
+                            .index_browser
+                            .as_ref()
+                            .and_then(IndexBrowser::selected);
+
+                        if let Some(rfc_number) = selected
+                        {
+                            app.close_index_browser();
+                            loader.open(
+                                &mut app,
+                                rfc_number,
+                                event_handler.sender(),
+                            );
+                        }
+                    },
+                    (AppMode::Browse, code) =>
+                    {
+                        let page = app.viewport_height;
+
+                        if let Some(index_browser) = &mut app.index_browser
+                        {
+                            match code
+                            {
+                                KeyCode::Down => index_browser.next(1),
+                                KeyCode::Up => index_browser.previous(1),
+                                KeyCode::PageDown => index_browser.next(page),
+                                KeyCode::PageUp => index_browser.previous(page),
+                                KeyCode::Backspace =>
+                                {
+                                    index_browser.pop_filter_char();
+                                },
+                                KeyCode::Char(ch) =>
+                                {
+                                    index_browser.push_filter_char(ch);
+                                },
+                                _ =>
+                                {},
+                            }
+                        }
+                    },
+
                     // Table of contents toggle with 't'
                     (AppMode::Normal, KeyCode::Char('t')) =>
                     {
@@ -207,15 +2329,15 @@ where
                         KeyCode::Char('f') | KeyCode::PageDown,
                     ) =>
                     {
-                        let terminal_height = terminal.size()?.height.into();
+                        let page_height = app.page_height(terminal);
 
-                        app.scroll_down(terminal_height);
+                        app.scroll_down(page_height);
                     },
                     (AppMode::Normal, KeyCode::Char('b') | KeyCode::PageUp) =>
                     {
-                        let terminal_height = terminal.size()?.height.into();
+                        let page_height = app.page_height(terminal);
 
-                        app.scroll_up(terminal_height);
+                        app.scroll_up(page_height);
                     },
                     // Whole document scroll
                     (AppMode::Normal, KeyCode::Char('g')) =>
@@ -229,6 +2351,138 @@ where
                         app.scroll_down(app.rfc_line_number);
                     },
 
+                    // Resize the ToC panel
+                    (AppMode::Normal, KeyCode::Char('<'))
+                        if app
+                            .app_state
+                            .contains(AppStateFlags::SHOULD_SHOW_TOC) =>
+                    {
+                        app.narrow_toc();
+                    },
+                    (AppMode::Normal, KeyCode::Char('>'))
+                        if app
+                            .app_state
+                            .contains(AppStateFlags::SHOULD_SHOW_TOC) =>
+                    {
+                        app.widen_toc();
+                    },
+
+                    // Search the last query again, e.g. after opening an RFC
+                    (AppMode::Normal, KeyCode::Char('&'))
+                        if !app.rerun_last_search() =>
+                    {
+                        app.show_message("No previous search to repeat");
+                    },
+                    // Start a motion like `]p`
+                    (AppMode::Normal, KeyCode::Char(prefix @ ('[' | ']'))) =>
+                    {
+                        app.pending_motion = Some(prefix);
+                    },
+                    // Go to the next or previous page
+                    (AppMode::Normal, KeyCode::Char('}')) =>
+                    {
+                        run_motion(&mut app, ']', KeyCode::Char('p'));
+                    },
+                    (AppMode::Normal, KeyCode::Char('{')) =>
+                    {
+                        run_motion(&mut app, '[', KeyCode::Char('p'));
+                    },
+                    // Fetch the latest version of the RFC in place
+                    (AppMode::Normal, KeyCode::Char('R')) =>
+                    {
+                        if let Some(rfc_number) = app.rfc_number
+                        {
+                            loader.fetch(
+                                &mut app,
+                                rfc_number,
+                                FetchPurpose::Reload,
+                                event_handler.sender(),
+                            );
+                        }
+                    },
+                    // Reveal the cached file, e.g. to open it in an editor
+                    (AppMode::Normal, KeyCode::Char('P')) =>
+                    {
+                        if let Some(rfc_number) = app.rfc_number
+                        {
+                            let path =
+                                loader.cache.format_cache_path(rfc_number);
+                            let path = path.to_string_lossy();
+
+                            if app.copy_to_clipboard(&path)
+                            {
+                                app.show_message(format!(
+                                    "{path}\n\n(copied to the clipboard)"
+                                ));
+                            }
+                            else
+                            {
+                                app.show_message(path);
+                            }
+                        }
+                    },
+                    // Copy the lines with search matches
+                    (AppMode::Normal, KeyCode::Char('M')) =>
+                    {
+                        copy_search_matches(&mut app);
+                    },
+                    // Copy the current line
+                    (AppMode::Normal, KeyCode::Char('Y')) =>
+                    {
+                        if app.copy_current_line()
+                        {
+                            app.show_message(format!(
+                                "Copied line {}",
+                                app.current_line().saturating_add(1)
+                            ));
+                        }
+                        else
+                        {
+                            app.show_message("Couldn't copy the current line");
+                        }
+                    },
+                    // Toggle the fuller keybinding hints
+                    (_, KeyCode::F(1)) =>
+                    {
+                        app.toggle_legend();
+                    },
+                    // Toggle the statusbar for more reading space
+                    (_, KeyCode::F(2)) =>
+                    {
+                        app.toggle_statusbar();
+                    },
+                    // Toggle trailing whitespace trimming
+                    (AppMode::Normal, KeyCode::Char('W')) =>
+                    {
+                        app.toggle_trailing_whitespace_trim();
+                    },
+                    // Toggle showing whitespace and control characters
+                    (AppMode::Normal, KeyCode::Char('V')) =>
+                    {
+                        app.toggle_whitespace_display();
+                    },
+                    // Toggle reflowing prose
+                    (AppMode::Normal, KeyCode::Char('F')) =>
+                    {
+                        app.toggle_reflow();
+                    },
+                    // Toggle highlighting the current line
+                    (AppMode::Normal, KeyCode::Char('L')) =>
+                    {
+                        app.toggle_current_line_highlight();
+                    },
+                    // Toggle highlighting the section titles
+                    (AppMode::Normal, KeyCode::Char('T')) =>
+                    {
+                        app.toggle_title_highlight();
+                    },
+                    // Cycle the color of the search matches, not on Ctrl + c
+                    (AppMode::Normal, KeyCode::Char('c'))
+                        if key.modifiers.is_empty() =>
+                    {
+                        app.cycle_match_color();
+                    },
+
                     // Search handling
                     (AppMode::Normal, KeyCode::Char('/')) =>
                     {
@@ -241,7 +2495,7 @@ where
                     },
                     (AppMode::Search, KeyCode::Esc) =>
                     {
-                        app.exit_search_mode();
+                        app.cancel_search();
                     },
                     (AppMode::Search, KeyCode::Backspace) =>
                     {
@@ -280,11 +2534,30 @@ where
                     {
                         app.toggle_regex_mode();
                     },
+                    // Ctrl + l toggles matching across line breaks
+                    (AppMode::Search, KeyCode::Char('l'))
+                        if key.modifiers == KeyModifiers::CONTROL =>
+                    {
+                        app.toggle_cross_line_search();
+                    },
                     (AppMode::Search, KeyCode::Char(ch)) =>
                     {
                         app.add_search_char(ch);
                     },
 
+                    // Go to a line by its number
+                    (AppMode::Normal, KeyCode::Char(':')) =>
+                    {
+                        app.enter_goto_mode();
+                    },
+                    (AppMode::Goto, KeyCode::Enter) => app.confirm_goto(),
+                    (AppMode::Goto, KeyCode::Esc) => app.cancel_goto(),
+                    (AppMode::Goto, KeyCode::Backspace) =>
+                    {
+                        app.remove_goto_char();
+                    },
+                    (AppMode::Goto, KeyCode::Char(ch)) => app.add_goto_char(ch),
+
                     // Search result navigation
                     (AppMode::Normal, KeyCode::Char('n')) =>
                     {
@@ -321,6 +2594,21 @@ where
                     {
                         app.jump_to_toc_entry();
                     },
+                    // Adjust the auto-scrolling speed, or fold the ToC
+                    (AppMode::Normal, KeyCode::Char('+')) =>
+                    {
+                        app.expand_or_speed_up();
+                    },
+                    (AppMode::Normal, KeyCode::Char('-')) =>
+                    {
+                        app.collapse_or_slow_down();
+                    },
+
+                    // Scroll hands-free
+                    (AppMode::Normal, KeyCode::Char('a')) =>
+                    {
+                        app.toggle_autoscroll(Instant::now());
+                    },
 
                     _ =>
                     {}, // Ignore other key combinations
@@ -332,6 +2620,8 @@ where
             {},
             Event::Tick =>
             {
+                app.tick_autoscroll(Instant::now());
+                app.tick_incremental_search(Instant::now());
                 should_redraw = true;
             },
             Event::Resize(_, _) =>
@@ -339,6 +2629,55 @@ where
                 terminal.clear()?;
                 should_redraw = true;
             },
+            Event::FetchFinished {
+                fetch_id,
+                rfc_number,
+                result,
+            } =>
+            {
+                // Ignore the results of canceled fetches
+                if let Some(purpose) = app.finish_fetch(fetch_id)
+                {
+                    loader.finish(&mut app, rfc_number, purpose, result);
+                }
+                should_redraw = true;
+            },
+            Event::IndexLoaded { entries, percent } =>
+            {
+                if let Some(index_browser) = &mut app.index_browser
+                {
+                    index_browser.push_entries(entries, percent);
+                }
+                should_redraw = true;
+            },
+            Event::CachedTitleLoaded { rfc_number, title } =>
+            {
+                if let Some(cache_browser) = &mut app.cache_browser
+                {
+                    cache_browser.set_title(rfc_number, title);
+                }
+                should_redraw = true;
+            },
+            Event::IndexFailed { error } =>
+            {
+                error!("Failed to load index: {error:#}");
+
+                // Nothing to show without a document, like a failed startup
+                if !app.has_document()
+                {
+                    return Err(error.context("Failed to load the RFC index"));
+                }
+
+                app.index_browser = None;
+                if app.mode == AppMode::Browse
+                {
+                    app.close_index_browser();
+                }
+                app.show_message(format!(
+                    "Failed to load the RFC index: {error}"
+                ));
+                should_redraw = true;
+            },
         }
 
         if should_redraw
@@ -347,5 +2686,14 @@ where
         }
     }
 
+    loader.save_position(&app);
+
+    if app.config != initial_config && !loader.is_safe
+    {
+        app.config
+            .save_changes(&initial_config)
+            .context("Failed to save config")?;
+    }
+
     Ok(())
 }
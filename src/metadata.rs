@@ -0,0 +1,491 @@
+//! Extraction of metadata from the RFC text.
+//!
+//! Reads the title, a short summary, the abstract and the category off the
+//! front page, without needing the RFC index.
+use std::fmt;
+
+use crate::types::RfcNum;
+
+/// Heading of the abstract section.
+const ABSTRACT_HEADING: &str = "Abstract";
+/// Minimum gap between the left and right column of the header block.
+const HEADER_COLUMN_GAP: &str = "  ";
+
+/// Category of an RFC, telling a standard from other documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RfcCategory
+{
+    /// A proposed, draft or internet standard.
+    StandardsTrack,
+    /// A best current practice (BCP).
+    BestCurrentPractice,
+    /// An informational document.
+    Informational,
+    /// An experimental protocol or extension.
+    Experimental,
+    /// A document of historical interest only.
+    Historic,
+}
+
+impl RfcCategory
+{
+    /// Parses a category as spelled in the header block.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value of the `Category` field
+    ///
+    /// # Returns
+    ///
+    /// The category, or `None` if the spelling is unknown.
+    fn from_header(value: &str) -> Option<Self>
+    {
+        let normalized = value
+            .split(|char: char| char.is_whitespace() || char == '-')
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+
+        match normalized.as_str()
+        {
+            "standards track" | "standard track" | "proposed standard" |
+            "draft standard" | "internet standard" | "standard" =>
+            {
+                Some(Self::StandardsTrack)
+            },
+            "best current practice" | "bcp" => Some(Self::BestCurrentPractice),
+            "informational" => Some(Self::Informational),
+            "experimental" => Some(Self::Experimental),
+            "historic" | "historical" => Some(Self::Historic),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RfcCategory
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let name = match self
+        {
+            Self::StandardsTrack => "Standards Track",
+            Self::BestCurrentPractice => "Best Current Practice",
+            Self::Informational => "Informational",
+            Self::Experimental => "Experimental",
+            Self::Historic => "Historic",
+        };
+
+        formatter.write_str(name)
+    }
+}
+
+/// Metadata found on the front page of an RFC.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RfcMetadata
+{
+    /// The title of the RFC.
+    pub title: Option<Box<str>>,
+    /// The first paragraph of body text following the title.
+    pub summary: Option<Box<str>>,
+    /// The paragraphs of the abstract, separated by blank lines.
+    pub abstract_text: Option<Box<str>>,
+    /// The category given in the header block.
+    pub category: Option<RfcCategory>,
+    /// The RFCs this one updates.
+    pub updates: Vec<RfcNum>,
+    /// The RFCs this one obsoletes.
+    pub obsoletes: Vec<RfcNum>,
+}
+
+impl RfcMetadata
+{
+    /// Extracts the metadata from the text of an RFC.
+    ///
+    /// The front page starts with the header block, followed by the centered
+    /// title after a blank line.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text of the RFC
+    ///
+    /// # Returns
+    ///
+    /// The metadata, with the fields that couldn't be found left empty.
+    #[must_use]
+    pub fn parse(content: &str) -> Self
+    {
+        let mut paragraphs = paragraphs(content);
+
+        // The header block comes first
+        let header = paragraphs.next().unwrap_or_default();
+
+        let title = paragraphs
+            .next()
+            .filter(|lines| lines.iter().all(|line| is_indented(line)))
+            .map(|lines| join_lines(&lines));
+
+        // Headings like "Abstract" aren't indented, body text is
+        let summary = title
+            .as_ref()
+            .and_then(|_| {
+                paragraphs
+                    .find(|lines| lines.iter().all(|line| is_indented(line)))
+            })
+            .map(|lines| join_lines(&lines));
+
+        Self {
+            title,
+            summary,
+            abstract_text: parse_abstract(content),
+            category: header_field(&header, "Category")
+                .as_deref()
+                .and_then(RfcCategory::from_header),
+            updates: header_field(&header, "Updates")
+                .map(|value| parse_rfc_numbers(&value))
+                .unwrap_or_default(),
+            obsoletes: header_field(&header, "Obsoletes")
+                .map(|value| parse_rfc_numbers(&value))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Gets the best available description of the RFC for previews.
+    ///
+    /// # Returns
+    ///
+    /// The abstract if found, otherwise the summary.
+    #[must_use]
+    pub fn preview(&self) -> Option<&str>
+    {
+        self.abstract_text
+            .as_deref()
+            .or(self.summary.as_deref())
+    }
+}
+
+/// Finds the value of a field in the left column of the header block.
+///
+/// Values ending with a comma continue on the following line if it starts
+/// with a number, like long lists of obsoleted RFCs.
+///
+/// # Arguments
+///
+/// * `header` - The lines of the header block
+/// * `name` - The name of the field, matched case-insensitively
+///
+/// # Returns
+///
+/// The trimmed value of the field, or `None` if the field isn't present.
+fn header_field(header: &[&str], name: &str) -> Option<String>
+{
+    let mut left_column = header.iter().map(|line| {
+        line.trim_start()
+            .split(HEADER_COLUMN_GAP)
+            .next()
+            .unwrap_or_default()
+            .trim()
+    });
+
+    let mut value = left_column.find_map(|column| {
+        let (field, value) = column.split_once(':')?;
+        field
+            .trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim().to_owned())
+    })?;
+
+    while value.ends_with(',')
+    {
+        match left_column.next()
+        {
+            // Right column lines like dates don't start with a number
+            Some(continuation)
+                if continuation
+                    .starts_with(|char: char| char.is_ascii_digit()) =>
+            {
+                value.push(' ');
+                value.push_str(continuation);
+            },
+            _ => break,
+        }
+    }
+
+    Some(value)
+}
+
+/// Parses the RFC numbers listed in a header field.
+///
+/// # Arguments
+///
+/// * `value` - The value of the field, like `5705, 6066` or `RFC 760`
+///
+/// # Returns
+///
+/// The RFC numbers in the order listed.
+fn parse_rfc_numbers(value: &str) -> Vec<RfcNum>
+{
+    value
+        .split(|char: char| !char.is_ascii_digit())
+        .filter_map(|number| number.parse().ok())
+        .collect()
+}
+
+/// Extracts the abstract section of an RFC.
+///
+/// The abstract starts after an unindented "Abstract" heading and runs until
+/// the next unindented line, the following heading.
+///
+/// # Arguments
+///
+/// * `content` - The text of the RFC
+///
+/// # Returns
+///
+/// The paragraphs of the abstract, each joined into a single line and
+/// separated by blank lines, or `None` if there's no abstract.
+fn parse_abstract(content: &str) -> Option<Box<str>>
+{
+    let mut lines = content
+        .lines()
+        .skip_while(|line| line.trim_end() != ABSTRACT_HEADING)
+        .skip(1);
+
+    let section: Vec<&str> = std::iter::from_fn(|| {
+        lines
+            .next()
+            .filter(|line| line.trim().is_empty() || is_indented(line))
+    })
+    .collect();
+
+    let abstract_text = paragraphs(&section.join("\n"))
+        .map(|lines| join_lines(&lines))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    (!abstract_text.is_empty()).then(|| abstract_text.into_boxed_str())
+}
+
+/// Splits the text into blank-line separated paragraphs.
+///
+/// # Arguments
+///
+/// * `content` - The text to split
+///
+/// # Returns
+///
+/// An iterator over the lines of each paragraph.
+fn paragraphs(content: &str) -> impl Iterator<Item = Vec<&str>>
+{
+    let mut lines = content.lines().peekable();
+
+    std::iter::from_fn(move || {
+        // Skip the blank lines between paragraphs
+        while lines
+            .next_if(|line| line.trim().is_empty())
+            .is_some()
+        {}
+
+        let paragraph: Vec<&str> = std::iter::from_fn(|| {
+            lines.next_if(|line| !line.trim().is_empty())
+        })
+        .collect();
+
+        (!paragraph.is_empty()).then_some(paragraph)
+    })
+}
+
+/// Checks whether a line starts with whitespace.
+///
+/// # Arguments
+///
+/// * `line` - The line to check
+///
+/// # Returns
+///
+/// `true` if the line is indented.
+fn is_indented(line: &str) -> bool
+{
+    line.starts_with(char::is_whitespace)
+}
+
+/// Joins the lines of a paragraph into a single line.
+///
+/// # Arguments
+///
+/// * `lines` - The lines to join
+///
+/// # Returns
+///
+/// The trimmed lines separated by single spaces.
+fn join_lines(lines: &[&str]) -> Box<str>
+{
+    lines
+        .iter()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .into_boxed_str()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_parse_front_page()
+    {
+        let content = "\
+Internet Engineering Task Force (IETF)                       E. Rescorla
+Request for Comments: 8446                                       Mozilla
+Category: Standards Track                                    August 2018
+
+
+        The Transport Layer Security (TLS) Protocol Version 1.3
+
+Abstract
+
+   This document specifies version 1.3 of the Transport Layer Security
+   (TLS) protocol.
+
+   This document updates RFCs 5705 and 6066.
+";
+
+        let metadata = RfcMetadata::parse(content);
+
+        assert_eq!(
+            metadata.title.as_deref(),
+            Some("The Transport Layer Security (TLS) Protocol Version 1.3")
+        );
+        assert_eq!(
+            metadata.abstract_text.as_deref(),
+            Some(
+                "This document specifies version 1.3 of the Transport Layer \
+                 Security (TLS) protocol.\n\nThis document updates RFCs 5705 \
+                 and 6066."
+            )
+        );
+        assert_eq!(
+            metadata.summary.as_deref(),
+            Some(
+                "This document specifies version 1.3 of the Transport Layer \
+                 Security (TLS) protocol."
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_multi_paragraph_abstract()
+    {
+        let content = "\
+Network Working Group                                         S. Bradner
+Request for Comments: 2119                            Harvard University
+BCP: 14                                                       March 1997
+Category: Best Current Practice
+
+        Key words for use in RFCs to Indicate Requirement Levels
+
+Status of this Memo
+
+   This document specifies an Internet Best Current Practices for the
+   Internet Community, and requests discussion and suggestions for
+   improvements.
+
+Abstract
+
+   In many standards track documents several words are used to signify
+   the requirements in the specification.
+
+   Authors who follow these guidelines should incorporate this phrase
+   near the beginning of their document:
+
+      The key words \"MUST\" and \"MUST NOT\" are to be interpreted as
+      described in RFC 2119.
+
+1. MUST   This word, or the terms \"REQUIRED\" or \"SHALL\", mean that the
+";
+
+        let metadata = RfcMetadata::parse(content);
+
+        assert_eq!(
+            metadata.abstract_text.as_deref(),
+            Some(
+                "In many standards track documents several words are used to \
+                 signify the requirements in the specification.\n\nAuthors \
+                 who follow these guidelines should incorporate this phrase \
+                 near the beginning of their document:\n\nThe key words \
+                 \"MUST\" and \"MUST NOT\" are to be interpreted as described \
+                 in RFC 2119."
+            )
+        );
+        // The status section before the abstract isn't mistaken for it
+        assert_eq!(metadata.preview(), metadata.abstract_text.as_deref());
+    }
+
+    #[test]
+    fn test_parse_without_title()
+    {
+        let metadata = RfcMetadata::parse("Just some\ntext\n\nAbstract\n");
+
+        assert_eq!(metadata, RfcMetadata::default());
+    }
+
+    #[test]
+    fn test_parse_header_fields()
+    {
+        let rfc = |number| RfcNum::new(number).expect("its non-zero");
+        let content = [
+            "Internet Engineering Task Force (IETF)          R. Fielding, Ed.",
+            "Request for Comments: 9110                                 Adobe",
+            "STD: 97                                       M. Nottingham, Ed.",
+            "Obsoletes: 2818, 7230, 7231, 7232, 7233, 7235,            Fastly",
+            "           7538, 7615, 7694                       J. Reschke, Ed.",
+            "Updates: 3864                                         greenbytes",
+            "Category: Standards Track                              June 2022",
+            "ISSN: 2070-1721",
+            "",
+            "",
+            "                             HTTP Semantics",
+        ]
+        .join("\n");
+
+        let metadata = RfcMetadata::parse(&content);
+
+        assert_eq!(metadata.category, Some(RfcCategory::StandardsTrack));
+        assert_eq!(metadata.updates, [rfc(3864)]);
+        assert_eq!(
+            metadata.obsoletes,
+            [2818, 7230, 7231, 7232, 7233, 7235, 7538, 7615, 7694].map(rfc)
+        );
+
+        // Older layouts name the RFCs and may lack a category
+        let metadata = RfcMetadata::parse(
+            "Network Working Group\nRequest for Comments: 791\nObsoletes: RFC \
+             760\n\n   INTERNET PROTOCOL\n",
+        );
+        assert_eq!(metadata.category, None);
+        assert_eq!(metadata.obsoletes, [rfc(760)]);
+        assert!(metadata.updates.is_empty());
+    }
+
+    #[test]
+    fn test_category_spellings()
+    {
+        for (value, category) in [
+            ("Standards Track", RfcCategory::StandardsTrack),
+            ("standards-track", RfcCategory::StandardsTrack),
+            ("Proposed  Standard", RfcCategory::StandardsTrack),
+            ("Best Current Practice", RfcCategory::BestCurrentPractice),
+            ("BCP", RfcCategory::BestCurrentPractice),
+            ("Informational", RfcCategory::Informational),
+            ("EXPERIMENTAL", RfcCategory::Experimental),
+            ("Historic", RfcCategory::Historic),
+        ]
+        {
+            assert_eq!(RfcCategory::from_header(value), Some(category));
+        }
+
+        assert_eq!(RfcCategory::from_header("Unknown"), None);
+    }
+}
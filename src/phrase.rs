@@ -0,0 +1,247 @@
+//! Search of phrases across the line breaks.
+//!
+//! RFCs wrap their prose at fixed columns, so a phrase may be split over two
+//! lines. The words of the text are joined by single spaces for the search,
+//! and the matches are mapped back to the lines they span.
+use regex::Regex;
+
+use crate::types::{LineNumber, MatchSpan};
+
+/// A word of the text, a run of non-whitespace characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Word
+{
+    /// The byte offset of the word in the joined text.
+    joined_start: usize,
+    /// The line the word is on.
+    line: LineNumber,
+    /// The byte offset of the word in its line.
+    column: usize,
+    /// The length of the word in bytes.
+    len: usize,
+}
+
+/// The words of a text joined by single spaces.
+struct JoinedText
+{
+    /// The joined text.
+    text: String,
+    /// The words in order of appearance.
+    words: Vec<Word>,
+}
+
+impl JoinedText
+{
+    /// Joins the words of the text by single spaces.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text to join
+    fn new(content: &str) -> Self
+    {
+        let mut text = String::with_capacity(content.len());
+        let mut words = Vec::new();
+
+        for (line, line_str) in content.lines().enumerate()
+        {
+            let mut word_start = None;
+
+            // A trailing whitespace ends the last word of the line
+            for (column, ch) in line_str
+                .char_indices()
+                .chain([(line_str.len(), ' ')])
+            {
+                match (word_start, ch.is_whitespace())
+                {
+                    (None, false) => word_start = Some(column),
+                    (Some(start), true) =>
+                    {
+                        if !text.is_empty()
+                        {
+                            text.push(' ');
+                        }
+                        words.push(Word {
+                            joined_start: text.len(),
+                            line,
+                            column: start,
+                            len: column.saturating_sub(start),
+                        });
+                        // Both ends are at character boundaries
+                        text.push_str(
+                            line_str
+                                .get(start..column)
+                                .unwrap_or_default(),
+                        );
+                        word_start = None;
+                    },
+                    _ =>
+                    {},
+                }
+            }
+        }
+
+        Self { text, words }
+    }
+
+    /// Maps a range of the joined text back to the lines it spans.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range in the joined text
+    ///
+    /// # Returns
+    ///
+    /// The spans of the range in each line it spans, in line order.
+    fn line_spans(&self, range: &MatchSpan) -> Vec<(LineNumber, MatchSpan)>
+    {
+        // The first word ending after the range starts
+        let first_index = self.words.partition_point(|word| {
+            word.joined_start.saturating_add(word.len) <= range.start
+        });
+
+        let mut spans: Vec<(LineNumber, MatchSpan)> = Vec::new();
+
+        for word in self.words[first_index..]
+            .iter()
+            .take_while(|word| word.joined_start < range.end)
+        {
+            let start = range.start.saturating_sub(word.joined_start);
+            let end = range
+                .end
+                .saturating_sub(word.joined_start)
+                .min(word.len);
+            let span = word.column.saturating_add(start)..
+                word.column.saturating_add(end);
+
+            // Words on the same line are covered along with the spaces
+            // between them
+            match spans.last_mut()
+            {
+                Some((line, line_span)) if *line == word.line =>
+                {
+                    line_span.end = span.end;
+                },
+                _ => spans.push((word.line, span)),
+            }
+        }
+
+        spans
+    }
+}
+
+/// Collects the matches of a pattern in the text with its words joined by
+/// single spaces, like a phrase wrapped over two lines.
+///
+/// # Arguments
+///
+/// * `regex` - The regex to search with
+/// * `content` - The content to search in
+/// * `max_matches` - The maximum number of matches to collect
+///
+/// # Returns
+///
+/// An array of 2-tuples, where each tuple contains a line number and a vector
+/// of match spans for that line, in line order, and whether there were more
+/// matches than collected. A match spanning several lines has a span in each
+/// of them.
+#[must_use]
+pub fn collect_phrase_matches(
+    regex: &Regex,
+    content: &str,
+    max_matches: usize,
+) -> (Vec<(LineNumber, Vec<MatchSpan>)>, bool)
+{
+    let joined = JoinedText::new(content);
+    let mut results: Vec<(LineNumber, Vec<MatchSpan>)> = Vec::new();
+
+    let mut matches = regex
+        .find_iter(&joined.text)
+        .filter(|r#match| !r#match.is_empty());
+
+    // Counted by matches, as one may have spans on several lines
+    for r#match in matches.by_ref().take(max_matches)
+    {
+        for (line, span) in joined.line_spans(&r#match.range())
+        {
+            match results.last_mut()
+            {
+                Some((last_line, spans)) if *last_line == line =>
+                {
+                    spans.push(span);
+                },
+                _ => results.push((line, vec![span])),
+            }
+        }
+    }
+    let is_capped = matches.next().is_some();
+
+    (results, is_capped)
+}
+
+/// Joins the words of a plain query by single spaces, like the searched
+/// text.
+///
+/// # Arguments
+///
+/// * `query` - The query to normalize
+///
+/// # Returns
+///
+/// The words of the query joined by single spaces.
+#[must_use]
+pub fn normalize_phrase(query: &str) -> String
+{
+    query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_phrase_across_lines()
+    {
+        let content = "   the key words \"MUST\", \"MUST\n   NOT\", \
+                       \"REQUIRED\"\n\n   Not NOT";
+        let regex = Regex::new(&regex::escape("\"MUST NOT\"")).expect("valid");
+
+        assert_eq!(
+            collect_phrase_matches(&regex, content, 10),
+            (
+                vec![
+                    (0, vec![MatchSpan { start: 25, end: 30 }]),
+                    (1, vec![MatchSpan { start: 3, end: 7 }])
+                ],
+                false
+            )
+        );
+
+        // Words on the same line are covered with the spaces between them,
+        // and paragraphs are joined as well
+        let regex = Regex::new("(?i)key words|REQUIRED\" not").expect("valid");
+        assert_eq!(
+            collect_phrase_matches(&regex, content, 10),
+            (
+                vec![
+                    (0, vec![MatchSpan { start: 7, end: 16 }]),
+                    (1, vec![MatchSpan { start: 10, end: 19 }]),
+                    (3, vec![MatchSpan { start: 3, end: 6 }])
+                ],
+                false
+            )
+        );
+
+        // The cap counts matches, not the lines they cover
+        let (results, is_capped) = collect_phrase_matches(&regex, content, 2);
+        assert_eq!(results.len(), 3);
+        assert!(!is_capped);
+        let (results, is_capped) = collect_phrase_matches(&regex, content, 1);
+        assert_eq!(results.len(), 1);
+        assert!(is_capped);
+        assert_eq!(normalize_phrase("  MUST \t NOT "), "MUST NOT");
+    }
+}
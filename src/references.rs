@@ -0,0 +1,161 @@
+//! Detection of references to other RFCs.
+//!
+//! Finds mentions like `RFC 2119` or `[RFC2119]` in the document text, and
+//! the entries of the references section.
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::types::{LineNumber, RfcNum};
+
+/// Matches references to RFCs, capturing the number.
+///
+/// Only the uppercase form is matched to avoid false positives in prose.
+static RFC_REFERENCE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bRFC[ -]?(\d{1,5})\b").expect("Invalid RFC reference regex")
+});
+
+/// Matches the start of an entry in a references section, like
+/// `   [RFC2119]  Bradner, S., ...`, capturing the label.
+static REFERENCE_ENTRY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^ {0,8}\[([^\]\s]+)\]").expect("Invalid reference entry regex")
+});
+
+/// An entry of a references section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceEntry
+{
+    /// The label of the entry without the brackets, like `RFC2119`.
+    pub label: Box<str>,
+    /// The first line of the entry, trimmed.
+    pub text: Box<str>,
+    /// The line number where the entry starts.
+    pub line_number: LineNumber,
+    /// The RFC the entry refers to, if any.
+    pub rfc_number: Option<RfcNum>,
+}
+
+/// Finds all RFCs referenced in the given text.
+///
+/// # Arguments
+///
+/// * `text` - The text to search for references
+///
+/// # Returns
+///
+/// An iterator over the referenced RFC numbers in order of appearance.
+pub fn find_rfc_references(text: &str) -> impl Iterator<Item = RfcNum> + '_
+{
+    RFC_REFERENCE_REGEX
+        .captures_iter(text)
+        .filter_map(|caps| caps[1].parse().ok())
+}
+
+/// Finds the entries of a references section.
+///
+/// The RFC of an entry is the first one mentioned in it, which is the label
+/// itself for entries like `[RFC2119]`.
+///
+/// # Arguments
+///
+/// * `content` - The text of the document
+/// * `range` - The lines of the references section
+///
+/// # Returns
+///
+/// The entries in order of appearance.
+#[must_use]
+pub fn find_reference_entries(
+    content: &str,
+    range: Range<LineNumber>,
+) -> Vec<ReferenceEntry>
+{
+    let mut entries: Vec<ReferenceEntry> = Vec::new();
+
+    for (line_number, line) in content
+        .lines()
+        .enumerate()
+        .take(range.end)
+        .skip(range.start)
+    {
+        if let Some(caps) = REFERENCE_ENTRY_REGEX.captures(line)
+        {
+            entries.push(ReferenceEntry {
+                label: caps[1].into(),
+                text: line.trim().into(),
+                line_number,
+                rfc_number: None,
+            });
+        }
+
+        // Lines before the first entry, like headings, are skipped
+        if let Some(entry) = entries.last_mut() &&
+            entry.rfc_number.is_none()
+        {
+            entry.rfc_number = find_rfc_references(line).next();
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_find_rfc_references()
+    {
+        let references: Vec<u16> = find_rfc_references(
+            "See [RFC2119], RFC 8174 and RFC-9110, not RFC0 or rfc 1.",
+        )
+        .map(RfcNum::get)
+        .collect();
+
+        assert_eq!(references, [2119, 8174, 9110]);
+    }
+
+    #[test]
+    fn test_find_reference_entries()
+    {
+        let content = "\
+9.  References
+
+9.1.  Normative References
+
+   [RFC2119]  Bradner, S., \"Key words for use in RFCs to Indicate
+              Requirement Levels\", BCP 14, RFC 2119, March 1997.
+
+   [TLS13]    Rescorla, E., \"The Transport Layer Security (TLS)
+              Protocol Version 1.3\", RFC 8446, August 2018.
+
+   [W3C.xml]  Bray, T., \"Extensible Markup Language (XML) 1.0\".
+
+Appendix A.  Example
+
+   [RFC1234]  Not part of the references.
+";
+        let entries = find_reference_entries(content, 0..12);
+
+        let labels: Vec<&str> = entries
+            .iter()
+            .map(|entry| &*entry.label)
+            .collect();
+        assert_eq!(labels, ["RFC2119", "TLS13", "W3C.xml"]);
+
+        let rfc_numbers: Vec<Option<u16>> = entries
+            .iter()
+            .map(|entry| entry.rfc_number.map(RfcNum::get))
+            .collect();
+        assert_eq!(rfc_numbers, [Some(2119), Some(8446), None]);
+
+        assert_eq!(entries[1].line_number, 7);
+        assert!(
+            entries[1]
+                .text
+                .starts_with("[TLS13]    Rescorla")
+        );
+    }
+}
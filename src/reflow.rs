@@ -0,0 +1,221 @@
+//! Reflowing of prose to the viewport width.
+//!
+//! Joins and rewraps prose paragraphs, mainly for Internet-Drafts with
+//! markdown-derived formatting, while fenced (```` ``` ````) and indented
+//! blocks are kept verbatim.
+use std::borrow::Cow;
+
+use textwrap::{Options, wrap};
+
+use crate::types::LineNumber;
+
+/// Indentation from which a line belongs to an indented block, like a figure
+/// or code, rather than prose.
+const INDENTED_BLOCK_WIDTH: usize = 4;
+
+/// Delimiter of fenced blocks.
+const FENCE: &str = "```";
+
+/// A line of the reflowed text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReflowedLine<'text>
+{
+    /// The line of the original text the line starts in.
+    pub source_line: LineNumber,
+    /// The text of the line.
+    pub text: Cow<'text, str>,
+}
+
+impl ReflowedLine<'_>
+{
+    /// Copies the text of the line to no longer borrow the original text.
+    ///
+    /// # Returns
+    ///
+    /// The line with an owned text.
+    #[must_use]
+    pub fn into_owned(self) -> ReflowedLine<'static>
+    {
+        ReflowedLine {
+            source_line: self.source_line,
+            text: Cow::Owned(self.text.into_owned()),
+        }
+    }
+}
+
+/// Reflows the prose paragraphs of the text to the given width.
+///
+/// A prose paragraph is a run of non-blank lines outside fences that share
+/// an indentation below [`INDENTED_BLOCK_WIDTH`]. Everything else, and prose
+/// lines already fitting the width on their own, is kept as is.
+///
+/// # Arguments
+///
+/// * `content` - The text to reflow
+/// * `width` - The width to wrap the prose to
+///
+/// # Returns
+///
+/// The lines of the reflowed text.
+#[must_use]
+pub fn reflow(content: &str, width: usize) -> Vec<ReflowedLine<'_>>
+{
+    let mut reflowed = Vec::new();
+    // Start and lines of the prose paragraph being collected
+    let mut paragraph: Vec<(LineNumber, &str)> = Vec::new();
+    let mut is_in_fence = false;
+
+    for (line_num, line) in content.lines().enumerate()
+    {
+        let is_fence = line.trim_start().starts_with(FENCE);
+        let is_prose = !is_in_fence &&
+            !is_fence &&
+            !line.trim().is_empty() &&
+            indentation(line) < INDENTED_BLOCK_WIDTH;
+        let does_continue = paragraph
+            .first()
+            .is_none_or(|&(_, first)| indentation(first) == indentation(line));
+
+        // A prose line with a differing indentation starts a new paragraph
+        if !is_prose || !does_continue
+        {
+            flush_paragraph(&mut paragraph, width, &mut reflowed);
+        }
+
+        if is_prose
+        {
+            paragraph.push((line_num, line));
+            continue;
+        }
+
+        if is_fence
+        {
+            is_in_fence = !is_in_fence;
+        }
+
+        reflowed.push(ReflowedLine {
+            source_line: line_num,
+            text: Cow::Borrowed(line),
+        });
+    }
+
+    flush_paragraph(&mut paragraph, width, &mut reflowed);
+
+    reflowed
+}
+
+/// Wraps the collected prose paragraph and appends it to the output.
+///
+/// # Arguments
+///
+/// * `paragraph` - The lines of the paragraph, emptied afterwards
+/// * `width` - The width to wrap the paragraph to
+/// * `reflowed` - The output to append the wrapped lines to
+fn flush_paragraph<'text>(
+    paragraph: &mut Vec<(LineNumber, &'text str)>,
+    width: usize,
+    reflowed: &mut Vec<ReflowedLine<'text>>,
+)
+{
+    let Some(&(source_line, first)) = paragraph.first()
+    else
+    {
+        return;
+    };
+
+    // Nothing to join, lines with a deliberate layout like page headers are
+    // left untouched
+    if let [(_, line)] = paragraph.as_slice() &&
+        line.len() <= width
+    {
+        reflowed.push(ReflowedLine {
+            source_line,
+            text: Cow::Borrowed(line),
+        });
+        paragraph.clear();
+        return;
+    }
+
+    let indent = first
+        .get(..indentation(first))
+        .unwrap_or_default();
+    let joined = paragraph
+        .iter()
+        .map(|(_, line)| line.trim())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let options = Options::new(width)
+        .initial_indent(indent)
+        .subsequent_indent(indent);
+
+    reflowed.extend(
+        wrap(&joined, options)
+            .into_iter()
+            .map(|line| ReflowedLine {
+                source_line,
+                text: Cow::Owned(line.into_owned()),
+            }),
+    );
+    paragraph.clear();
+}
+
+/// Width of the indentation of a line.
+///
+/// # Arguments
+///
+/// * `line` - The line to measure
+///
+/// # Returns
+///
+/// The number of leading whitespace bytes.
+fn indentation(line: &str) -> usize
+{
+    line.len()
+        .saturating_sub(line.trim_start().len())
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_reflow_keeps_fences_and_indented_blocks()
+    {
+        let content = [
+            "   This prose paragraph was",
+            "   wrapped narrowly.",
+            "",
+            "   ```",
+            "   let  kept = \"as is\";",
+            "   ```",
+            "",
+            "      +--------+",
+            "      | Figure |",
+            "      +--------+",
+        ]
+        .join("\n");
+
+        let reflowed = reflow(&content, 40);
+        let lines: Vec<(LineNumber, &str)> = reflowed
+            .iter()
+            .map(|line| (line.source_line, line.text.as_ref()))
+            .collect();
+
+        assert_eq!(
+            lines,
+            [
+                (0, "   This prose paragraph was wrapped"),
+                (0, "   narrowly."),
+                (2, ""),
+                (3, "   ```"),
+                (4, "   let  kept = \"as is\";"),
+                (5, "   ```"),
+                (6, ""),
+                (7, "      +--------+"),
+                (8, "      | Figure |"),
+                (9, "      +--------+"),
+            ]
+        );
+    }
+}
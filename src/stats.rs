@@ -0,0 +1,252 @@
+//! Local usage counters of the cache.
+//!
+//! Counts how many opened RFCs came from the cache and how many had to be
+//! fetched. The counters are only stored on disk, nothing is ever sent
+//! anywhere.
+use std::path::{Path, PathBuf};
+use std::{fmt, fs};
+
+use anyhow::{Context as _, Result};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+use crate::types::DocumentSource;
+
+/// Name of the counter file inside the data directory.
+const STATS_FILE_NAME: &str = "stats.toml";
+
+/// Counters of the opened RFCs by where they were loaded from.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(default, deny_unknown_fields)]
+pub struct UsageStats
+{
+    /// Number of RFCs opened from the cache.
+    pub cache_hits: u64,
+    /// Number of RFCs fetched from the network.
+    pub fetches: u64,
+}
+
+impl UsageStats
+{
+    /// Loads the counters from the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the counter file
+    ///
+    /// # Returns
+    ///
+    /// The loaded counters, or zeroed ones if the file doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load_from(path: &Path) -> Result<Self>
+    {
+        if !path.exists()
+        {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path).with_context(|| {
+            format!("Failed to read stats file {}", path.display())
+        })?;
+
+        toml::from_str(&content).with_context(|| {
+            format!("Failed to parse stats file {}", path.display())
+        })
+    }
+
+    /// Saves the counters to the given path.
+    ///
+    /// Creates the parent directory if it doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the counter file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the counters cannot be serialized or the file
+    /// cannot be written.
+    pub fn save_to(&self, path: &Path) -> Result<()>
+    {
+        if let Some(parent) = path.parent()
+        {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create data directory {}", parent.display())
+            })?;
+        }
+
+        let content =
+            toml::to_string(self).context("Failed to serialize stats")?;
+
+        fs::write(path, content).with_context(|| {
+            format!("Failed to write stats file {}", path.display())
+        })
+    }
+
+    /// Counts an opened RFC in the counter file.
+    ///
+    /// RFCs from local files and URLs aren't counted, as they bypass the
+    /// cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the counter file
+    /// * `source` - Where the RFC was loaded from
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the counter file cannot be read or written.
+    pub fn record(path: &Path, source: DocumentSource) -> Result<()>
+    {
+        let mut stats = Self::load_from(path)?;
+
+        match source
+        {
+            DocumentSource::Cache =>
+            {
+                stats.cache_hits = stats.cache_hits.saturating_add(1);
+            },
+            DocumentSource::Network =>
+            {
+                stats.fetches = stats.fetches.saturating_add(1);
+            },
+            DocumentSource::File | DocumentSource::Url => return Ok(()),
+        }
+
+        stats.save_to(path)
+    }
+
+    /// Resets the counters by removing the counter file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the counter file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the existing file cannot be removed.
+    pub fn reset(path: &Path) -> Result<()>
+    {
+        if !path.exists()
+        {
+            return Ok(());
+        }
+
+        fs::remove_file(path).with_context(|| {
+            format!("Failed to remove stats file {}", path.display())
+        })
+    }
+
+    /// Gets the number of opened RFCs.
+    ///
+    /// # Returns
+    ///
+    /// The sum of the cache hits and the fetches.
+    #[must_use]
+    pub const fn opens(&self) -> u64
+    {
+        self.cache_hits.saturating_add(self.fetches)
+    }
+
+    /// Gets the share of the opened RFCs that came from the cache.
+    ///
+    /// # Returns
+    ///
+    /// The hit rate in percent, or `None` if no RFC was opened yet.
+    #[must_use]
+    pub const fn hit_rate(&self) -> Option<u64>
+    {
+        self.cache_hits
+            .saturating_mul(100)
+            .checked_div(self.opens())
+    }
+
+    /// Gets the path of the counter file.
+    ///
+    /// # Returns
+    ///
+    /// The path where the counter file is expected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data directory cannot be determined.
+    pub fn path() -> Result<PathBuf>
+    {
+        let project_dirs = ProjectDirs::from("", "", env!("CARGO_PKG_NAME"))
+            .context("Failed to determine project directories")?;
+
+        Ok(project_dirs
+            .data_local_dir()
+            .join(STATS_FILE_NAME))
+    }
+}
+
+impl fmt::Display for UsageStats
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self.hit_rate()
+        {
+            Some(hit_rate) => write!(
+                formatter,
+                "cache hit rate: {hit_rate}% over {} opens",
+                self.opens()
+            ),
+            None => write!(formatter, "no RFCs opened yet"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_record_and_reset() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir
+            .path()
+            .join("nested")
+            .join(STATS_FILE_NAME);
+
+        assert_eq!(
+            UsageStats::load_from(&path)?.to_string(),
+            "no RFCs opened yet"
+        );
+
+        for source in [
+            DocumentSource::Cache,
+            DocumentSource::Cache,
+            DocumentSource::Cache,
+            DocumentSource::Network,
+            DocumentSource::File,
+        ]
+        {
+            UsageStats::record(&path, source)?;
+        }
+
+        let stats = UsageStats::load_from(&path)?;
+        assert_eq!(
+            stats,
+            UsageStats {
+                cache_hits: 3,
+                fetches: 1,
+            }
+        );
+        assert_eq!(stats.to_string(), "cache hit rate: 75% over 4 opens");
+
+        UsageStats::reset(&path)?;
+        assert_eq!(UsageStats::load_from(&path)?, UsageStats::default());
+
+        Ok(())
+    }
+}
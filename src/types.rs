@@ -1,12 +1,214 @@
 //! Type aliases and common types used throughout the app.
+use std::fmt;
 use std::num::NonZeroU16;
-use std::ops::Range;
+use std::ops::{Deref, Range};
+
+use anyhow::{Context as _, Result, bail};
 
 /// Type alias for RFC numbers.
 pub type RfcNum = NonZeroU16;
 
+/// Parses an RFC number given in one of its common forms.
+///
+/// Accepts a bare number (`8446`) or one prefixed with `RFC` in any case,
+/// optionally separated by a space or a dash (`RFC8446`, `rfc 8446`,
+/// `RFC-8446`).
+///
+/// # Arguments
+///
+/// * `input` - The text to parse
+///
+/// # Returns
+///
+/// The parsed RFC number.
+///
+/// # Errors
+///
+/// Returns an error if the input doesn't contain a valid, non-zero RFC
+/// number.
+pub fn parse_rfc_number(input: &str) -> Result<RfcNum>
+{
+    /// Prefix commonly written before the number.
+    const RFC_PREFIX: &str = "rfc";
+
+    let trimmed = input.trim();
+
+    let digits = match trimmed.get(..RFC_PREFIX.len())
+    {
+        Some(prefix) if prefix.eq_ignore_ascii_case(RFC_PREFIX) => trimmed
+            .get(RFC_PREFIX.len()..)
+            .unwrap_or_default()
+            .trim_start_matches([' ', '-']),
+        _ => trimmed,
+    };
+
+    if digits.is_empty() ||
+        !digits
+            .bytes()
+            .all(|byte| byte.is_ascii_digit())
+    {
+        bail!("'{input}' is not an RFC number, expected e.g. 8446 or RFC8446");
+    }
+
+    digits.parse().with_context(|| {
+        format!("'{input}' is not a valid RFC number (1-{})", u16::MAX)
+    })
+}
+
 /// Type alias for line numbers.
 pub type LineNumber = usize;
 
 /// Type alias for matches spanning a line.
 pub type MatchSpan = Range<usize>;
+
+/// Where a document was loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentSource
+{
+    /// Read from the local cache.
+    Cache,
+    /// Fetched from the network.
+    Network,
+    /// Read from a local file.
+    File,
+    /// Fetched from a URL given by the user.
+    Url,
+}
+
+impl fmt::Display for DocumentSource
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Self::Cache => formatter.write_str("cache"),
+            Self::Network => formatter.write_str("network"),
+            Self::File => formatter.write_str("file"),
+            Self::Url => formatter.write_str("url"),
+        }
+    }
+}
+
+/// Text of a loaded document.
+///
+/// Either owned on the heap or, with the `mmap` feature, backed by a
+/// memory-mapped cache file.
+#[derive(Debug, Default)]
+pub struct DocumentText(DocumentStorage);
+
+/// Storage behind a [`DocumentText`].
+#[derive(Debug)]
+enum DocumentStorage
+{
+    /// Text owned on the heap.
+    Owned(Box<str>),
+    /// Memory-mapped bytes, known to be valid UTF-8.
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Default for DocumentStorage
+{
+    fn default() -> Self
+    {
+        Self::Owned(Box::default())
+    }
+}
+
+impl DocumentText
+{
+    /// Wraps a memory map, validating that it holds UTF-8 text.
+    ///
+    /// Validation only scans the mapping, no pages are copied to the heap.
+    ///
+    /// # Arguments
+    ///
+    /// * `mmap` - The memory map of the document
+    ///
+    /// # Returns
+    ///
+    /// The document text backed by the mapping.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mapped bytes are not valid UTF-8.
+    #[cfg(feature = "mmap")]
+    pub fn from_mmap(mmap: memmap2::Mmap) -> Result<Self>
+    {
+        std::str::from_utf8(&mmap).context("Document is not valid UTF-8")?;
+
+        Ok(Self(DocumentStorage::Mapped(mmap)))
+    }
+
+    /// Checks whether the text is backed by a memory map.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the text is memory-mapped.
+    #[must_use]
+    pub const fn is_mapped(&self) -> bool
+    {
+        match self.0
+        {
+            DocumentStorage::Owned(_) => false,
+            #[cfg(feature = "mmap")]
+            DocumentStorage::Mapped(_) => true,
+        }
+    }
+}
+
+impl Deref for DocumentText
+{
+    type Target = str;
+
+    fn deref(&self) -> &str
+    {
+        match &self.0
+        {
+            DocumentStorage::Owned(text) => text,
+            #[cfg(feature = "mmap")]
+            DocumentStorage::Mapped(mmap) =>
+            {
+                // SAFETY: `from_mmap` is the only way to create this variant
+                // and it validated the bytes as UTF-8.
+                unsafe { std::str::from_utf8_unchecked(mmap) }
+            },
+        }
+    }
+}
+
+impl From<Box<str>> for DocumentText
+{
+    fn from(text: Box<str>) -> Self
+    {
+        Self(DocumentStorage::Owned(text))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_parse_rfc_number_forms() -> Result<()>
+    {
+        let expected = RfcNum::new(8446).expect("its non-zero");
+
+        for input in ["8446", "RFC8446", "rfc8446", "rfc 8446", "RFC-8446"]
+        {
+            assert_eq!(parse_rfc_number(input)?, expected, "input: {input}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_rfc_number_rejects_garbage()
+    {
+        for input in ["", "RFC", "draft-8446", "84x6", "0", "RFC 70000", "-1"]
+        {
+            assert!(parse_rfc_number(input).is_err(), "input: {input}");
+        }
+    }
+}
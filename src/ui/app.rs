@@ -6,58 +6,69 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::stdout;
-use std::num::NonZeroU16;
-use std::thread;
+use std::time::{Duration, Instant};
+use std::{iter, thread};
 
 use bitflags::bitflags;
-use cached::proc_macro::cached;
 use crossterm::cursor::{Hide, Show};
 use crossterm::execute;
-use crossterm::terminal::{SetTitle, size};
-use log::warn;
-use ratatui::Frame;
+use crossterm::terminal::SetTitle;
+use log::{error, warn};
+use ratatui::backend::Backend;
 use ratatui::layout::{Alignment, Constraint, Direction, Flex, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span, Text};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::widgets::{Block, Clear, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
 use regex::Regex;
 
+use super::cache_browser::CacheBrowser;
 use super::guard::TerminalGuard;
+use super::index_browser::IndexBrowser;
+use super::reference_list::ReferenceList;
+use super::theme::Theme;
 use super::toc_panel::TocPanel;
-use crate::types::{LineNumber, MatchSpan, RfcNum};
-
-/// Style for highlighting matches in the search results.
-const MATCH_HIGHLIGHT_STYLE: Style = Style::new()
-    .fg(Color::Yellow)
-    .add_modifier(Modifier::BOLD);
-
-/// Style for highlighting titles in the document.
-const TITLE_HIGHLIGHT_STYLE: Style = Style::new()
-    .fg(Color::Cyan)
-    .add_modifier(Modifier::BOLD);
-
-/// Style for the statusbar.
-const STATUSBAR_STYLE: Style = Style::new()
-    .bg(Color::White)
-    .fg(Color::Black);
+use crate::abnf::AbnfIndex;
+use crate::config::{
+    Config, ConfigColor, JumpAlignment, MatchPercent, StatusField, TocPosition,
+};
+use crate::index::join_rfc_numbers;
+use crate::metadata::RfcMetadata;
+use crate::phrase::{collect_phrase_matches, normalize_phrase};
+use crate::references::{find_reference_entries, find_rfc_references};
+use crate::reflow::{ReflowedLine, reflow};
+use crate::types::{
+    DocumentSource, DocumentText, LineNumber, MatchSpan, RfcNum,
+};
 
 // UI constants
 /// Minimum terminal width in columns for proper UI rendering.
 const MIN_TERMINAL_WIDTH: u16 = 94;
 /// Minimum terminal height in rows for proper UI rendering.
 const MIN_TERMINAL_HEIGHT: u16 = 15;
-
-// ToC/content split percentages.
-/// Constraints for the `ToC`/content split.
-const TOC_SPLIT_CONSTRAINTS: [Constraint; 2] = {
-    /// 1/4 for `ToC`, 3/4 for content.
-    const TOC_PERCENTAGE: u16 = 25;
-
-    [
-        Constraint::Percentage(TOC_PERCENTAGE),
-        Constraint::Percentage(100 - TOC_PERCENTAGE),
-    ]
-};
+/// Minimum width of the content area in columns to show the `ToC` beside it,
+/// the width of RFC lines. Below it the `ToC` is shown over the content.
+const MIN_CONTENT_WIDTH_FOR_TOC_SPLIT: u16 = 72;
+
+// ToC width bounds.
+/// Minimum width of the `ToC` panel as percentage of the terminal width.
+const MIN_TOC_WIDTH: u16 = 10;
+/// Maximum width of the `ToC` panel as percentage of the terminal width.
+const MAX_TOC_WIDTH: u16 = 60;
+/// Percentage points the `ToC` width changes by per key press.
+const TOC_WIDTH_STEP: u16 = 5;
+
+// Auto-scroll interval bounds.
+/// Minimum interval between auto-scrolled lines in milliseconds.
+const MIN_AUTOSCROLL_INTERVAL_MS: u64 = 100;
+/// Maximum interval between auto-scrolled lines in milliseconds.
+const MAX_AUTOSCROLL_INTERVAL_MS: u64 = 10_000;
+/// Milliseconds the auto-scroll interval changes by per key press.
+const AUTOSCROLL_INTERVAL_STEP_MS: u64 = 100;
+
+/// Time the query has to stay unchanged before highlighting its matches
+/// while typing, so fast typing doesn't search for every character.
+const INCREMENTAL_SEARCH_DELAY: Duration = Duration::from_millis(150);
 
 // Search parallelization thresholds.
 /// Minimum number of lines before search work can be parallelized.
@@ -65,6 +76,29 @@ const MIN_LINES_FOR_PARALLEL_SEARCH: usize = 1500;
 /// Minimum number of lines each worker should handle.
 const PARALLEL_SEARCH_MIN_LINES_PER_WORKER: usize = 250;
 
+/// Marker of the rows past the end of the document.
+const END_MARKER: &str = "~";
+
+/// Sections most RFCs have, by their name and the lowercase text their
+/// `ToC` titles contain.
+const STANDARD_SECTIONS: [(&str, &str); 4] = [
+    ("Security Considerations", "security considerations"),
+    ("IANA Considerations", "iana considerations"),
+    ("References", "references"),
+    // Both spellings are common
+    ("Acknowledgements", "acknowledg"),
+];
+
+/// Colors the search matches are cycled through, including the ones of the
+/// built-in themes.
+const MATCH_COLORS: [Color; 5] = [
+    Color::Yellow,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Green,
+    Color::Red,
+];
+
 /// Application mode for the current UI state.
 ///
 /// Controls what is displayed and how the user input is interpreted.
@@ -77,12 +111,62 @@ pub enum AppMode
     Help,
     /// Search mode, accepting search input.
     Search,
+    /// Waiting for a background fetch of an RFC.
+    Fetching,
+    /// Browsing the RFC index.
+    Browse,
+    /// Peeking at a referenced RFC.
+    Peek,
+    /// Picking a standard section to jump to.
+    Sections,
+    /// Picking an entry of the references section.
+    References,
+    /// Picking a cached RFC to open.
+    CacheBrowser,
+    /// Typing a line number to jump to.
+    Goto,
+}
+
+/// What a fetched RFC is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchPurpose
+{
+    /// Open the RFC in place of the current document.
+    Open,
+    /// Peek at the RFC over the current document.
+    Peek,
+    /// Replace the current document with its latest version.
+    Reload,
+}
+
+/// A background fetch awaiting its result.
+#[derive(Debug, Clone, Copy)]
+pub struct PendingFetch
+{
+    /// Identifies the fetch among the fetches of the same RFC.
+    pub id: u64,
+    /// The RFC being fetched.
+    pub rfc_number: RfcNum,
+    /// What the RFC is fetched for.
+    pub purpose: FetchPurpose,
+    /// When the fetch was started.
+    pub started_at: Instant,
+}
+
+/// A peek at a referenced RFC without leaving the current document.
+#[derive(Debug, Clone)]
+pub struct ReferencePeek
+{
+    /// The RFC peeked at.
+    pub rfc_number: RfcNum,
+    /// The metadata shown of the RFC.
+    pub metadata: RfcMetadata,
 }
 
 bitflags! {
     /// Flags indicating the current state of the application.
     #[derive(Debug)]
-    pub struct AppStateFlags: u8
+    pub struct AppStateFlags: u16
     {
         /// Application should continue running
         const SHOULD_RUN = 1;
@@ -94,6 +178,18 @@ bitflags! {
         const IS_CASE_SENSITIVE = 1 << 3;
         /// Are we searching with regex?
         const IS_USING_REGEX = 1 << 4;
+        /// Whether trailing whitespace is trimmed from lines on render
+        const SHOULD_TRIM_TRAILING_WHITESPACE = 1 << 5;
+        /// Whether ABNF rule references can be followed
+        const IS_ABNF_NAVIGATION_ENABLED = 1 << 6;
+        /// Whether search stopped collecting at the match limit
+        const HAS_CAPPED_RESULTS = 1 << 7;
+        /// Whether whitespace and control characters are made visible
+        const SHOULD_SHOW_WHITESPACE = 1 << 8;
+        /// Whether prose is reflowed to the viewport width
+        const SHOULD_REFLOW = 1 << 9;
+        /// Are we matching phrases across line breaks?
+        const IS_CROSS_LINE_SEARCH = 1 << 10;
     }
 }
 
@@ -113,36 +209,110 @@ pub struct App
 {
     // Core document
     /// Content of the currently loaded RFC.
-    pub rfc_content: Box<str>,
-    /// Number of the currently loaded RFC.
-    pub rfc_number: RfcNum,
+    pub rfc_content: DocumentText,
+    /// Number of the currently loaded RFC, `None` before the first one is
+    /// opened from the index browser or for a draft without a number.
+    pub rfc_number: Option<RfcNum>,
+    /// Whether a document is loaded, there's none while the index browser
+    /// is shown at startup.
+    has_document: bool,
     /// Table of contents panel for the current document.
     pub rfc_toc_panel: TocPanel,
     /// Total line number of the content.
     pub rfc_line_number: LineNumber,
+    /// Total word count of the content.
+    pub rfc_word_count: usize,
+    /// Where the current document was loaded from.
+    pub rfc_source: DocumentSource,
+    /// ABNF rules defined in the content.
+    pub rfc_abnf_index: AbnfIndex,
+    /// Metadata read off the front page of the content.
+    pub rfc_metadata: RfcMetadata,
+    /// First lines of the pages after the first one, found by their footers
+    /// and form feeds.
+    pub rfc_page_starts: Vec<LineNumber>,
+    /// The width the content was last reflowed to and its reflowed lines,
+    /// reused until either changes.
+    reflow_cache: Option<(usize, Vec<ReflowedLine<'static>>)>,
+    /// Prefix key waiting for the motion it starts, like `]` of `]p`.
+    pub pending_motion: Option<char>,
+    /// Bookmarked lines in ascending order, without duplicates.
+    pub bookmarks: Vec<LineNumber>,
+    /// When the last line was auto-scrolled, `None` unless auto-scrolling.
+    pub autoscroll_last: Option<Instant>,
 
     // Navigation
     /// Current scroll position in the document.
     pub current_scroll_pos: LineNumber,
+    /// Height of the content area in rows as of the last render.
+    pub viewport_height: LineNumber,
+    /// Height of the terminal in rows as last known.
+    terminal_height: LineNumber,
+    /// Whether the `ToC` was shown over the content as of the last render,
+    /// as the content would be too narrow beside it.
+    pub is_toc_overlaid: bool,
+    /// Target line and resulting scroll position of the last jump.
+    last_jump: Option<(LineNumber, LineNumber)>,
 
     // UI state
     /// Current application mode.
     pub mode: AppMode,
+    /// Scroll position in the help overlay, clamped to its text on render.
+    help_scroll_pos: u16,
     /// Flags for managing the application state.
     pub app_state: AppStateFlags,
-    /// Handle graceful terminal shutdown.
+    /// User configuration.
+    pub config: Config,
+    /// Styles used for rendering.
+    pub theme: Theme,
+    /// Background fetch being waited on, if any.
+    pub pending_fetch: Option<PendingFetch>,
+    /// Number of background fetches started, to identify them by.
+    fetch_count: u64,
+    /// Message shown to the user until the next key press.
+    pub message: Option<String>,
+    /// Browser of the RFC index, once it has been loaded.
+    pub index_browser: Option<IndexBrowser>,
+    /// The referenced RFC being peeked at.
+    pub peek: Option<ReferencePeek>,
+    /// List of the entries of the references section while picking one.
+    pub reference_list: Option<ReferenceList>,
+    /// List of the cached RFCs while picking one.
+    pub cache_browser: Option<CacheBrowser>,
+    /// Handle graceful terminal shutdown, `None` when not attached to a
+    /// terminal.
     #[allow(
         dead_code,
         reason = "Its purpose is its `Drop` implementation, not direct field \
                   access."
     )]
-    guard: TerminalGuard,
+    guard: Option<TerminalGuard>,
 
     // Search
     /// Text of the query to search.
     pub query_text: String,
     /// Cursor position in the search text (byte index).
     pub query_cursor_pos: usize,
+    /// Digits of the line number typed in goto mode.
+    pub goto_text: String,
+    /// The last query searched for, kept when the highlights are reset.
+    last_query: String,
+    /// Scroll position and current line before the search being typed, to
+    /// return to if it's canceled. Only kept with the search preview.
+    search_origin: Option<(LineNumber, LineNumber)>,
+    /// When the query being typed last changed, `None` once its matches are
+    /// highlighted. Only kept with the incremental search.
+    query_changed_at: Option<Instant>,
+    /// The pattern of the query the matches were last highlighted for while
+    /// typing, covering the search flags as well.
+    incremental_pattern: Option<String>,
+    /// The query and the bits of the search flags the search regex was last
+    /// compiled for, along with the regex, `None` if the query was an invalid
+    /// regex.
+    search_regex: Option<(String, u16, Option<Regex>)>,
+    /// Whether matches were highlighted while typing the current search, to
+    /// be dropped if it's canceled.
+    has_incremental_highlights: bool,
     /// Line numbers where query matches were found.
     pub query_match_line_nums: Vec<LineNumber>,
     /// Index of the currently selected query match.
@@ -157,1317 +327,6100 @@ impl App
     ///
     /// # Arguments
     ///
-    /// * `rfc_number` - The RFC number of the document
+    /// * `rfc_number` - The RFC number of the document, `None` for a draft
+    ///   without one
     /// * `content` - The content of the RFC document
+    /// * `rfc_source` - Where the document was loaded from
+    /// * `config` - The user configuration
     ///
     /// # Returns
     ///
     /// A new `App` instance initialized for the specified RFC.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the terminal cannot be set up.
     #[must_use]
-    pub fn new(rfc_number: RfcNum, rfc_content: Box<str>) -> Self
+    pub fn new(
+        rfc_number: Option<RfcNum>,
+        rfc_content: DocumentText,
+        rfc_source: DocumentSource,
+        config: Config,
+    ) -> Self
     {
-        let rfc_toc_panel = TocPanel::new(&rfc_content);
-        let rfc_line_number = rfc_content.lines().count();
+        let mut app = Self {
+            guard: Some(
+                TerminalGuard::new().expect("Failed to create terminal guard"),
+            ),
+            ..Self::without_terminal(
+                rfc_number,
+                rfc_content,
+                rfc_source,
+                config,
+            )
+        };
 
-        let title = format!("RFC {rfc_number} - Press ? for help");
-        if let Err(error) = execute!(stdout(), SetTitle(title))
-        {
-            warn!("Couldn't set the window title: {error}");
-        }
+        app.set_window_title();
 
-        Self {
-            rfc_content,
-            rfc_number,
-            rfc_toc_panel,
-            rfc_line_number,
+        app
+    }
+
+    /// Creates a new App instance showing the index browser without a
+    /// document.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_browser` - The browser of the RFC index
+    /// * `config` - The user configuration
+    ///
+    /// # Returns
+    ///
+    /// A new `App` instance in browse mode.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the terminal cannot be set up.
+    #[must_use]
+    pub fn with_index_browser(
+        index_browser: IndexBrowser,
+        config: Config,
+    ) -> Self
+    {
+        let mut app = Self {
+            guard: Some(
+                TerminalGuard::new().expect("Failed to create terminal guard"),
+            ),
+            theme: Theme::from_config(&config),
+            config,
             ..Default::default()
-        }
+        };
+        app.open_index_browser(index_browser);
+
+        app.set_window_title();
+
+        app
     }
 
-    /// Checks if the terminal is too small.
+    /// Creates an App instance that doesn't touch the terminal.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number of the document, `None` for a draft
+    ///   without one
+    /// * `content` - The content of the RFC document
+    /// * `rfc_source` - Where the document was loaded from
+    /// * `config` - The user configuration
     ///
     /// # Returns
     ///
-    /// A boolean indicating if the terminal is too small.
-    fn is_terminal_too_small() -> bool
+    /// A new `App` instance without a terminal guard.
+    fn without_terminal(
+        rfc_number: Option<RfcNum>,
+        rfc_content: DocumentText,
+        rfc_source: DocumentSource,
+        config: Config,
+    ) -> Self
     {
-        let (current_width, current_height) =
-            size().expect("Couldn't get terminal size");
+        let theme = Theme::from_config(&config);
+        let config = Config {
+            toc_width: config
+                .toc_width
+                .clamp(MIN_TOC_WIDTH, MAX_TOC_WIDTH),
+            autoscroll_interval_ms: config
+                .autoscroll_interval_ms
+                .clamp(MIN_AUTOSCROLL_INTERVAL_MS, MAX_AUTOSCROLL_INTERVAL_MS),
+            ..config
+        };
+
+        let mut app = Self {
+            config,
+            theme,
+            ..Default::default()
+        };
+        app.set_document(rfc_number, rfc_content, rfc_source);
+        app.open_toc_if_long();
 
-        current_width < MIN_TERMINAL_WIDTH ||
-            current_height < MIN_TERMINAL_HEIGHT
+        app
     }
 
-    /// Builds the RFC text with highlighting for search matches and titles.
-    fn build_text(&self) -> Text<'_>
+    /// Replaces the current document with another one.
+    ///
+    /// Resets the scroll position and the search state of the previous
+    /// document.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number of the document
+    /// * `rfc_content` - The content of the RFC document
+    /// * `rfc_source` - Where the document was loaded from
+    pub fn load_document(
+        &mut self,
+        rfc_number: RfcNum,
+        rfc_content: DocumentText,
+        rfc_source: DocumentSource,
+    )
     {
-        // Keep confirmed highlights in Normal mode, but hide them while
-        // actively editing in Search mode to avoid stale visuals.
-        let should_show_search_highlights =
-            self.mode != AppMode::Search && self.has_search_results();
+        self.set_document(Some(rfc_number), rfc_content, rfc_source);
+        self.reset_for_new_document();
+        self.open_toc_if_long();
 
-        let lines: Vec<Line> = self
-            .rfc_content
-            .lines()
-            .enumerate()
-            .map(|(line_num, line_str)| {
-                let is_title = self.rfc_toc_panel
-                                         .entries()
-                                         .binary_search_by(|entry| entry.line_number.cmp(&line_num))
-                                         .is_ok();
+        self.set_window_title();
+    }
 
-                if should_show_search_highlights
-                {
-                    // Highlight search match
-                    if let Some(matches) = self.query_matches.get(&line_num)
-                    {
-                        return Self::build_line_with_search_and_title_highlights(
-                            line_str, matches, is_title,
-                        );
-                    }
-                }
+    /// Scrolls to the line the document was last read at.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The stored line, clamped to the document in case it got
+    ///   shorter
+    pub fn restore_scroll_pos(&mut self, line: usize)
+    {
+        self.current_scroll_pos =
+            line.min(self.rfc_line_number.saturating_sub(1));
+    }
 
-                if is_title
-                {
-                    // Only title highlighting
-                    Line::from(Span::styled(line_str, TITLE_HIGHLIGHT_STYLE))
-                }
-                else
-                {
-                    // No highlighting
-                    Line::from(line_str)
-                }
+    /// Opens the `ToC` panel if the document has more entries than set in
+    /// the config, leaving it as is otherwise.
+    fn open_toc_if_long(&mut self)
+    {
+        if self
+            .config
+            .auto_toc_min_entries
+            .is_some_and(|min_entries| {
+                self.rfc_toc_panel.entries().len() > min_entries
             })
-            .collect();
+        {
+            self.app_state
+                .insert(AppStateFlags::SHOULD_SHOW_TOC);
+        }
+    }
 
-        Text::from(lines)
+    /// Sets the document along with all the state derived from its content.
+    ///
+    /// Every document goes through here, so none of the derived state is
+    /// left over from the previous document.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC number of the document, `None` for a draft
+    ///   without one
+    /// * `rfc_content` - The content of the RFC document
+    /// * `rfc_source` - Where the document was loaded from
+    fn set_document(
+        &mut self,
+        rfc_number: Option<RfcNum>,
+        rfc_content: DocumentText,
+        rfc_source: DocumentSource,
+    )
+    {
+        let rfc_content = self.config.presented_text(rfc_content);
+
+        self.rfc_toc_panel = TocPanel::new(&rfc_content);
+        self.rfc_line_number = rfc_content.lines().count();
+        self.rfc_word_count = rfc_content.split_whitespace().count();
+        self.rfc_abnf_index = AbnfIndex::new(&rfc_content);
+        self.rfc_metadata = RfcMetadata::parse(&rfc_content);
+        self.rfc_page_starts = find_page_starts(&rfc_content);
+        self.reflow_cache = None;
+        self.rfc_content = rfc_content;
+        self.rfc_number = rfc_number;
+        self.rfc_source = rfc_source;
+        self.has_document = true;
     }
 
-    /// Builds a line with both search and title highlighting.
+    /// Replaces the current document with a newer version of it.
+    ///
+    /// The scroll position is kept, so the reader stays around the same
+    /// place.
     ///
     /// # Arguments
     ///
-    /// * `line_str` - The line content
-    /// * `matches` - Search match spans in the line
-    /// * `is_title` - Whether this line is a title
+    /// * `rfc_content` - The latest content of the current document
     ///
     /// # Returns
     ///
-    /// A `Line` with appropriate highlighting applied.
-    fn build_line_with_search_and_title_highlights<'line_str>(
-        line_str: &'line_str str,
-        matches: &[MatchSpan],
-        is_title: bool,
-    ) -> Line<'line_str>
+    /// `true` if the content changed, `false` if it's the same and the
+    /// document was left as is.
+    pub fn reload_document(&mut self, rfc_content: DocumentText) -> bool
     {
-        let mut spans = Vec::new();
-        let mut last_end = 0;
-
-        for match_span in matches
+        let Some(rfc_number) = self.rfc_number
+        else
         {
-            // Clamp indexes to the line length to avoid out of bounds access
-            let start = match_span.start.min(line_str.len());
-            let end = match_span.end.min(line_str.len());
-
-            if start > last_end &&
-                let Some(text) = line_str.get(last_end..start)
-            {
-                if is_title
-                {
-                    spans.push(Span::styled(text, TITLE_HIGHLIGHT_STYLE));
-                }
-                else
-                {
-                    spans.push(Span::raw(text));
-                }
-            }
-
-            if let Some(mtc) = line_str.get(start..end)
-            {
-                spans.push(Span::styled(mtc, MATCH_HIGHLIGHT_STYLE));
-            }
-
-            last_end = end;
-        }
+            return false;
+        };
 
-        // Add remaining text after the last match
-        if last_end < line_str.len() &&
-            let Some(text) = line_str.get(last_end..)
+        let rfc_content = self.config.presented_text(rfc_content);
+        if *rfc_content == *self.rfc_content
         {
-            if is_title
-            {
-                spans.push(Span::styled(text, TITLE_HIGHLIGHT_STYLE));
-            }
-            else
-            {
-                spans.push(Span::raw(text));
-            }
+            self.rfc_source = DocumentSource::Network;
+            return false;
         }
 
-        Line::from(spans)
+        let scroll_pos = self.current_scroll_pos;
+        self.load_document(rfc_number, rfc_content, DocumentSource::Network);
+        self.current_scroll_pos =
+            scroll_pos.min(self.rfc_line_number.saturating_sub(1));
+
+        true
     }
 
-    /// Renders the application UI to the provided frame.
+    /// Resets the state tied to the previous document.
+    ///
+    /// Scroll position, jump history, pending motions and search results
+    /// refer to lines of the previous document and would be stale in the new
+    /// one.
+    fn reset_for_new_document(&mut self)
+    {
+        self.current_scroll_pos = 0;
+        self.last_jump = None;
+        self.pending_motion = None;
+        self.bookmarks.clear();
+        self.query_cursor_pos = 0;
+        self.search_origin = None;
+        self.reset_search_highlights();
+    }
+
+    /// Opens the index browser.
     ///
     /// # Arguments
     ///
-    /// * `frame` - The frame to render the UI to
+    /// * `index_browser` - The browser to show, replacing a previous one
+    pub fn open_index_browser(&mut self, index_browser: IndexBrowser)
+    {
+        self.index_browser = Some(index_browser);
+        self.mode = AppMode::Browse;
+    }
+
+    /// Shows the previously loaded index browser again.
     ///
-    /// # Panics
+    /// # Returns
     ///
-    /// Panics if the frame cannot be rendered.
-    pub fn render(&mut self, frame: &mut Frame)
+    /// `true` if a browser was loaded and is shown, `false` otherwise.
+    pub const fn resume_index_browser(&mut self) -> bool
     {
-        /// Height of the status bar in rows.
-        const STATUSBAR_HEIGHT_CONSTRAINT: Constraint = Constraint::Length(1);
+        let is_loaded = self.index_browser.is_some();
 
-        if Self::is_terminal_too_small()
+        if is_loaded
         {
-            Self::render_too_small_message(frame);
-            return;
+            self.mode = AppMode::Browse;
         }
 
-        // Clear the entire frame on each render to prevent artifacts
-        frame.render_widget(Clear, frame.area());
+        is_loaded
+    }
 
-        // Create main layout with statusbar at bottom
-        let [main_area, statusbar_area] = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Min(0), // Main content takes remaining space
-                STATUSBAR_HEIGHT_CONSTRAINT,
-            ])
-            .areas(frame.area());
+    /// Closes the index browser, keeping it for reopening.
+    pub const fn close_index_browser(&mut self)
+    {
+        self.mode = AppMode::Normal;
+    }
 
-        let (content_area, toc_area) = if self
-            .app_state
-            .contains(AppStateFlags::SHOULD_SHOW_TOC)
-        {
-            // Create layout with ToC panel on the left
-            let [toc_area, content_area] = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints(TOC_SPLIT_CONSTRAINTS)
-                .areas(main_area);
+    /// Jumps to the references section and lists its entries.
+    ///
+    /// The section spans its `ToC` entry and the following ones about
+    /// references, like normative and informative references. Shows a
+    /// message instead if there's no such section or it has no entries.
+    pub fn open_reference_list(&mut self)
+    {
+        let entries = self.rfc_toc_panel.entries();
+        let is_references =
+            |title: &str| title.to_lowercase().contains("references");
 
-            (content_area, Some(toc_area))
-        }
+        let Some(start_index) = entries
+            .iter()
+            .position(|entry| is_references(&entry.title))
         else
         {
-            (main_area, None)
+            self.show_message("No references section found in the contents");
+            return;
         };
 
-        if let Some(toc_area) = toc_area
+        let start = entries[start_index].line_number;
+        let end = entries
+            .iter()
+            .skip(start_index)
+            .find(|entry| !is_references(&entry.title))
+            .map_or(self.rfc_line_number, |entry| entry.line_number);
+
+        let reference_entries =
+            find_reference_entries(&self.rfc_content, start..end.max(start));
+        self.jump_to_line(start);
+
+        if reference_entries.is_empty()
         {
-            // Render ToC in the left area
-            self.rfc_toc_panel.render(frame, toc_area);
+            self.show_message("No entries found in the references section");
+            return;
         }
 
-        // Render the text with highlights if in search mode or if there is a
-        // search text
-        let text = self.build_text();
+        self.reference_list = Some(ReferenceList::new(reference_entries));
+        self.mode = AppMode::References;
+    }
 
-        // Clamp the scroll position instead of panicking
-        let y = u16::try_from(self.current_scroll_pos).unwrap_or(u16::MAX);
-        let paragraph = Paragraph::new(text)
-            .wrap(Wrap { trim: false })
-            .scroll((y, 0));
+    /// Closes the list of the references.
+    pub fn close_reference_list(&mut self)
+    {
+        self.reference_list = None;
+        self.mode = AppMode::Normal;
+    }
 
-        // Rendering the paragraph happens here
-        frame.render_widget(paragraph, content_area);
+    /// Opens the list of the cached RFCs.
+    ///
+    /// Shows a message instead if nothing is cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache_browser` - The list to show
+    pub fn open_cache_browser(&mut self, cache_browser: CacheBrowser)
+    {
+        if cache_browser.selected().is_none()
+        {
+            self.show_message("No cached RFCs found");
+            return;
+        }
 
-        // Render statusbar
-        self.render_statusbar(frame, statusbar_area);
+        self.cache_browser = Some(cache_browser);
+        self.mode = AppMode::CacheBrowser;
+    }
 
-        // Render help if in help mode
-        if self.mode == AppMode::Help
+    /// Closes the list of the cached RFCs.
+    pub fn close_cache_browser(&mut self)
+    {
+        self.cache_browser = None;
+        self.mode = AppMode::Normal;
+    }
+
+    /// Checks if a document is loaded.
+    ///
+    /// # Returns
+    ///
+    /// `false` while the index browser opened at startup has nothing to go
+    /// back to.
+    #[must_use]
+    pub const fn has_document(&self) -> bool
+    {
+        self.has_document
+    }
+
+    /// Sets the terminal window title for the current RFC.
+    ///
+    /// Skipped if disabled in the config or without a terminal. The original
+    /// title is saved first to be restored on exit.
+    fn set_window_title(&mut self)
+    {
+        if !self.config.set_window_title
         {
-            Self::render_help(frame);
+            return;
         }
 
-        // Render search if in search mode
-        if self.mode == AppMode::Search
+        let Some(guard) = &mut self.guard
+        else
         {
-            self.render_search(frame);
-        }
+            return;
+        };
+        guard.save_title();
 
-        // Render no search message
-        if self
-            .app_state
-            .contains(AppStateFlags::HAS_NO_RESULTS)
+        let title = match self.rfc_number
         {
-            Self::render_no_search_results(frame);
+            Some(rfc_number) => format!("RFC {rfc_number} - Press ? for help"),
+            None => String::from("RFC Reader - Press ? for help"),
+        };
+        if let Err(error) = execute!(stdout(), SetTitle(title))
+        {
+            warn!("Couldn't set the window title: {error}");
         }
     }
 
-    /// Renders the help overlay with keyboard shortcuts.
+    /// Finds the first RFC referenced on the line at the scroll position.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `frame` - The frame to render the help overlay to
-    fn render_help(frame: &mut Frame)
+    /// The referenced RFC number, or `None` if the line has no reference.
+    #[must_use]
+    pub fn current_line_reference(&self) -> Option<RfcNum>
     {
-        /// Help overlay box width as percentage of the terminal width.
-        const HELP_OVERLAY_WIDTH_CONSTRAINT: Constraint =
-            Constraint::Percentage(60);
-        /// Help overlay box height as percentage of the terminal height.
-        const HELP_OVERLAY_HEIGHT_CONSTRAINT: Constraint =
-            Constraint::Percentage(65);
-
-        // Create a centered rectangle.
-        let area = centered_rect(
-            frame.area(),
-            HELP_OVERLAY_WIDTH_CONSTRAINT,
-            HELP_OVERLAY_HEIGHT_CONSTRAINT,
-        );
-
-        // Clear the area first to make it fully opaque
-        frame.render_widget(Clear, area);
+        self.rfc_content
+            .lines()
+            .nth(self.current_line())
+            .and_then(|line| find_rfc_references(line).next())
+    }
 
-        let text = Text::from(vec![
-            Line::from("Keybindings:"),
-            Line::from(""),
-            // Vim-like navigation
-            Line::from("j/k or ↓/↑: Scroll down/up"),
-            Line::from("f/b or PgDn/PgUp: Scroll page down/up"),
-            Line::from("g/G: Go to start/end of document"),
-            Line::from(""),
-            Line::from("t: Toggle table of contents"),
-            Line::from("w/s: Navigate ToC up/down"),
-            Line::from("Enter: Jump to ToC entry"),
-            Line::from(""),
-            Line::from("/: Search"),
-            Line::from("n/N: Next/previous search result"),
-            Line::from("Ctrl+C: Toggle case sensitivity"),
-            Line::from("Ctrl+R: Toggle regex search"),
-            Line::from("Esc: Reset search highlights"),
-            Line::from(""),
-            Line::from("q: Quit"),
-            Line::from("?: Toggle help"),
-        ]);
+    /// Copies the line at the scroll position to the clipboard.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the copy was requested, `false` if the document is empty or
+    /// no clipboard is available.
+    #[must_use]
+    pub fn copy_current_line(&self) -> bool
+    {
+        let Some(line) = self
+            .rfc_content
+            .lines()
+            .nth(self.current_line())
+        else
+        {
+            return false;
+        };
 
-        let help_box = Paragraph::new(text)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("RFC Reader Help")
-                    .title_alignment(Alignment::Center)
-                    .style(Style::default()),
-            )
-            .style(Style::default())
-            .wrap(Wrap { trim: true });
+        if self.guard.is_none()
+        {
+            warn!("No clipboard available to copy the current line");
+            return false;
+        }
 
-        // Put the help box in it.
-        frame.render_widget(help_box, area);
+        self.copy_to_clipboard(line.trim_end())
     }
 
-    /// Renders the search input box.
+    /// Joins the lines with search matches, each prefixed by its line number.
     ///
-    /// # Arguments
+    /// Only the collected matches are included, so a capped search leaves
+    /// out the lines past the cap.
     ///
-    /// * `frame` - The frame to render the search box to
-    fn render_search(&self, frame: &mut Frame)
+    /// # Returns
+    ///
+    /// The lines as `<line>: <text>`, or `None` without search results.
+    #[must_use]
+    pub fn search_matches_text(&self) -> Option<String>
     {
-        /// Search prompt prefix.
-        const SEARCH_PROMPT: &str = "/";
-        /// Prefix length for the search prompt ("/").
-        #[allow(
-            clippy::cast_possible_truncation,
-            reason = "Terminal width is excpected to fit in u16 bounds"
-        )]
-        const SEARCH_PREFIX_LENGTH: u16 = SEARCH_PROMPT.len() as _;
-        /// Search box height in rows.
-        const SEARCH_BOX_HEIGHT_ROWS: u16 = 3;
-        /// Horizontal start position divisor (x = width /
-        /// `SEARCH_BOX_X_DIVISOR`).
-        const SEARCH_BOX_X_DIVISOR: u16 = 4;
-        /// Box width divisor (`box_width` = width /
-        /// `SEARCH_BOX_WIDTH_DIVISOR`).
-        const SEARCH_BOX_WIDTH_DIVISOR: u16 = 2;
-        /// Distance from bottom in rows.
-        const SEARCH_BOX_BOTTOM_OFFSET_ROWS: u16 = 4;
-        /// Border width for cursor position calculation.
-        const SEARCH_BOX_BORDER_WIDTH: u16 = 1;
-
-        let area = Rect::new(
-            frame.area().width / SEARCH_BOX_X_DIVISOR,
-            frame
-                .area()
-                .height
-                .saturating_sub(SEARCH_BOX_BOTTOM_OFFSET_ROWS),
-            frame.area().width / SEARCH_BOX_WIDTH_DIVISOR,
-            SEARCH_BOX_HEIGHT_ROWS,
-        );
-
-        // Clear the area first to make it fully opaque
-        frame.render_widget(Clear, area);
+        if !self.has_search_results()
+        {
+            return None;
+        }
 
-        let text = Text::from(format!("{}{}", SEARCH_PROMPT, self.query_text));
+        let lines: Vec<&str> = self.rfc_content.lines().collect();
+        let text = self
+            .query_match_line_nums
+            .iter()
+            .filter_map(|&line_num| {
+                lines.get(line_num).map(|line| {
+                    format!(
+                        "{}: {}",
+                        line_num.saturating_add(1),
+                        line.trim_end()
+                    )
+                })
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
 
-        let search_box = Paragraph::new(text)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("Search")
-                    .style(Style::default()),
-            )
-            .style(Style::default());
+        Some(text)
+    }
 
-        frame.render_widget(search_box, area);
+    /// Jumps to the definition of the ABNF rule referenced on the line at the
+    /// scroll position.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a definition was found and jumped to.
+    pub fn jump_to_abnf_definition(&mut self) -> bool
+    {
+        let current_line = self.current_line();
+        let definition = self
+            .rfc_content
+            .lines()
+            .nth(current_line)
+            .and_then(|line| {
+                self.rfc_abnf_index
+                    .find_referenced_definition(line, current_line)
+            });
 
-        // Calculate cursor position
-        // The cursor should be after the "/" prefix and at the current position
-        // in the query text
-        let cursor_x = area
-            .x
-            .saturating_add(SEARCH_BOX_BORDER_WIDTH)
-            .saturating_add(SEARCH_PREFIX_LENGTH)
-            .saturating_add(
-                self.query_text
-                    .get(..self.query_cursor_pos)
-                    .map_or(0, |before_cursor| before_cursor.chars().count())
-                    .try_into()
-                    .unwrap_or(0),
-            );
-        let cursor_y = area
-            .y
-            .saturating_add(SEARCH_BOX_BORDER_WIDTH);
+        if let Some(line_num) = definition
+        {
+            self.jump_to_line(line_num);
+        }
 
-        // Set cursor position
-        frame.set_cursor_position((cursor_x, cursor_y));
+        definition.is_some()
     }
 
-    /// Renders the no search results message.
+    /// Starts waiting for a background fetch of an RFC.
     ///
     /// # Arguments
     ///
-    /// * `frame` - The frame to render the no search results message to
-    fn render_no_search_results(frame: &mut Frame)
+    /// * `rfc_number` - The RFC being fetched
+    /// * `purpose` - What the RFC is fetched for
+    ///
+    /// # Returns
+    ///
+    /// The id of the fetch, to hand back to [`Self::finish_fetch`].
+    pub fn start_fetch(
+        &mut self,
+        rfc_number: RfcNum,
+        purpose: FetchPurpose,
+    ) -> u64
     {
-        /// No-search-results overlay width as percentage of the terminal width.
-        const NO_SEARCH_OVERLAY_WIDTH_CONSTRAINT: Constraint =
-            Constraint::Percentage(40);
-        /// No-search-results overlay height percentage.
-        const NO_SEARCH_OVERLAY_HEIGHT_CONSTRAINT: Constraint =
-            Constraint::Percentage(25);
-        /// No-search-results overlay title text.
-        const NO_SEARCH_TITLE: &str = "No matches - Press Esc to dismiss";
-        /// No-search-results overlay message text.
-        const NO_SEARCH_MESSAGE: &str = "Search yielded nothing";
+        self.fetch_count = self.fetch_count.wrapping_add(1);
+        self.pending_fetch = Some(PendingFetch {
+            id: self.fetch_count,
+            rfc_number,
+            purpose,
+            started_at: Instant::now(),
+        });
+        self.mode = AppMode::Fetching;
 
-        let area = centered_rect(
-            frame.area(),
-            NO_SEARCH_OVERLAY_WIDTH_CONSTRAINT,
-            NO_SEARCH_OVERLAY_HEIGHT_CONSTRAINT,
-        );
+        self.fetch_count
+    }
 
-        // Clear the area first to make it fully opaque
-        frame.render_widget(Clear, area);
+    /// Cancels the pending fetch, keeping the current document.
+    ///
+    /// The result of the fetch is discarded once it arrives.
+    pub const fn cancel_fetch(&mut self)
+    {
+        self.pending_fetch = None;
+        self.mode = AppMode::Normal;
+    }
 
-        let text = Text::raw(NO_SEARCH_MESSAGE);
+    /// Completes the pending fetch with the given id.
+    ///
+    /// A canceled fetch of the same RFC may finish after a new one was
+    /// started, so fetches are told apart by id rather than by RFC.
+    ///
+    /// # Arguments
+    ///
+    /// * `fetch_id` - The id [`Self::start_fetch`] returned for the fetch
+    ///
+    /// # Returns
+    ///
+    /// What the RFC was fetched for if the fetch was still awaited, `None` if
+    /// it was canceled.
+    pub fn finish_fetch(&mut self, fetch_id: u64) -> Option<FetchPurpose>
+    {
+        let purpose = self
+            .pending_fetch
+            .filter(|pending| pending.id == fetch_id)
+            .map(|pending| pending.purpose);
 
-        let no_search_box = Paragraph::new(text)
-            .block(
-                Block::default()
-                    .title(NO_SEARCH_TITLE)
-                    .borders(Borders::ALL)
-                    .style(Style::default().fg(Color::Red)),
-            )
-            .alignment(Alignment::Center)
-            .style(Style::default());
+        if purpose.is_some()
+        {
+            self.cancel_fetch();
+        }
 
-        frame.render_widget(no_search_box, area);
+        purpose
     }
 
-    /// Renders the too small message.
-    ///
-    /// The message is displayed when the terminal is too small to display
-    /// the application.
+    /// Shows a peek at a referenced RFC.
     ///
     /// # Arguments
     ///
-    /// * `frame` - The frame to render the too small message to
-    fn render_too_small_message(frame: &mut Frame)
+    /// * `rfc_number` - The RFC peeked at
+    /// * `content` - The text of the RFC to take the metadata from
+    pub fn show_peek(&mut self, rfc_number: RfcNum, content: &str)
     {
-        /// "Terminal too small" overlay height as percentage of the terminal
-        /// height.
-        const TOO_SMALL_OVERLAY_HEIGHT_CONSTRAINT: Constraint =
-            Constraint::Percentage(50);
-        /// "Terminal too small" overlay title text.
-        const TOO_SMALL_ERROR_TEXT: &str = "Terminal size is too small:";
+        self.peek = Some(ReferencePeek {
+            rfc_number,
+            metadata: RfcMetadata::parse(content),
+        });
+        self.mode = AppMode::Peek;
+    }
+
+    /// Closes the peek.
+    ///
+    /// # Returns
+    ///
+    /// The RFC that was peeked at, if any.
+    pub fn close_peek(&mut self) -> Option<RfcNum>
+    {
+        self.mode = AppMode::Normal;
 
-        let (current_width, current_height) =
-            size().expect("Couldn't get terminal size");
+        self.peek.take().map(|peek| peek.rfc_number)
+    }
 
-        // Determine colors based on whether dimensions meet requirements
-        let current_width_color = if current_width >= MIN_TERMINAL_WIDTH
-        {
-            Color::Green
-        }
+    /// Copies text to the system clipboard.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to copy
+    ///
+    /// # Returns
+    ///
+    /// `true` if the copy was requested from the terminal, `false` without a
+    /// terminal or if writing to it failed.
+    #[must_use]
+    pub fn copy_to_clipboard(&self, text: &str) -> bool
+    {
+        let Some(guard) = &self.guard
         else
         {
-            Color::Red
+            return false;
         };
 
-        let current_height_color = if current_height >= MIN_TERMINAL_HEIGHT
+        if let Err(err) = guard.copy_to_clipboard(text)
         {
-            Color::Green
+            error!("Failed to copy to the clipboard: {err}");
+            return false;
         }
-        else
-        {
-            Color::Red
-        };
-
-        // Clear the area first to make it fully opaque
-        frame.render_widget(Clear, frame.area());
-
-        let area = centered_rect(
-            frame.area(),
-            Constraint::Min(
-                TOO_SMALL_ERROR_TEXT
-                    .len()
-                    .try_into()
-                    .expect("TOO_SMALL_ERROR_TEXT length too big to cast"),
-            ),
-            TOO_SMALL_OVERLAY_HEIGHT_CONSTRAINT,
-        );
 
-        let text = Text::from(vec![
-            Line::from(TOO_SMALL_ERROR_TEXT),
-            Line::from(vec![
-                Span::raw("Width: "),
-                Span::styled(
-                    format!("{current_width}"),
-                    Style::default()
-                        .fg(current_width_color)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::raw(", "),
-                Span::raw("Height: "),
-                Span::styled(
-                    format!("{current_height}"),
-                    Style::default()
-                        .fg(current_height_color)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]),
-            Line::from(""),
-            Line::from("Minimum required:"),
-            Line::from(vec![
-                Span::raw("Width: "),
-                Span::styled(
-                    format!("{MIN_TERMINAL_WIDTH}"),
-                    Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::raw(", "),
-                Span::raw("Height: "),
-                Span::styled(
-                    format!("{MIN_TERMINAL_HEIGHT}"),
-                    Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]),
-        ]);
+        true
+    }
 
-        let paragraph = Paragraph::new(text).alignment(Alignment::Center);
+    /// Shows a message until the next key press.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to show
+    pub fn show_message(&mut self, message: impl Into<String>)
+    {
+        self.message = Some(message.into());
+    }
 
-        frame.render_widget(paragraph, area);
+    /// Dismisses the shown message.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a message was shown.
+    pub fn dismiss_message(&mut self) -> bool
+    {
+        self.message.take().is_some()
     }
 
-    /// Renders the statusbar with current status.
+    /// Checks if the terminal is too small.
     ///
     /// # Arguments
     ///
-    /// * `frame` - The frame to render the statusbar to
-    /// * `area` - The area to render the statusbar in
-    fn render_statusbar(&self, frame: &mut Frame, area: Rect)
+    /// * `area` - The area of the whole terminal
+    ///
+    /// # Returns
+    ///
+    /// A boolean indicating if the terminal is too small.
+    const fn is_terminal_too_small(area: Rect) -> bool
     {
-        // Build text content first so sections are sized to their actual
-        // content.
-        let progress_text = self.build_progress_text();
-        let left_text = format!("RFC {} | {}", self.rfc_number, progress_text);
-        let mode_text = self.get_mode_text();
-        let help_text = self.get_help_text();
+        area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT
+    }
 
-        #[expect(
-            clippy::cast_possible_truncation,
-            reason = "Statusbar text lengths fit in u16"
-        )]
-        let left_len = left_text.chars().count() as u16;
-        #[expect(
-            clippy::cast_possible_truncation,
-            reason = "Statusbar text lengths fit in u16"
-        )]
-        let right_len = help_text.chars().count() as u16;
+    /// Renders the document text, following the scroll position.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render to
+    /// * `content_area` - The area of the content
+    fn render_content(&mut self, frame: &mut Frame, content_area: Rect)
+    {
+        // Search matches refer to the original lines, so those are shown
+        // while the matches are highlighted
+        let should_reflow = self
+            .app_state
+            .contains(AppStateFlags::SHOULD_REFLOW) &&
+            !self.shows_search_highlights();
 
-        let [left_section, middle_section, right_section] = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Length(left_len),
-                Constraint::Fill(1),
-                Constraint::Length(right_len),
-            ])
-            .flex(Flex::SpaceBetween)
-            .areas(area);
+        if should_reflow
+        {
+            self.update_reflow_cache(content_area.width.into());
+        }
 
-        // Left section
-        let left_statusbar = Paragraph::new(left_text)
-            .style(STATUSBAR_STYLE)
-            .alignment(Alignment::Left);
-        frame.render_widget(left_statusbar, left_section);
+        // Render the text with highlights if in search mode or if there is a
+        // search text
+        let (mut text, scroll_row) = if should_reflow
+        {
+            self.build_reflowed_text()
+        }
+        else
+        {
+            (self.build_text(), self.current_scroll_pos)
+        };
 
-        // Middle section
-        let middle_statusbar = Paragraph::new(mode_text)
-            .style(STATUSBAR_STYLE)
-            .alignment(Alignment::Center);
-        frame.render_widget(middle_statusbar, middle_section);
+        if self.config.show_end_markers
+        {
+            // Enough to fill the viewport even when scrolled to the last line
+            text.lines.extend(iter::repeat_n(
+                Line::styled(END_MARKER, self.theme.end_marker),
+                self.viewport_height,
+            ));
+        }
 
-        // Right section
-        let right_statusbar = Paragraph::new(help_text)
-            .style(STATUSBAR_STYLE)
-            .alignment(Alignment::Right);
-        frame.render_widget(right_statusbar, right_section);
+        // Clamp the scroll position instead of panicking
+        let y = u16::try_from(scroll_row).unwrap_or(u16::MAX);
+        let paragraph = Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .scroll((y, 0));
+
+        // Rendering the paragraph happens here
+        frame.render_widget(paragraph, content_area);
     }
 
-    /// Builds the mode text representation for the statusbar.
+    /// Renders the `ToC` panel on its configured side.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A string containing the current mode.
-    fn get_mode_text(&self) -> Cow<'static, str>
+    /// * `frame` - The frame to render to
+    /// * `area` - The area of the panel
+    fn render_toc(&mut self, frame: &mut Frame, area: Rect)
     {
-        match self.mode
-        {
-            AppMode::Normal
-                if self
-                    .app_state
-                    .contains(AppStateFlags::SHOULD_SHOW_TOC) =>
-            {
-                Cow::Borrowed("NORMAL (ToC)")
-            },
-            AppMode::Normal => Cow::Borrowed("NORMAL"),
-            AppMode::Help => Cow::Borrowed("HELP"),
-            AppMode::Search => Cow::Owned(self.get_search_mode_text()),
-        }
+        self.rfc_toc_panel.render(
+            frame,
+            area,
+            &self.theme,
+            self.config.toc_position,
+            self.config.toc_title_overflow,
+        );
     }
 
-    /// Builds the search mode text for the statusbar.
-    /// Includes case sensitivity and regex flags.
+    /// Splits the main area between the content and the `ToC`.
+    ///
+    /// The `ToC` covers the whole main area if the content would be too
+    /// narrow beside it.
+    ///
+    /// # Arguments
+    ///
+    /// * `main_area` - The area above the statusbar
     ///
     /// # Returns
     ///
-    /// A string containing the search mode text.
-    fn get_search_mode_text(&self) -> String
+    /// The content area and the `ToC` area if the `ToC` is shown.
+    fn split_main_area(&mut self, main_area: Rect) -> (Rect, Option<Rect>)
     {
-        const EMPTY_BOX_CHAR: char = '☐';
-        const CHECKED_BOX_CHAR: char = '☑';
-
-        let case_char = if self
+        if self
             .app_state
-            .contains(AppStateFlags::IS_CASE_SENSITIVE)
+            .contains(AppStateFlags::SHOULD_SHOW_TOC)
         {
-            CHECKED_BOX_CHAR
+            let [toc_constraint, content_constraint] =
+                self.toc_split_constraints();
+            let (toc_area, content_area) = match self.config.toc_position
+            {
+                TocPosition::Left =>
+                {
+                    let [toc_area, content_area] = Layout::horizontal([
+                        toc_constraint,
+                        content_constraint,
+                    ])
+                    .areas(main_area);
+                    (toc_area, content_area)
+                },
+                TocPosition::Right =>
+                {
+                    let [content_area, toc_area] = Layout::horizontal([
+                        content_constraint,
+                        toc_constraint,
+                    ])
+                    .areas(main_area);
+                    (toc_area, content_area)
+                },
+            };
+
+            self.is_toc_overlaid =
+                content_area.width < MIN_CONTENT_WIDTH_FOR_TOC_SPLIT;
+
+            if self.is_toc_overlaid
+            {
+                (main_area, Some(main_area))
+            }
+            else
+            {
+                (content_area, Some(toc_area))
+            }
         }
         else
         {
-            EMPTY_BOX_CHAR
-        };
-
-        let regex_char = if self
-            .app_state
-            .contains(AppStateFlags::IS_USING_REGEX)
-        {
-            CHECKED_BOX_CHAR
+            self.is_toc_overlaid = false;
+            (main_area, None)
         }
-        else
-        {
-            EMPTY_BOX_CHAR
-        };
-
-        format!("SEARCH | C:{case_char} R:{regex_char}")
     }
 
-    /// Builds the progress text for the statusbar.
+    /// Checks if the search matches are highlighted.
     ///
     /// # Returns
     ///
-    /// A string containing the current line number, total lines, progress
-    /// percentage, and search information.
-    #[expect(
-        clippy::arithmetic_side_effects,
-        reason = "LineNumber not expected to overflow"
-    )]
-    fn build_progress_text(&self) -> String
+    /// `true` if there are search results outside of the search mode.
+    fn shows_search_highlights(&self) -> bool
     {
-        let progress_percentage = {
-            let last_line_pos = self.rfc_line_number.saturating_sub(1);
+        // Keep confirmed highlights in Normal mode, but hide them while
+        // actively editing in Search mode to avoid stale visuals.
+        self.mode != AppMode::Search && self.has_search_results()
+    }
 
-            (self.current_scroll_pos * 100)
-                .checked_div(last_line_pos)
-                .unwrap_or(if self.rfc_line_number > 0 { 100 } else { 0 })
-        };
+    /// Builds the RFC text with highlighting for search matches and titles.
+    fn build_text(&self) -> Text<'_>
+    {
+        let should_show_search_highlights = self.shows_search_highlights();
+        let should_trim_trailing_whitespace = self
+            .app_state
+            .contains(AppStateFlags::SHOULD_TRIM_TRAILING_WHITESPACE);
+        let should_show_whitespace = self
+            .app_state
+            .contains(AppStateFlags::SHOULD_SHOW_WHITESPACE);
+        let highlighted_line = self
+            .config
+            .highlight_current_line
+            .then(|| self.current_line());
 
-        let search_info = self.build_search_info().unwrap_or_default();
+        let lines: Vec<Line> = self
+            .rfc_content
+            .lines()
+            .enumerate()
+            .map(|(line_num, line_str)| {
+                // Only the end is trimmed, leading indentation is crucial for
+                // diagrams. Match spans are clamped against the trimmed line
+                // when the line is built.
+                let line_str = if should_trim_trailing_whitespace
+                {
+                    line_str.trim_end()
+                }
+                else
+                {
+                    line_str
+                };
+
+                let is_title = self.config.highlight_titles &&
+                    self.rfc_toc_panel
+                        .entries()
+                        .binary_search_by(|entry| {
+                            entry.line_number.cmp(&line_num)
+                        })
+                        .is_ok();
+
+                let line = match self.query_matches.get(&line_num)
+                {
+                    // Highlight search match
+                    Some(matches) if should_show_search_highlights =>
+                    {
+                        Self::build_line_with_search_and_title_highlights(
+                            line_str,
+                            matches,
+                            is_title,
+                            &self.theme,
+                        )
+                    },
+                    // Only title highlighting
+                    _ if is_title => Line::from(Span::styled(
+                        line_str,
+                        self.theme.title_highlight,
+                    )),
+                    // No highlighting
+                    _ => Line::from(line_str),
+                };
 
-        format!(
-            "L {}/{} ({}%){}",
-            self.current_scroll_pos + 1,
-            self.rfc_line_number,
-            progress_percentage,
-            search_info
-        )
+                // Set as the line style, which the span styles of the other
+                // highlights are patched over
+                let line = if highlighted_line == Some(line_num)
+                {
+                    line.style(self.theme.current_line)
+                }
+                else
+                {
+                    line
+                };
+
+                // Substituted after highlighting so match offsets still
+                // refer to the original text
+                if should_show_whitespace
+                {
+                    reveal_whitespace(line, self.theme.whitespace)
+                }
+                else
+                {
+                    hide_form_feeds(line)
+                }
+            })
+            .collect();
+
+        Text::from(lines)
     }
 
-    /// Builds the search info text for the statusbar.
-    /// This includes the current match index and total match count.
+    /// Reflows the content to the given width unless it already is.
     ///
-    /// # Returns
+    /// Reflowing rewraps the whole document, too slow to redo every frame.
     ///
-    /// An `Option<String>` containing the search info if there are matches,
-    /// or `None` if there are no matches or the query is empty.
-    #[expect(
-        clippy::arithmetic_side_effects,
-        reason = "LineNumber not expected to overflow"
-    )]
-    fn build_search_info(&self) -> Option<String>
+    /// # Arguments
+    ///
+    /// * `width` - The width to reflow the prose to
+    fn update_reflow_cache(&mut self, width: usize)
     {
-        // Don't show the previous search's info when entering a new search.
-        if self.mode == AppMode::Search || !self.has_search_results()
+        if self
+            .reflow_cache
+            .as_ref()
+            .is_some_and(|&(cached_width, _)| cached_width == width)
         {
-            return None;
+            return;
         }
 
-        let total_matches_n: LineNumber = self.query_match_line_nums.len();
-        // Clamp index to last valid match
-        let index: LineNumber = self
-            .current_query_match_index
-            .min(total_matches_n.saturating_sub(1));
-
-        Some(format!(" | M {}/{}", index + 1, total_matches_n))
+        let reflowed = reflow(&self.rfc_content, width)
+            .into_iter()
+            .map(ReflowedLine::into_owned)
+            .collect();
+        self.reflow_cache = Some((width, reflowed));
     }
 
-    /// Builds the help text for the statusbar.
-    /// Helps the user understand available commands.
+    /// Builds the RFC text with its prose reflowed by
+    /// [`Self::update_reflow_cache`].
+    ///
+    /// Only titles are highlighted, as search matches refer to the original
+    /// lines.
     ///
     /// # Returns
     ///
-    /// A string containing the help text for the statusbar.
-    const fn get_help_text(&self) -> &'static str
+    /// The reflowed text and the row of the text to scroll to, the first one
+    /// starting at or after the scroll position.
+    fn build_reflowed_text(&self) -> (Text<'_>, LineNumber)
     {
-        match (self.mode, self.has_search_results())
-        {
-            (AppMode::Normal, _)
-                if self
-                    .app_state
-                    .contains(AppStateFlags::SHOULD_SHOW_TOC) =>
-            {
-                "t:toggle ToC  w/s:nav  Enter:jump  q:quit"
-            },
-            (AppMode::Normal, true) => "n/N:next/prev  Esc:clear",
-            (AppMode::Normal, false) =>
-            {
-                "up/down:scroll  /:search  ?:help  q:quit"
-            },
-            (AppMode::Help, _) => "?/Esc:close",
-            (AppMode::Search, _) => "Enter:search  Esc:cancel",
-        }
+        let reflowed = self
+            .reflow_cache
+            .as_ref()
+            .map_or(&[][..], |(_, lines)| lines.as_slice());
+
+        let scroll_row = reflowed
+            .iter()
+            .position(|line| line.source_line >= self.current_scroll_pos)
+            .unwrap_or(reflowed.len());
+
+        let lines: Vec<Line> = reflowed
+            .iter()
+            .map(|line| {
+                let is_title = self.config.highlight_titles &&
+                    self.rfc_toc_panel
+                        .entries()
+                        .binary_search_by(|entry| {
+                            entry.line_number.cmp(&line.source_line)
+                        })
+                        .is_ok();
+
+                let line = if is_title
+                {
+                    Line::from(Span::styled(
+                        line.text.as_ref(),
+                        self.theme.title_highlight,
+                    ))
+                }
+                else
+                {
+                    Line::from(line.text.as_ref())
+                };
+
+                hide_form_feeds(line)
+            })
+            .collect();
+
+        (Text::from(lines), scroll_row)
     }
 
-    /// Scrolls the document up by the specified amount.
+    /// Builds a line with both search and title highlighting.
     ///
     /// # Arguments
     ///
-    /// * `amount` - Number of lines to scroll up
-    pub const fn scroll_up(&mut self, amount: LineNumber)
-    {
-        // Don't allow wrapping, once we reach the top, stay there.
-        self.current_scroll_pos = self
-            .current_scroll_pos
-            .saturating_sub(amount);
-    }
-
-    /// Scrolls the document down by the specified amount.
+    /// * `line_str` - The line content
+    /// * `matches` - Search match spans in the line
+    /// * `is_title` - Whether this line is a title
+    /// * `theme` - The theme to take the highlight styles from
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `amount` - Number of lines to scroll down
-    pub fn scroll_down(&mut self, amount: LineNumber)
+    /// A `Line` with appropriate highlighting applied.
+    fn build_line_with_search_and_title_highlights<'line_str>(
+        line_str: &'line_str str,
+        matches: &[MatchSpan],
+        is_title: bool,
+        theme: &Theme,
+    ) -> Line<'line_str>
     {
-        let last_line_pos = self.rfc_line_number.saturating_sub(1);
-        // Clamp the scroll position to the last line.
-        // Once we reach the bottom, stay there.
-        self.current_scroll_pos = (self
-            .current_scroll_pos
-            .saturating_add(amount))
-        .min(last_line_pos);
-    }
+        let mut spans = Vec::new();
+        let mut last_end = 0;
 
-    /// Jumps to the current `ToC` entry by scrolling to its line.
-    ///
-    /// If no entry is selected, does nothing.
-    pub fn jump_to_toc_entry(&mut self)
-    {
-        if let Some(line_num) = self.rfc_toc_panel.selected_line()
+        for match_span in matches
         {
-            self.current_scroll_pos = line_num;
-        }
-    }
+            // Clamp indexes to the line length to avoid out of bounds access
+            let start = match_span.start.min(line_str.len());
+            let end = match_span.end.min(line_str.len());
 
-    /// Toggles the help overlay.
-    pub fn toggle_help(&mut self)
-    {
-        self.mode = if self.mode == AppMode::Help
-        {
-            AppMode::Normal
-        }
-        else
-        {
-            AppMode::Help
-        };
-    }
+            // The match lies entirely in the trimmed part of the line
+            if start >= end
+            {
+                continue;
+            }
 
-    /// Toggles the table of contents panel.
-    ///
-    /// If the panel is shown, it will be hidden, and vice versa.
-    pub fn toggle_toc(&mut self)
-    {
-        self.app_state
-            .toggle(AppStateFlags::SHOULD_SHOW_TOC);
-    }
-
-    /// Toggles case sensitivity for searches.
-    ///
-    /// If case sensitivity is enabled, searches will be case-sensitive.
-    /// If disabled, searches will be case-insensitive.
-    pub fn toggle_case_sensitivity(&mut self)
-    {
-        self.app_state
-            .toggle(AppStateFlags::IS_CASE_SENSITIVE);
-    }
-
-    /// Toggles regex mode for searches.
-    ///
-    /// If regex mode is enabled, searches will interpret the query as a regex
-    /// pattern.
-    pub fn toggle_regex_mode(&mut self)
-    {
-        self.app_state
-            .toggle(AppStateFlags::IS_USING_REGEX);
-    }
+            if start > last_end &&
+                let Some(text) = line_str.get(last_end..start)
+            {
+                if is_title
+                {
+                    spans.push(Span::styled(text, theme.title_highlight));
+                }
+                else
+                {
+                    spans.push(Span::raw(text));
+                }
+            }
 
-    /// Enters search mode, clearing any previous search.
-    pub fn enter_search_mode(&mut self)
-    {
-        self.mode = AppMode::Search;
-        self.query_text.clear(); // Start with an empty search
-        self.query_cursor_pos = 0;
+            if let Some(mtc) = line_str.get(start..end)
+            {
+                spans.push(Span::styled(mtc, theme.match_highlight));
+            }
 
-        // Show cursor when entering search mode
-        if let Err(error) = execute!(stdout(), Show)
-        {
-            warn!("Failed to show cursor: {error}");
+            last_end = end;
         }
-    }
-
-    /// Exits search mode and returns to normal mode.
-    pub fn exit_search_mode(&mut self)
-    {
-        self.mode = AppMode::Normal;
 
-        // Hide cursor when exiting search mode
-        if let Err(error) = execute!(stdout(), Hide)
+        // Add remaining text after the last match
+        if last_end < line_str.len() &&
+            let Some(text) = line_str.get(last_end..)
         {
-            warn!("Failed to hide cursor: {error}");
+            if is_title
+            {
+                spans.push(Span::styled(text, theme.title_highlight));
+            }
+            else
+            {
+                spans.push(Span::raw(text));
+            }
         }
-    }
 
-    /// Checks if there are any search results.
-    ///
-    /// # Returns
-    ///
-    /// A boolean indicating if there are any search results.
-    const fn has_search_results(&self) -> bool
-    {
-        !self.query_text.is_empty() && !self.query_match_line_nums.is_empty()
+        Line::from(spans)
     }
 
-    /// Adds a character to the search text at cursor position.
+    /// Renders the application UI to the provided frame.
     ///
     /// # Arguments
     ///
-    /// * `ch` - The character to add
-    pub fn add_search_char(&mut self, ch: char)
+    /// * `frame` - The frame to render the UI to
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frame cannot be rendered.
+    pub fn render(&mut self, frame: &mut Frame)
     {
-        self.query_text
-            .insert(self.query_cursor_pos, ch);
-        self.query_cursor_pos = self
-            .query_cursor_pos
-            .saturating_add(ch.len_utf8());
-    }
+        /// Height of the status bar in rows.
+        const STATUSBAR_HEIGHT_ROWS: u16 = 1;
+        /// Height of the keybinding legend in rows.
+        const LEGEND_HEIGHT_ROWS: u16 = 1;
 
-    /// Removes the character before the cursor in the search text.
-    pub fn remove_search_char(&mut self)
-    {
-        if self.query_cursor_pos > 0
+        if Self::is_terminal_too_small(frame.area())
         {
-            self.move_search_cursor_left();
-            self.delete_search_char();
+            self.render_too_small_message(frame);
+            return;
         }
-    }
 
-    /// Deletes the character front of the cursor in the search text.
-    pub fn delete_search_char(&mut self)
-    {
-        if self.query_cursor_pos < self.query_text.len()
-        {
-            self.query_text.remove(self.query_cursor_pos);
-        }
-    }
+        // Clear the entire frame on each render to prevent artifacts
+        frame.render_widget(Clear, frame.area());
 
-    /// Moves the search cursor left by one character.
-    pub fn move_search_cursor_left(&mut self)
-    {
-        if self.query_cursor_pos > 0
+        let legend_height = if self.config.show_legend
         {
-            // Find the previous character boundary
-            let mut pos = self.query_cursor_pos.saturating_sub(1);
-            while pos > 0 && !self.query_text.is_char_boundary(pos)
-            {
-                pos = pos.saturating_sub(1);
-            }
-            self.query_cursor_pos = pos;
+            LEGEND_HEIGHT_ROWS
         }
-    }
-
-    /// Moves the search cursor right by one character.
-    pub fn move_search_cursor_right(&mut self)
-    {
-        if self.query_cursor_pos < self.query_text.len()
+        else
         {
-            let mut pos = self.query_cursor_pos.saturating_add(1);
-            while pos < self.query_text.len() &&
-                !self.query_text.is_char_boundary(pos)
-            {
-                pos = pos.saturating_add(1);
-            }
-            self.query_cursor_pos = pos;
+            0
+        };
+        // Hiding it leaves the row to the content
+        let statusbar_height = if self.config.show_statusbar
+        {
+            STATUSBAR_HEIGHT_ROWS
         }
-    }
-
-    /// Moves the search cursor to the start of the text.
-    pub const fn move_search_cursor_home(&mut self)
-    {
-        self.query_cursor_pos = 0;
-    }
+        else
+        {
+            0
+        };
 
-    /// Moves the search cursor to the end of the text.
-    pub const fn move_search_cursor_end(&mut self)
-    {
-        self.query_cursor_pos = self.query_text.len();
-    }
+        // Create main layout with statusbar at bottom, the legend above it
+        let [main_area, legend_area, statusbar_area] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0), // Main content takes remaining space
+                Constraint::Length(legend_height),
+                Constraint::Length(statusbar_height),
+            ])
+            .areas(frame.area());
 
-    /// Performs a search using the current search text.
-    ///
-    /// Finds all occurrences of the search text in the RFC content
-    /// and stores the results. If results are found, jumps to the
-    /// first result starting from the current scroll position.
-    pub fn perform_search(&mut self)
-    {
-        self.query_match_line_nums.clear();
-        self.query_matches.clear();
+        let (content_area, toc_area) = self.split_main_area(main_area);
 
-        if self.query_text.is_empty()
+        if let Some(toc_area) = toc_area &&
+            !self.is_toc_overlaid
         {
-            return;
+            self.render_toc(frame, toc_area);
         }
 
-        let is_case_sensitive = self
-            .app_state
-            .contains(AppStateFlags::IS_CASE_SENSITIVE);
-        let is_regex = self
-            .app_state
-            .contains(AppStateFlags::IS_USING_REGEX);
+        self.terminal_height = frame.area().height.into();
+        self.viewport_height = content_area.height.into();
 
-        let Some(regex) = get_compiled_regex(
-            self.query_text.clone(),
-            is_case_sensitive,
-            is_regex,
-        )
-        else
-        {
-            self.app_state
-                .insert(AppStateFlags::HAS_NO_RESULTS);
-            return;
-        };
+        self.render_content(frame, content_area);
 
-        // Compute all search matches first, then commit to app state
-        // atomically.
-        let search_results: Vec<(LineNumber, Vec<MatchSpan>)> =
-            collect_search_matches(&regex, &self.rfc_content);
+        // Shown over the content like the other overlays
+        if let Some(toc_area) = toc_area &&
+            self.is_toc_overlaid
+        {
+            frame.render_widget(Clear, toc_area);
+            self.render_toc(frame, toc_area);
+        }
 
-        self.query_match_line_nums
-            .reserve(search_results.len());
-        self.query_matches
-            .reserve(search_results.len());
+        // Render statusbar
+        if self.config.show_statusbar
+        {
+            self.render_statusbar(frame, statusbar_area);
+        }
 
-        for (line_num, matches_in_line) in search_results
+        if self.config.show_legend
         {
-            self.query_match_line_nums.push(line_num);
-            self.query_matches
-                .insert(line_num, matches_in_line);
+            self.render_legend(frame, legend_area);
         }
 
-        if self.query_match_line_nums.is_empty()
+        // Render help if in help mode
+        if self.mode == AppMode::Help
         {
-            self.app_state
-                .insert(AppStateFlags::HAS_NO_RESULTS);
+            self.render_help(frame);
         }
-        // Jump to the first result starting from our location.
-        else
+
+        // Render search if in search mode
+        if self.mode == AppMode::Search
         {
-            self.app_state
-                .remove(AppStateFlags::HAS_NO_RESULTS);
+            self.render_search(frame, main_area);
+        }
 
-            self.current_query_match_index = self
-                .query_match_line_nums
-                // First position where line_num >= self.current_scroll_pos
-                .partition_point(|&line_num: &LineNumber| {
-                    line_num < self.current_scroll_pos
-                });
+        if self.mode == AppMode::Goto
+        {
+            self.render_goto(frame, main_area);
+        }
 
-            self.jump_to_search_result();
+        // Render no search message
+        if self
+            .app_state
+            .contains(AppStateFlags::HAS_NO_RESULTS)
+        {
+            self.render_no_search_results(frame);
         }
-    }
 
-    /// Moves to the next search result after the current scroll position.
-    ///
-    /// If there are no search results, does nothing.
-    pub fn next_search_result(&mut self)
-    {
-        if !self.has_search_results()
+        self.render_list_overlay(frame);
+
+        if self.mode == AppMode::Peek &&
+            let Some(peek) = &self.peek
         {
-            return;
+            self.render_peek(frame, peek);
         }
 
-        // Find the first result after the current scroll position
-        if let Some(next_index) = self
-            .query_match_line_nums
-            .iter()
-            .position(|&line_num| line_num > self.current_scroll_pos)
+        if self.mode == AppMode::Sections
         {
-            self.current_query_match_index = next_index;
-            self.jump_to_search_result();
+            self.render_sections_menu(frame);
         }
-    }
 
-    /// Moves to the previous search result before the current scroll position.
-    ///
-    /// If there are no search results, does nothing.
-    pub fn prev_search_result(&mut self)
-    {
-        if !self.has_search_results()
+        if let Some(pending_fetch) = self.pending_fetch
         {
-            return;
+            self.render_fetching(frame, pending_fetch);
         }
 
-        // Find the last result before the current scroll position
-        if let Some(prev_index) = self
-            .query_match_line_nums
-            .iter()
-            .rposition(|&line_num| line_num < self.current_scroll_pos)
+        if let Some(message) = &self.message
         {
-            self.current_query_match_index = prev_index;
-            self.jump_to_search_result();
+            self.render_message(frame, message);
         }
     }
 
-    /// Jumps to the current search result by scrolling to its line.
-    fn jump_to_search_result(&mut self)
-    {
-        if let Some(line_num) = self
-            .query_match_line_nums
+    /// Renders the spinner overlay of a pending fetch.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the overlay to
+    /// * `pending_fetch` - The fetch being waited on
+    fn render_fetching(&self, frame: &mut Frame, pending_fetch: PendingFetch)
+    {
+        /// Frames of the spinner animation.
+        const SPINNER_FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+        /// Duration of a single spinner frame in milliseconds.
+        const SPINNER_FRAME_MILLIS: u128 = 200;
+        /// Fetch overlay width as percentage of the terminal width.
+        const FETCH_OVERLAY_WIDTH_CONSTRAINT: Constraint =
+            Constraint::Percentage(40);
+        /// Fetch overlay height in rows, including the borders.
+        const FETCH_OVERLAY_HEIGHT_CONSTRAINT: Constraint =
+            Constraint::Length(3);
+
+        let elapsed_frames = pending_fetch
+            .started_at
+            .elapsed()
+            .as_millis() /
+            SPINNER_FRAME_MILLIS;
+        let spinner_frame = usize::try_from(elapsed_frames)
+            .ok()
+            .and_then(|frames| frames.checked_rem(SPINNER_FRAMES.len()))
+            .and_then(|index| SPINNER_FRAMES.get(index))
+            .copied()
+            .unwrap_or_default();
+
+        let text = Text::raw(format!(
+            "{spinner_frame} Fetching RFC {}...",
+            pending_fetch.rfc_number
+        ));
+
+        self.render_centered_overlay(
+            frame,
+            FETCH_OVERLAY_WIDTH_CONSTRAINT,
+            FETCH_OVERLAY_HEIGHT_CONSTRAINT,
+            Block::bordered().title("Press Esc to cancel"),
+            text.centered(),
+            Style::default(),
+        );
+    }
+
+    /// Renders a message overlay.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the message to
+    /// * `message` - The message to show
+    fn render_message(&self, frame: &mut Frame, message: &str)
+    {
+        /// Message overlay width as percentage of the terminal width.
+        const MESSAGE_OVERLAY_WIDTH_CONSTRAINT: Constraint =
+            Constraint::Percentage(50);
+        /// Message overlay height as percentage of the terminal height.
+        const MESSAGE_OVERLAY_HEIGHT_CONSTRAINT: Constraint =
+            Constraint::Percentage(25);
+
+        self.render_centered_overlay(
+            frame,
+            MESSAGE_OVERLAY_WIDTH_CONSTRAINT,
+            MESSAGE_OVERLAY_HEIGHT_CONSTRAINT,
+            Block::bordered().title("Press any key to dismiss"),
+            Text::raw(message).centered(),
+            Style::default(),
+        );
+    }
+
+    /// Renders the peek at a referenced RFC.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the peek to
+    /// * `peek` - The peek to render
+    fn render_peek(&self, frame: &mut Frame, peek: &ReferencePeek)
+    {
+        /// Peek overlay width as percentage of the terminal width.
+        const PEEK_OVERLAY_WIDTH_CONSTRAINT: Constraint =
+            Constraint::Percentage(60);
+        /// Peek overlay height as percentage of the terminal height.
+        const PEEK_OVERLAY_HEIGHT_CONSTRAINT: Constraint =
+            Constraint::Percentage(40);
+
+        let metadata = &peek.metadata;
+        let mut lines = vec![
+            Line::from(
+                metadata
+                    .title
+                    .as_deref()
+                    .unwrap_or("Untitled"),
+            )
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+        ];
+        if let Some(category) = metadata.category
+        {
+            lines.push(Line::from(Span::styled(
+                category.to_string(),
+                self.theme.category.get(category),
+            )));
+        }
+        for (relation, rfc_numbers) in [
+            ("Updates", &metadata.updates),
+            ("Obsoletes", &metadata.obsoletes),
+        ]
+        {
+            if !rfc_numbers.is_empty()
+            {
+                lines.push(Line::from(format!(
+                    "{relation} {}",
+                    join_rfc_numbers(rfc_numbers)
+                )));
+            }
+        }
+        lines.push(Line::from(""));
+        lines.extend(
+            metadata
+                .preview()
+                .unwrap_or("No summary found")
+                .lines()
+                .map(Line::from),
+        );
+        let text = Text::from(lines);
+
+        let block = Block::bordered()
+            .title(Line::from(format!("RFC {}", peek.rfc_number)).centered())
+            .title_bottom(Line::from("Enter:open  Esc:close").centered());
+
+        self.render_centered_overlay(
+            frame,
+            PEEK_OVERLAY_WIDTH_CONSTRAINT,
+            PEEK_OVERLAY_HEIGHT_CONSTRAINT,
+            block,
+            text,
+            Style::default(),
+        );
+    }
+
+    /// Renders the menu of the standard sections.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the menu to
+    fn render_sections_menu(&self, frame: &mut Frame)
+    {
+        /// Sections menu width as percentage of the terminal width.
+        const SECTIONS_OVERLAY_WIDTH_CONSTRAINT: Constraint =
+            Constraint::Percentage(40);
+        /// Sections menu height in rows, one per section and the borders.
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "The section count fits in u16"
+        )]
+        const SECTIONS_OVERLAY_HEIGHT_CONSTRAINT: Constraint =
+            Constraint::Length(STANDARD_SECTIONS.len() as u16 + 2);
+
+        let lines: Vec<Line> = self
+            .standard_sections()
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, line_num))| match line_num
+            {
+                Some(line_num) => Line::from(format!(
+                    "{}: {name} (line {})",
+                    index.saturating_add(1),
+                    line_num.saturating_add(1)
+                )),
+                None => Line::styled(
+                    format!("{}: {name}", index.saturating_add(1)),
+                    self.theme.whitespace,
+                ),
+            })
+            .collect();
+
+        self.render_centered_overlay(
+            frame,
+            SECTIONS_OVERLAY_WIDTH_CONSTRAINT,
+            SECTIONS_OVERLAY_HEIGHT_CONSTRAINT,
+            Block::bordered().title(Line::from("Jump to").centered()),
+            Text::from(lines),
+            Style::default(),
+        );
+    }
+
+    /// Renders the help overlay with keyboard shortcuts.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the help overlay to
+    fn render_help(&mut self, frame: &mut Frame)
+    {
+        /// Help overlay box width as percentage of the terminal width.
+        const HELP_OVERLAY_WIDTH_CONSTRAINT: Constraint =
+            Constraint::Percentage(60);
+        /// Help overlay box height as percentage of the terminal height.
+        const HELP_OVERLAY_HEIGHT_CONSTRAINT: Constraint =
+            Constraint::Percentage(65);
+
+        let text = Text::from(vec![
+            Line::from("Keybindings:"),
+            Line::from(""),
+            // Vim-like navigation
+            Line::from("j/k or ↓/↑: Scroll down/up"),
+            Line::from("f/b or PgDn/PgUp: Scroll page down/up"),
+            Line::from("g/G: Go to start/end of document"),
+            Line::from("W: Toggle trailing whitespace trimming"),
+            Line::from("V: Toggle showing whitespace"),
+            Line::from("L: Toggle highlighting the current line"),
+            Line::from("T: Toggle highlighting the section titles"),
+            Line::from("c: Cycle the color of the search matches"),
+            Line::from("F: Toggle reflowing prose to the window width"),
+            Line::from(""),
+            Line::from("r: Open the RFC referenced on the current line"),
+            Line::from("p: Peek at the RFC referenced on the current line"),
+            Line::from("I: Browse the RFC index"),
+            Line::from("o: Pick a cached RFC to open"),
+            Line::from("P: Show and copy the cached file of the RFC"),
+            Line::from("Y: Copy the current line"),
+            Line::from("R: Reload the RFC from the network"),
+            Line::from("A: Toggle ABNF rule navigation"),
+            Line::from("d: Go to the ABNF rule referenced on the current line"),
+            Line::from(""),
+            Line::from("t: Toggle table of contents"),
+            Line::from("w/s: Navigate ToC up/down"),
+            Line::from("Enter: Jump to ToC entry"),
+            Line::from("</>: Narrow/widen ToC"),
+            Line::from(
+                "-/+: Collapse/expand all ToC levels, unless auto-scrolling",
+            ),
+            Line::from("[[: Go to the start of the section"),
+            Line::from(":N Enter: Go to line N"),
+            Line::from("S: Jump to a standard section, like the references"),
+            Line::from("C: Jump to the references and pick one to open"),
+            Line::from("[p/]p or {/}: Previous/next page"),
+            Line::from("m: Toggle a bookmark on the current line"),
+            Line::from("a: Toggle auto-scrolling, +/- to speed up/slow down"),
+            Line::from("[b/]b: Previous/next bookmark"),
+            Line::from(""),
+            Line::from("/: Search"),
+            Line::from("n/N: Next/previous search result"),
+            Line::from("&: Repeat the last search"),
+            Line::from("M: Copy the lines with search matches"),
+            Line::from("Ctrl+C: Toggle case sensitivity"),
+            Line::from("Ctrl+R: Toggle regex search"),
+            Line::from("Ctrl+L: Toggle matching phrases across line breaks"),
+            Line::from("Esc: Reset search highlights"),
+            Line::from(""),
+            Line::from("q: Quit"),
+            Line::from("?: Toggle help"),
+            Line::from("F1: Toggle the keybinding legend"),
+            Line::from("F2: Toggle the statusbar"),
+        ]);
+
+        let area = centered_rect(
+            frame.area(),
+            HELP_OVERLAY_WIDTH_CONSTRAINT,
+            HELP_OVERLAY_HEIGHT_CONSTRAINT,
+        );
+        // Inside the borders
+        let inner_width = usize::from(area.width.saturating_sub(2)).max(1);
+        let inner_height = area.height.saturating_sub(2);
+        // Wrapped rows of the text, as wide lines take more than one
+        let text_height: usize = text
+            .lines
+            .iter()
+            .map(|line| line.width().div_ceil(inner_width).max(1))
+            .sum();
+        let max_scroll = u16::try_from(text_height)
+            .unwrap_or(u16::MAX)
+            .saturating_sub(inner_height);
+        self.help_scroll_pos = self.help_scroll_pos.min(max_scroll);
+
+        let mut block =
+            Block::bordered().title(Line::from("RFC Reader Help").centered());
+        // Tell that there's more to the help than fits
+        if self.help_scroll_pos < max_scroll
+        {
+            block = block.title_bottom(
+                Line::from("j/k:scroll  (more below)").centered(),
+            );
+        }
+        else if max_scroll > 0
+        {
+            block = block.title_bottom(Line::from("j/k:scroll").centered());
+        }
+
+        let help = Paragraph::new(text)
+            .block(self.overlay_block(block, Style::default()))
+            .wrap(Wrap { trim: true })
+            .scroll((self.help_scroll_pos, 0));
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(help, area);
+    }
+
+    /// Renders the search input box.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the search box to
+    /// * `main_area` - The area above the statusbar to place the box in
+    fn render_search(&self, frame: &mut Frame, main_area: Rect)
+    {
+        // The active modes, like `Search [regex] [case]`
+        let mut title = String::from("Search");
+        for (flag, label) in [
+            (AppStateFlags::IS_USING_REGEX, " [regex]"),
+            (AppStateFlags::IS_CASE_SENSITIVE, " [case]"),
+            (AppStateFlags::IS_CROSS_LINE_SEARCH, " [lines]"),
+        ]
+        {
+            if self.app_state.contains(flag)
+            {
+                title.push_str(label);
+            }
+        }
+
+        self.render_input_box(
+            frame,
+            main_area,
+            &title,
+            "/",
+            &self.query_text,
+            self.query_cursor_pos,
+        );
+    }
+
+    /// Renders the line number input box of the goto mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the box to
+    /// * `main_area` - The area above the statusbar to place the box in
+    fn render_goto(&self, frame: &mut Frame, main_area: Rect)
+    {
+        self.render_input_box(
+            frame,
+            main_area,
+            "Go to line",
+            ":",
+            &self.goto_text,
+            self.goto_text.len(),
+        );
+    }
+
+    /// Renders a single line input box with the cursor in it.
+    ///
+    /// Input wider than the box is scrolled horizontally to keep the cursor
+    /// in view, rather than wrapped out of the box.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the box to
+    /// * `main_area` - The area above the statusbar to place the box in
+    /// * `title` - The title of the box
+    /// * `prompt` - The prefix before the input, like `/`
+    /// * `input` - The typed text
+    /// * `cursor_pos` - Cursor position in the typed text (byte index)
+    fn render_input_box(
+        &self,
+        frame: &mut Frame,
+        main_area: Rect,
+        title: &str,
+        prompt: &str,
+        input: &str,
+        cursor_pos: usize,
+    )
+    {
+        /// Border width for cursor position calculation.
+        const INPUT_BOX_BORDER_WIDTH: u16 = 1;
+
+        let area = search_box_area(main_area);
+
+        // The cursor should be after the prompt and at the current position
+        // in the input
+        let cursor_column: u16 = prompt
+            .chars()
+            .count()
+            .saturating_add(
+                input
+                    .get(..cursor_pos)
+                    .map_or(0, |before_cursor| before_cursor.chars().count()),
+            )
+            .try_into()
+            .unwrap_or(u16::MAX);
+        // Leaves the last column of the box to the cursor past the input
+        let last_column = area
+            .width
+            .saturating_sub(INPUT_BOX_BORDER_WIDTH.saturating_mul(2))
+            .saturating_sub(1);
+        let scroll_x = cursor_column.saturating_sub(last_column);
+
+        frame.render_widget(Clear, area);
+
+        // Not wrapped, the cursor would be out of place on the wrapped rows
+        let input_box = Paragraph::new(format!("{prompt}{input}"))
+            .block(self.overlay_block(
+                Block::bordered().title(title),
+                Style::default(),
+            ))
+            .scroll((0, scroll_x));
+
+        frame.render_widget(input_box, area);
+
+        let cursor_x = area
+            .x
+            .saturating_add(INPUT_BOX_BORDER_WIDTH)
+            .saturating_add(cursor_column.saturating_sub(scroll_x));
+        let cursor_y = area.y.saturating_add(INPUT_BOX_BORDER_WIDTH);
+
+        // Set cursor position
+        frame.set_cursor_position((cursor_x, cursor_y));
+    }
+
+    /// Renders the no search results message.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the no search results message to
+    fn render_no_search_results(&self, frame: &mut Frame)
+    {
+        /// No-search-results overlay width as percentage of the terminal width.
+        const NO_SEARCH_OVERLAY_WIDTH_CONSTRAINT: Constraint =
+            Constraint::Percentage(40);
+        /// No-search-results overlay height percentage.
+        const NO_SEARCH_OVERLAY_HEIGHT_CONSTRAINT: Constraint =
+            Constraint::Percentage(25);
+        /// No-search-results overlay title text.
+        const NO_SEARCH_TITLE: &str = "No matches - Press Esc to dismiss";
+        /// No-search-results overlay message text.
+        const NO_SEARCH_MESSAGE: &str = "Search yielded nothing";
+
+        self.render_centered_overlay(
+            frame,
+            NO_SEARCH_OVERLAY_WIDTH_CONSTRAINT,
+            NO_SEARCH_OVERLAY_HEIGHT_CONSTRAINT,
+            Block::bordered().title(NO_SEARCH_TITLE),
+            Text::raw(NO_SEARCH_MESSAGE).centered(),
+            Style::default().fg(Color::Red),
+        );
+    }
+
+    /// Renders the too small message.
+    ///
+    /// The message is displayed when the terminal is too small to display
+    /// the application.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the too small message to
+    fn render_too_small_message(&self, frame: &mut Frame)
+    {
+        /// "Terminal too small" overlay height as percentage of the terminal
+        /// height.
+        const TOO_SMALL_OVERLAY_HEIGHT_CONSTRAINT: Constraint =
+            Constraint::Percentage(50);
+        /// "Terminal too small" overlay title text.
+        const TOO_SMALL_ERROR_TEXT: &str = "Terminal size is too small:";
+
+        let Rect {
+            width: current_width,
+            height: current_height,
+            ..
+        } = frame.area();
+
+        // Determine colors based on whether dimensions meet requirements
+        let current_width_color = if current_width >= MIN_TERMINAL_WIDTH
+        {
+            Color::Green
+        }
+        else
+        {
+            Color::Red
+        };
+
+        let current_height_color = if current_height >= MIN_TERMINAL_HEIGHT
+        {
+            Color::Green
+        }
+        else
+        {
+            Color::Red
+        };
+
+        // Cover the whole terminal, the content wouldn't fit anyway
+        self.render_overlay(
+            frame,
+            frame.area(),
+            Block::new(),
+            Text::default(),
+            Style::default(),
+        );
+
+        let text = Text::from(vec![
+            Line::from(TOO_SMALL_ERROR_TEXT),
+            Line::from(vec![
+                Span::raw("Width: "),
+                Span::styled(
+                    format!("{current_width}"),
+                    Style::default()
+                        .fg(current_width_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(", "),
+                Span::raw("Height: "),
+                Span::styled(
+                    format!("{current_height}"),
+                    Style::default()
+                        .fg(current_height_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::from(""),
+            Line::from("Minimum required:"),
+            Line::from(vec![
+                Span::raw("Width: "),
+                Span::styled(
+                    format!("{MIN_TERMINAL_WIDTH}"),
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(", "),
+                Span::raw("Height: "),
+                Span::styled(
+                    format!("{MIN_TERMINAL_HEIGHT}"),
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+        ]);
+
+        self.render_centered_overlay(
+            frame,
+            Constraint::Min(
+                TOO_SMALL_ERROR_TEXT
+                    .len()
+                    .try_into()
+                    .expect("TOO_SMALL_ERROR_TEXT length too big to cast"),
+            ),
+            TOO_SMALL_OVERLAY_HEIGHT_CONSTRAINT,
+            Block::new(),
+            text.centered(),
+            Style::default(),
+        );
+    }
+
+    /// Renders an overlay, cleared to be opaque and styled by the theme.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the overlay to
+    /// * `area` - The area to cover
+    /// * `block` - The block around the content, with its borders and titles
+    /// * `text` - The content of the overlay, wrapped to the area
+    /// * `style` - The style patched over the theme's overlay style
+    fn render_overlay(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        block: Block<'_>,
+        text: Text<'_>,
+        style: Style,
+    )
+    {
+        frame.render_widget(Clear, area);
+
+        let overlay = Paragraph::new(text)
+            .block(self.overlay_block(block, style))
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(overlay, area);
+    }
+
+    /// Styles the block of an overlay by the theme.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - The block around the content, with its borders and titles
+    /// * `style` - The style patched over the theme's overlay style
+    ///
+    /// # Returns
+    ///
+    /// The styled block.
+    fn overlay_block<'block>(
+        &self,
+        block: Block<'block>,
+        style: Style,
+    ) -> Block<'block>
+    {
+        block
+            .style(self.theme.overlay.patch(style))
+            .border_style(self.theme.overlay_border.patch(style))
+    }
+
+    /// Renders an overlay centered in the frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the overlay to
+    /// * `width` - The width of the overlay
+    /// * `height` - The height of the overlay
+    /// * `block` - The block around the content, with its borders and titles
+    /// * `text` - The content of the overlay, wrapped to the area
+    /// * `style` - The style patched over the theme's overlay style
+    ///
+    /// # Returns
+    ///
+    /// The area covered by the overlay.
+    fn render_centered_overlay(
+        &self,
+        frame: &mut Frame,
+        width: Constraint,
+        height: Constraint,
+        block: Block<'_>,
+        text: Text<'_>,
+        style: Style,
+    ) -> Rect
+    {
+        let area = centered_rect(frame.area(), width, height);
+        self.render_overlay(frame, area, block, text, style);
+
+        area
+    }
+
+    /// Renders the statusbar with current status.
+    ///
+    /// The fields of each section are taken from the configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render the statusbar to
+    /// * `area` - The area to render the statusbar in
+    fn render_statusbar(&self, frame: &mut Frame, area: Rect)
+    {
+        let statusbar_config = &self.config.statusbar;
+
+        // Build text content first so sections are sized to their actual
+        // content.
+        let left_text = self.build_statusbar_section(&statusbar_config.left);
+        let middle_text =
+            self.build_statusbar_section(&statusbar_config.middle);
+        let right_text = self.build_statusbar_section(&statusbar_config.right);
+
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "Statusbar text lengths fit in u16"
+        )]
+        let left_len = left_text.width() as u16;
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "Statusbar text lengths fit in u16"
+        )]
+        let right_len = right_text.width() as u16;
+
+        let [left_section, middle_section, right_section] = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(left_len),
+                Constraint::Fill(1),
+                Constraint::Length(right_len),
+            ])
+            .flex(Flex::SpaceBetween)
+            .areas(area);
+
+        // Left section
+        let left_statusbar = Paragraph::new(left_text)
+            .style(self.theme.statusbar)
+            .alignment(Alignment::Left);
+        frame.render_widget(left_statusbar, left_section);
+
+        // Middle section
+        let middle_statusbar = Paragraph::new(middle_text)
+            .style(self.theme.statusbar)
+            .alignment(Alignment::Center);
+        frame.render_widget(middle_statusbar, middle_section);
+
+        // Right section
+        let right_statusbar = Paragraph::new(right_text)
+            .style(self.theme.statusbar)
+            .alignment(Alignment::Right);
+        frame.render_widget(right_statusbar, right_section);
+    }
+
+    /// Builds the text of a statusbar section from its fields.
+    ///
+    /// Fields without anything to show are skipped along with their
+    /// separator.
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - The fields to show in the section
+    ///
+    /// # Returns
+    ///
+    /// A line containing the section text, fields with their own style
+    /// as separate spans.
+    fn build_statusbar_section(&self, fields: &[StatusField]) -> Line<'_>
+    {
+        let mut section_text = Line::default();
+
+        for &field in fields
+        {
+            let Some(field_text) = self.build_statusbar_field(field)
+            else
+            {
+                continue;
+            };
+
+            if !section_text.spans.is_empty()
+            {
+                section_text.push_span(field.separator());
+            }
+            section_text.push_span(Span::styled(
+                field_text,
+                self.statusbar_style(field),
+            ));
+        }
+
+        section_text
+    }
+
+    /// Gets the style of a statusbar field over the statusbar style.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The field to style
+    ///
+    /// # Returns
+    ///
+    /// The style of the field, the statusbar style for most of them.
+    fn statusbar_style(&self, field: StatusField) -> Style
+    {
+        match (field, self.rfc_metadata.category)
+        {
+            (StatusField::Category, Some(category)) =>
+            {
+                self.theme.category.get(category)
+            },
+            _ => Style::default(),
+        }
+    }
+
+    /// Builds the text of a single statusbar field.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The field to build
+    ///
+    /// # Returns
+    ///
+    /// The field text, or `None` if the field has nothing to show.
+    #[expect(
+        clippy::arithmetic_side_effects,
+        reason = "LineNumber not expected to overflow"
+    )]
+    fn build_statusbar_field(&self, field: StatusField)
+    -> Option<Cow<'_, str>>
+    {
+        match field
+        {
+            StatusField::Rfc => self
+                .rfc_number
+                .map(|rfc_number| Cow::Owned(format!("RFC {rfc_number}"))),
+            StatusField::Line => Some(Cow::Owned(format!(
+                "L {}/{}{}",
+                self.current_scroll_pos + 1,
+                self.rfc_line_number,
+                if self.is_bookmarked() { " *" } else { "" }
+            ))),
+            StatusField::Percent =>
+            {
+                Some(Cow::Owned(format!("({}%)", self.progress_percentage())))
+            },
+            StatusField::Matches => self.build_search_info().map(Cow::Owned),
+            StatusField::WordCount =>
+            {
+                Some(Cow::Owned(format!("{} words", self.rfc_word_count)))
+            },
+            StatusField::Page =>
+            {
+                self.current_page()
+                    .map(|(page, page_count)| {
+                        Cow::Owned(format!("Page {page}/{page_count}"))
+                    })
+            },
+            StatusField::Category => self
+                .rfc_metadata
+                .category
+                .map(|category| Cow::Owned(category.to_string())),
+            StatusField::Source =>
+            {
+                Some(Cow::Owned(self.rfc_source.to_string()))
+            },
+            StatusField::Time => Some(Cow::Owned(
+                chrono::Local::now()
+                    .format("%H:%M")
+                    .to_string(),
+            )),
+            StatusField::Mode => Some(self.get_mode_text()),
+            StatusField::Help => Some(Cow::Borrowed(self.get_help_text())),
+        }
+    }
+
+    /// Builds the mode text representation for the statusbar.
+    ///
+    /// # Returns
+    ///
+    /// A string containing the current mode.
+    fn get_mode_text(&self) -> Cow<'static, str>
+    {
+        match self.mode
+        {
+            AppMode::Normal if self.is_autoscrolling() => Cow::Owned(format!(
+                "NORMAL (auto {}ms)",
+                self.config.autoscroll_interval_ms
+            )),
+            AppMode::Normal
+                if self
+                    .app_state
+                    .contains(AppStateFlags::SHOULD_SHOW_TOC) =>
+            {
+                Cow::Borrowed("NORMAL (ToC)")
+            },
+            AppMode::Normal => Cow::Borrowed("NORMAL"),
+            AppMode::Help => Cow::Borrowed("HELP"),
+            AppMode::Search => Cow::Owned(self.get_search_mode_text()),
+            AppMode::Goto => Cow::Owned(format!("GOTO :{}", self.goto_text)),
+            AppMode::Fetching => Cow::Borrowed("FETCHING"),
+            AppMode::Browse => Cow::Borrowed("BROWSE"),
+            AppMode::Peek => Cow::Borrowed("PEEK"),
+            AppMode::Sections => Cow::Borrowed("SECTIONS"),
+            AppMode::References => Cow::Borrowed("REFERENCES"),
+            AppMode::CacheBrowser => Cow::Borrowed("CACHED"),
+        }
+    }
+
+    /// Builds the search mode text for the statusbar.
+    /// Includes case sensitivity and regex flags.
+    ///
+    /// # Returns
+    ///
+    /// A string containing the search mode text.
+    fn get_search_mode_text(&self) -> String
+    {
+        const EMPTY_BOX_CHAR: char = '☐';
+        const CHECKED_BOX_CHAR: char = '☑';
+
+        let case_char = if self
+            .app_state
+            .contains(AppStateFlags::IS_CASE_SENSITIVE)
+        {
+            CHECKED_BOX_CHAR
+        }
+        else
+        {
+            EMPTY_BOX_CHAR
+        };
+
+        let regex_char = if self
+            .app_state
+            .contains(AppStateFlags::IS_USING_REGEX)
+        {
+            CHECKED_BOX_CHAR
+        }
+        else
+        {
+            EMPTY_BOX_CHAR
+        };
+
+        let cross_line_char = if self
+            .app_state
+            .contains(AppStateFlags::IS_CROSS_LINE_SEARCH)
+        {
+            CHECKED_BOX_CHAR
+        }
+        else
+        {
+            EMPTY_BOX_CHAR
+        };
+
+        format!("SEARCH | C:{case_char} R:{regex_char} L:{cross_line_char}")
+    }
+
+    /// Calculates the reading progress through the document.
+    ///
+    /// # Returns
+    ///
+    /// The progress percentage, based on the current scroll position.
+    #[expect(
+        clippy::arithmetic_side_effects,
+        reason = "LineNumber not expected to overflow"
+    )]
+    fn progress_percentage(&self) -> usize
+    {
+        let last_line_pos = self.rfc_line_number.saturating_sub(1);
+
+        (self.current_scroll_pos * 100)
+            .checked_div(last_line_pos)
+            .unwrap_or(if self.rfc_line_number > 0 { 100 } else { 0 })
+    }
+
+    /// Builds the search info text for the statusbar.
+    /// This includes the current match index and total match count.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<String>` containing the search info if there are matches,
+    /// or `None` if there are no matches or the query is empty.
+    #[expect(
+        clippy::arithmetic_side_effects,
+        reason = "LineNumber not expected to overflow"
+    )]
+    fn build_search_info(&self) -> Option<String>
+    {
+        // Don't show the previous search's info when entering a new search.
+        if self.mode == AppMode::Search || !self.has_search_results()
+        {
+            return None;
+        }
+
+        let total_matches_n: LineNumber = self.query_match_line_nums.len();
+        // Clamp index to last valid match
+        let index: LineNumber = self
+            .current_query_match_index
+            .min(total_matches_n.saturating_sub(1));
+
+        let percent = match self.config.match_percent
+        {
+            MatchPercent::Off => None,
+            MatchPercent::Matches => Some((index + 1) * 100 / total_matches_n),
+            MatchPercent::Document =>
+            {
+                let last_line_pos = self.rfc_line_number.saturating_sub(1);
+                self.query_match_line_nums
+                    .get(index)
+                    .map(|&line_num| {
+                        (line_num * 100)
+                            .checked_div(last_line_pos)
+                            .unwrap_or(100)
+                    })
+            },
+        };
+        let search_info = match percent
+        {
+            Some(percent) =>
+            {
+                format!("M {}/{} ({percent}%)", index + 1, total_matches_n)
+            },
+            None => format!("M {}/{}", index + 1, total_matches_n),
+        };
+
+        if self
+            .app_state
+            .contains(AppStateFlags::HAS_CAPPED_RESULTS)
+        {
+            return Some(format!(
+                "{search_info} | {}+ matches (refine query)",
+                self.config.max_search_matches
+            ));
+        }
+
+        Some(search_info)
+    }
+
+    /// Builds the help text for the statusbar.
+    /// Helps the user understand available commands.
+    ///
+    /// # Returns
+    ///
+    /// A string containing the help text for the statusbar.
+    const fn get_help_text(&self) -> &'static str
+    {
+        match (self.mode, self.has_search_results())
+        {
+            (AppMode::Normal, _)
+                if self
+                    .app_state
+                    .contains(AppStateFlags::SHOULD_SHOW_TOC) =>
+            {
+                "t:toggle ToC  w/s:nav  Enter:jump  q:quit"
+            },
+            (AppMode::Normal, true) => "n/N:next/prev  Esc:clear",
+            (AppMode::Normal, false) =>
+            {
+                "up/down:scroll  /:search  ?:help  q:quit"
+            },
+            (AppMode::Help, _) => "j/k:scroll  ?/Esc:close",
+            (AppMode::Search, _) => "Enter:search  Esc:cancel",
+            (AppMode::Goto, _) => "Enter:jump  Esc:cancel",
+            (AppMode::Fetching, _) => "Esc:cancel",
+            (AppMode::Peek, _) => "Enter:open  Esc:close",
+            (AppMode::Sections, _) => "1-4:jump  Esc:close",
+            (AppMode::References | AppMode::CacheBrowser, _) =>
+            {
+                "up/down:select  Enter:open  Esc:close"
+            },
+            (AppMode::Browse, _) => "type:filter  up/down:select  Enter:open",
+        }
+    }
+
+    /// Renders the list picked from in the current mode over the content,
+    /// like the index browser.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render to
+    fn render_list_overlay(&mut self, frame: &mut Frame)
+    {
+        let area = list_overlay_area(frame.area());
+
+        match self.mode
+        {
+            AppMode::Browse
+                if let Some(index_browser) = &mut self.index_browser =>
+            {
+                frame.render_widget(Clear, area);
+                index_browser.render(frame, area, &self.theme);
+            },
+            AppMode::References
+                if let Some(reference_list) = &mut self.reference_list =>
+            {
+                frame.render_widget(Clear, area);
+                reference_list.render(frame, area, &self.theme);
+            },
+            AppMode::CacheBrowser
+                if let Some(cache_browser) = &mut self.cache_browser =>
+            {
+                frame.render_widget(Clear, area);
+                cache_browser.render(frame, area, &self.theme);
+            },
+            _ =>
+            {},
+        }
+    }
+
+    /// Renders the keybinding legend above the statusbar.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render to
+    /// * `area` - The area of the legend
+    fn render_legend(&self, frame: &mut Frame, area: Rect)
+    {
+        let legend = Paragraph::new(self.get_legend_text())
+            .style(self.theme.statusbar)
+            .alignment(Alignment::Center);
+        frame.render_widget(legend, area);
+    }
+
+    /// Gets the fuller keybinding hints of the legend for the current mode.
+    ///
+    /// # Returns
+    ///
+    /// A string containing the hints.
+    const fn get_legend_text(&self) -> &'static str
+    {
+        match (self.mode, self.has_search_results())
+        {
+            (AppMode::Normal, _)
+                if self
+                    .app_state
+                    .contains(AppStateFlags::SHOULD_SHOW_TOC) =>
+            {
+                "t: hide contents  w/s: select entry  Enter: jump to entry  \
+                 </>: resize  -/+: collapse/expand  [[: section start"
+            },
+            (AppMode::Normal, true) =>
+            {
+                "n/N: next/previous match  &: repeat search  /: new search  \
+                 Esc: clear highlights"
+            },
+            (AppMode::Normal, false) =>
+            {
+                "j/k: scroll  f/b: page  g/G: start/end  /: search  t: \
+                 contents  ?: all keys  q: quit"
+            },
+            (AppMode::Help, _) => "j/k: scroll  ?/Esc: close the help",
+            (AppMode::Search, _) =>
+            {
+                "type: edit the query  Enter: search  Esc: cancel  Ctrl+C: \
+                 case sensitivity  Ctrl+R: regex  Ctrl+L: across lines"
+            },
+            (AppMode::Fetching, _) => "Esc: cancel the fetch",
+            (AppMode::Goto, _) =>
+            {
+                "type: the line number  Enter: jump to the line  Esc: cancel"
+            },
+            (AppMode::Peek, _) =>
+            {
+                "Enter: open the RFC  Esc: back to the document"
+            },
+            (AppMode::Browse, _) => "type: filter by number or title  \
+                                     up/down: select  Enter: open  Esc: close",
+            (AppMode::Sections, _) =>
+            {
+                "1-4: jump to the section  Esc: back to the document"
+            },
+            (AppMode::References, _) =>
+            {
+                "up/down: select  Enter: open the RFC or jump to the entry  \
+                 Esc: close"
+            },
+            (AppMode::CacheBrowser, _) =>
+            {
+                "up/down: select  Enter: open the RFC  Esc: back to the \
+                 document"
+            },
+        }
+    }
+
+    /// Toggles the keybinding legend above the statusbar.
+    pub const fn toggle_legend(&mut self)
+    {
+        self.config.show_legend = !self.config.show_legend;
+    }
+
+    /// Toggles the statusbar, leaving its row to the content when hidden.
+    pub const fn toggle_statusbar(&mut self)
+    {
+        self.config.show_statusbar = !self.config.show_statusbar;
+    }
+
+    /// Scrolls the document up by the specified amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Number of lines to scroll up
+    pub const fn scroll_up(&mut self, amount: LineNumber)
+    {
+        // Don't allow wrapping, once we reach the top, stay there.
+        self.current_scroll_pos = self
+            .current_scroll_pos
+            .saturating_sub(amount);
+    }
+
+    /// Scrolls the document down by the specified amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Number of lines to scroll down
+    pub fn scroll_down(&mut self, amount: LineNumber)
+    {
+        let last_line_pos = self.rfc_line_number.saturating_sub(1);
+        // Clamp the scroll position to the last line.
+        // Once we reach the bottom, stay there.
+        self.current_scroll_pos = (self
+            .current_scroll_pos
+            .saturating_add(amount))
+        .min(last_line_pos);
+    }
+
+    /// Enters goto mode to type a line number to jump to.
+    pub fn enter_goto_mode(&mut self)
+    {
+        self.mode = AppMode::Goto;
+        self.goto_text.clear();
+    }
+
+    /// Adds a digit to the typed line number, ignoring other characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `ch` - The typed character
+    pub fn add_goto_char(&mut self, ch: char)
+    {
+        /// Digits beyond any document's line count.
+        const MAX_GOTO_DIGITS: usize = 9;
+
+        if ch.is_ascii_digit() && self.goto_text.len() < MAX_GOTO_DIGITS
+        {
+            self.goto_text.push(ch);
+        }
+    }
+
+    /// Removes the last digit of the typed line number.
+    pub fn remove_goto_char(&mut self)
+    {
+        self.goto_text.pop();
+    }
+
+    /// Leaves goto mode without jumping.
+    pub fn cancel_goto(&mut self)
+    {
+        self.goto_text.clear();
+        self.mode = AppMode::Normal;
+    }
+
+    /// Jumps to the typed line number and leaves goto mode.
+    ///
+    /// Line numbers start at 1 like in the statusbar, and are clamped to the
+    /// document. Nothing typed only leaves the mode.
+    pub fn confirm_goto(&mut self)
+    {
+        if let Ok(line_num) = self.goto_text.parse::<LineNumber>()
+        {
+            self.jump_to_line(line_num.saturating_sub(1));
+        }
+
+        self.cancel_goto();
+    }
+
+    /// Starts or stops auto-scrolling.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time, the first line is scrolled an interval after
+    ///   it
+    pub const fn toggle_autoscroll(&mut self, now: Instant)
+    {
+        self.autoscroll_last = match self.autoscroll_last
+        {
+            Some(_) => None,
+            None => Some(now),
+        };
+    }
+
+    /// Checks if the document is being auto-scrolled.
+    ///
+    /// # Returns
+    ///
+    /// `true` if auto-scrolling.
+    #[must_use]
+    pub const fn is_autoscrolling(&self) -> bool
+    {
+        self.autoscroll_last.is_some()
+    }
+
+    /// Shortens the interval between the auto-scrolled lines, down to a
+    /// minimum.
+    pub fn speed_up_autoscroll(&mut self)
+    {
+        self.config.autoscroll_interval_ms = self
+            .config
+            .autoscroll_interval_ms
+            .saturating_sub(AUTOSCROLL_INTERVAL_STEP_MS)
+            .max(MIN_AUTOSCROLL_INTERVAL_MS);
+    }
+
+    /// Lengthens the interval between the auto-scrolled lines, up to a
+    /// maximum.
+    pub fn slow_down_autoscroll(&mut self)
+    {
+        self.config.autoscroll_interval_ms = self
+            .config
+            .autoscroll_interval_ms
+            .saturating_add(AUTOSCROLL_INTERVAL_STEP_MS)
+            .min(MAX_AUTOSCROLL_INTERVAL_MS);
+    }
+
+    /// Handles `+`, speeding up auto-scrolling, otherwise expanding the
+    /// shown `ToC`.
+    ///
+    /// Auto-scrolling goes first, as the `ToC` is often open while reading.
+    pub fn expand_or_speed_up(&mut self)
+    {
+        if self.is_autoscrolling()
+        {
+            self.speed_up_autoscroll();
+        }
+        else if self
+            .app_state
+            .contains(AppStateFlags::SHOULD_SHOW_TOC)
+        {
+            self.rfc_toc_panel.expand_all();
+        }
+    }
+
+    /// Handles `-`, slowing down auto-scrolling, otherwise collapsing the
+    /// shown `ToC`.
+    pub fn collapse_or_slow_down(&mut self)
+    {
+        if self.is_autoscrolling()
+        {
+            self.slow_down_autoscroll();
+        }
+        else if self
+            .app_state
+            .contains(AppStateFlags::SHOULD_SHOW_TOC)
+        {
+            self.rfc_toc_panel.collapse_all();
+        }
+    }
+
+    /// Scrolls the lines due since the last auto-scrolled one.
+    ///
+    /// Paused outside the normal mode, like while searching or reading the
+    /// help, and stopped once the last line is in view.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time
+    pub fn tick_autoscroll(&mut self, now: Instant)
+    {
+        let Some(last) = self.autoscroll_last
+        else
+        {
+            return;
+        };
+
+        // Nothing piles up while paused
+        if self.mode != AppMode::Normal
+        {
+            self.autoscroll_last = Some(now);
+            return;
+        }
+
+        let interval =
+            Duration::from_millis(self.config.autoscroll_interval_ms);
+        let elapsed = now.saturating_duration_since(last);
+        let due_lines = elapsed
+            .as_millis()
+            .checked_div(interval.as_millis())
+            .unwrap_or_default();
+        let due_lines = u32::try_from(due_lines).unwrap_or(u32::MAX);
+
+        let end_pos = self
+            .rfc_line_number
+            .saturating_sub(self.viewport_height.max(1));
+
+        if due_lines > 0
+        {
+            self.scroll_down(
+                LineNumber::try_from(due_lines)
+                    .unwrap_or(LineNumber::MAX)
+                    .min(end_pos.saturating_sub(self.current_scroll_pos)),
+            );
+            // The remainder counts towards the next line
+            self.autoscroll_last =
+                last.checked_add(interval.saturating_mul(due_lines));
+        }
+
+        if self.current_scroll_pos >= end_pos
+        {
+            self.autoscroll_last = None;
+        }
+    }
+
+    /// Jumps to the current `ToC` entry by scrolling to its line.
+    ///
+    /// If no entry is selected, does nothing. An overlaid `ToC` is closed to
+    /// reveal the content.
+    pub fn jump_to_toc_entry(&mut self)
+    {
+        if let Some(line_num) = self.rfc_toc_panel.selected_line()
+        {
+            self.jump_to_line(line_num);
+
+            if self.is_toc_overlaid
+            {
+                self.toggle_toc();
+            }
+        }
+    }
+
+    /// Finds the standard sections, like security considerations, in the
+    /// `ToC`.
+    ///
+    /// # Returns
+    ///
+    /// The name of each standard section and the line of its first `ToC`
+    /// entry, `None` if the document doesn't have it.
+    #[must_use]
+    pub fn standard_sections(
+        &self,
+    ) -> [(&'static str, Option<LineNumber>); STANDARD_SECTIONS.len()]
+    {
+        STANDARD_SECTIONS.map(|(name, needle)| {
+            let line_num = self
+                .rfc_toc_panel
+                .entries()
+                .iter()
+                .find(|entry| entry.title.to_lowercase().contains(needle))
+                .map(|entry| entry.line_number);
+
+            (name, line_num)
+        })
+    }
+
+    /// Opens the menu of the standard sections.
+    ///
+    /// Shows a message instead if the document has none of them.
+    pub fn open_sections_menu(&mut self)
+    {
+        if self
+            .standard_sections()
+            .iter()
+            .all(|(_, line_num)| line_num.is_none())
+        {
+            self.show_message("No standard sections found in the contents");
+            return;
+        }
+
+        self.mode = AppMode::Sections;
+    }
+
+    /// Closes the menu of the standard sections.
+    pub const fn close_sections_menu(&mut self)
+    {
+        self.mode = AppMode::Normal;
+    }
+
+    /// Jumps to a standard section picked from the menu, closing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The zero-based position of the section in the menu
+    ///
+    /// # Returns
+    ///
+    /// `true` if the document has the section.
+    pub fn jump_to_standard_section(&mut self, index: usize) -> bool
+    {
+        let Some(&(_, Some(line_num))) = self.standard_sections().get(index)
+        else
+        {
+            return false;
+        };
+
+        self.close_sections_menu();
+        self.jump_to_line(line_num);
+        true
+    }
+
+    /// Jumps to the start of the section being read.
+    ///
+    /// The section is the last `ToC` entry at or above the current line. If
+    /// already at its start, jumps to the start of the previous section
+    /// instead.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there was a section start to jump to.
+    pub fn jump_to_section_top(&mut self) -> bool
+    {
+        let entries = self.rfc_toc_panel.entries();
+        let current_line = self.current_line();
+        // The last section starting above the current line. From the start
+        // of a section, that's the previous one, skipping entries that
+        // share its line.
+        let target = entries
+            .partition_point(|entry| entry.line_number < current_line)
+            .checked_sub(1)
+            .and_then(|index| entries.get(index))
+            .map(|entry| entry.line_number);
+
+        let Some(line_num) = target
+        else
+        {
+            return false;
+        };
+
+        self.jump_to_line(line_num);
+        true
+    }
+
+    /// Gets the page of the current line.
+    ///
+    /// # Returns
+    ///
+    /// The one-based number of the current page and the page count, or
+    /// `None` if the document has no page breaks.
+    #[must_use]
+    pub fn current_page(&self) -> Option<(usize, usize)>
+    {
+        if self.rfc_page_starts.is_empty()
+        {
+            return None;
+        }
+
+        let current_line = self.current_line();
+        let page_index = self
+            .rfc_page_starts
+            .partition_point(|&page_start| page_start <= current_line);
+
+        Some((
+            page_index.saturating_add(1),
+            self.rfc_page_starts.len().saturating_add(1),
+        ))
+    }
+
+    /// Jumps to the start of the next page.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there was a next page to jump to.
+    pub fn next_page(&mut self) -> bool
+    {
+        let current_line = self.current_line();
+        let Some(&page_start) = self
+            .rfc_page_starts
+            .iter()
+            .find(|&&page_start| page_start > current_line)
+        else
+        {
+            return false;
+        };
+
+        self.jump_to_line(page_start);
+        true
+    }
+
+    /// Jumps to the start of the current or previous page.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there was a page break to jump back to.
+    pub fn prev_page(&mut self) -> bool
+    {
+        let current_line = self.current_line();
+        if self.rfc_page_starts.is_empty() || current_line == 0
+        {
+            return false;
+        }
+
+        // The first page starts at the top of the document
+        let page_start = self
+            .rfc_page_starts
+            .iter()
+            .rev()
+            .find(|&&page_start| page_start < current_line)
+            .copied()
+            .unwrap_or(0);
+
+        self.jump_to_line(page_start);
+        true
+    }
+
+    /// Bookmarks the current line, or removes its bookmark.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the line was bookmarked, `false` if its bookmark was
+    /// removed.
+    pub fn toggle_bookmark(&mut self) -> bool
+    {
+        let current_line = self.current_line();
+
+        match self.bookmarks.binary_search(&current_line)
+        {
+            Ok(index) =>
+            {
+                self.bookmarks.remove(index);
+                false
+            },
+            Err(index) =>
+            {
+                self.bookmarks.insert(index, current_line);
+                true
+            },
+        }
+    }
+
+    /// Replaces the bookmarks, like with the stored ones of the document.
+    ///
+    /// # Arguments
+    ///
+    /// * `bookmarks` - The bookmarked lines, those past the end of the document
+    ///   are dropped in case it got shorter
+    pub fn set_bookmarks(&mut self, mut bookmarks: Vec<LineNumber>)
+    {
+        bookmarks.retain(|&line| line < self.rfc_line_number);
+        bookmarks.sort_unstable();
+        bookmarks.dedup();
+
+        self.bookmarks = bookmarks;
+    }
+
+    /// Checks if the current line is bookmarked.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the current line is bookmarked.
+    #[must_use]
+    pub fn is_bookmarked(&self) -> bool
+    {
+        self.bookmarks
+            .binary_search(&self.current_line())
+            .is_ok()
+    }
+
+    /// Jumps to the next bookmark.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there was a bookmark after the current line.
+    pub fn next_bookmark(&mut self) -> bool
+    {
+        let current_line = self.current_line();
+        let Some(&bookmark) = self
+            .bookmarks
+            .iter()
+            .find(|&&bookmark| bookmark > current_line)
+        else
+        {
+            return false;
+        };
+
+        self.jump_to_line(bookmark);
+        true
+    }
+
+    /// Jumps to the previous bookmark.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there was a bookmark before the current line.
+    pub fn prev_bookmark(&mut self) -> bool
+    {
+        let current_line = self.current_line();
+        let Some(&bookmark) = self
+            .bookmarks
+            .iter()
+            .rev()
+            .find(|&&bookmark| bookmark < current_line)
+        else
+        {
+            return false;
+        };
+
+        self.jump_to_line(bookmark);
+        true
+    }
+
+    /// Gets the height of the terminal to scroll a page by.
+    ///
+    /// A failed query falls back to the last known height instead of
+    /// aborting, as it's usually transient.
+    ///
+    /// # Arguments
+    ///
+    /// * `terminal` - The terminal to query
+    ///
+    /// # Returns
+    ///
+    /// The height of the terminal in rows.
+    pub fn page_height<B: Backend>(
+        &mut self,
+        terminal: &Terminal<B>,
+    ) -> LineNumber
+    {
+        match terminal.size()
+        {
+            Ok(size) => self.terminal_height = size.height.into(),
+            Err(error) =>
+            {
+                warn!(
+                    "Failed to query the terminal size, using the last known \
+                     one: {error}"
+                );
+            },
+        }
+
+        self.terminal_height
+    }
+
+    /// Scrolls to a line, placing it according to the jump alignment.
+    ///
+    /// # Arguments
+    ///
+    /// * `line_num` - The line to jump to
+    pub fn jump_to_line(&mut self, line_num: LineNumber)
+    {
+        let offset = match self.config.jump_alignment
+        {
+            JumpAlignment::Top => 0,
+            JumpAlignment::Center => self.viewport_height / 2,
+            // Never more than centering would
+            JumpAlignment::ScrollOff(lines) =>
+            {
+                lines.min(self.viewport_height / 2)
+            },
+        };
+
+        let last_line_pos = self.rfc_line_number.saturating_sub(1);
+        self.current_scroll_pos = line_num
+            .saturating_sub(offset)
+            .min(last_line_pos);
+        self.last_jump = Some((line_num, self.current_scroll_pos));
+    }
+
+    /// Gets the line the reader is at.
+    ///
+    /// This is the target of the last jump while the view hasn't scrolled
+    /// since, otherwise the top line of the viewport.
+    ///
+    /// # Returns
+    ///
+    /// The current line number.
+    #[must_use]
+    pub const fn current_line(&self) -> LineNumber
+    {
+        match self.last_jump
+        {
+            Some((target, scroll_pos))
+                if scroll_pos == self.current_scroll_pos =>
+            {
+                target
+            },
+            _ => self.current_scroll_pos,
+        }
+    }
+
+    /// Toggles the help overlay, opening it at the top.
+    pub fn toggle_help(&mut self)
+    {
+        self.mode = if self.mode == AppMode::Help
+        {
+            AppMode::Normal
+        }
+        else
+        {
+            self.help_scroll_pos = 0;
+            AppMode::Help
+        };
+    }
+
+    /// Scrolls the help overlay down by one line, up to the end of its text.
+    pub const fn scroll_help_down(&mut self)
+    {
+        self.help_scroll_pos = self.help_scroll_pos.saturating_add(1);
+    }
+
+    /// Scrolls the help overlay up by one line.
+    pub const fn scroll_help_up(&mut self)
+    {
+        self.help_scroll_pos = self.help_scroll_pos.saturating_sub(1);
+    }
+
+    /// Toggles the table of contents panel.
+    ///
+    /// If the panel is shown, it will be hidden, and vice versa.
+    pub fn toggle_toc(&mut self)
+    {
+        self.app_state
+            .toggle(AppStateFlags::SHOULD_SHOW_TOC);
+    }
+
+    /// Widens the `ToC` panel, up to a maximum width.
+    pub fn widen_toc(&mut self)
+    {
+        self.config.toc_width = self
+            .config
+            .toc_width
+            .saturating_add(TOC_WIDTH_STEP)
+            .min(MAX_TOC_WIDTH);
+    }
+
+    /// Narrows the `ToC` panel, down to a minimum width.
+    pub fn narrow_toc(&mut self)
+    {
+        self.config.toc_width = self
+            .config
+            .toc_width
+            .saturating_sub(TOC_WIDTH_STEP)
+            .max(MIN_TOC_WIDTH);
+    }
+
+    /// Gets the constraints for the `ToC`/content split.
+    ///
+    /// # Returns
+    ///
+    /// The constraints of the `ToC` and the content area.
+    const fn toc_split_constraints(&self) -> [Constraint; 2]
+    {
+        let toc_width = self.config.toc_width;
+
+        [
+            Constraint::Percentage(toc_width),
+            Constraint::Percentage(100_u16.saturating_sub(toc_width)),
+        ]
+    }
+
+    /// Toggles showing whitespace and control characters as symbols.
+    ///
+    /// Only the rendering is affected, search offsets stay the same.
+    pub fn toggle_whitespace_display(&mut self)
+    {
+        self.app_state
+            .toggle(AppStateFlags::SHOULD_SHOW_WHITESPACE);
+    }
+
+    /// Toggles reflowing prose to the viewport width.
+    ///
+    /// Fenced and indented blocks are kept verbatim. The original lines are
+    /// shown while search matches are highlighted.
+    pub fn toggle_reflow(&mut self)
+    {
+        self.app_state
+            .toggle(AppStateFlags::SHOULD_REFLOW);
+    }
+
+    /// Toggles highlighting the current line.
+    ///
+    /// The preference is kept in the config to persist it.
+    pub const fn toggle_current_line_highlight(&mut self)
+    {
+        self.config.highlight_current_line =
+            !self.config.highlight_current_line;
+    }
+
+    /// Toggles highlighting of the section titles.
+    ///
+    /// The preference is kept in the config to persist it.
+    pub const fn toggle_title_highlight(&mut self)
+    {
+        self.config.highlight_titles = !self.config.highlight_titles;
+    }
+
+    /// Switches the search matches to the next preset color.
+    ///
+    /// The color is kept in the config to persist it.
+    pub fn cycle_match_color(&mut self)
+    {
+        let current = self.theme.match_highlight.fg;
+        let next = MATCH_COLORS
+            .iter()
+            .position(|&color| Some(color) == current)
+            .and_then(|index| MATCH_COLORS.get(index.saturating_add(1)))
+            .copied()
+            .unwrap_or(MATCH_COLORS[0]);
+
+        self.config.match_color = Some(ConfigColor(next));
+        self.theme.match_highlight = self.theme.match_highlight.fg(next);
+    }
+
+    /// Toggles trimming of trailing whitespace on render.
+    ///
+    /// Trailing whitespace is invisible but can leave styling artifacts at
+    /// line ends. Leading indentation is always preserved.
+    pub fn toggle_trailing_whitespace_trim(&mut self)
+    {
+        self.app_state
+            .toggle(AppStateFlags::SHOULD_TRIM_TRAILING_WHITESPACE);
+    }
+
+    /// Toggles following ABNF rule references to their definitions.
+    ///
+    /// Off by default since rule definitions are detected heuristically.
+    pub fn toggle_abnf_navigation(&mut self)
+    {
+        self.app_state
+            .toggle(AppStateFlags::IS_ABNF_NAVIGATION_ENABLED);
+    }
+
+    /// Toggles case sensitivity for searches.
+    ///
+    /// If case sensitivity is enabled, searches will be case-sensitive.
+    /// If disabled, searches will be case-insensitive.
+    pub fn toggle_case_sensitivity(&mut self)
+    {
+        self.app_state
+            .toggle(AppStateFlags::IS_CASE_SENSITIVE);
+        self.on_query_changed();
+    }
+
+    /// Toggles regex mode for searches.
+    ///
+    /// If regex mode is enabled, searches will interpret the query as a regex
+    /// pattern.
+    pub fn toggle_regex_mode(&mut self)
+    {
+        self.app_state
+            .toggle(AppStateFlags::IS_USING_REGEX);
+        self.on_query_changed();
+    }
+
+    /// Toggles matching phrases across line breaks for searches.
+    ///
+    /// If enabled, the query is matched against the words of the document
+    /// joined by single spaces, finding phrases wrapped over two lines.
+    pub fn toggle_cross_line_search(&mut self)
+    {
+        self.app_state
+            .toggle(AppStateFlags::IS_CROSS_LINE_SEARCH);
+        self.on_query_changed();
+    }
+
+    /// Enters search mode, clearing any previous search.
+    pub fn enter_search_mode(&mut self)
+    {
+        self.mode = AppMode::Search;
+        self.query_text.clear(); // Start with an empty search
+        self.query_cursor_pos = 0;
+        self.search_origin = self
+            .config
+            .search_preview
+            .then(|| (self.current_scroll_pos, self.current_line()));
+
+        // Show cursor when entering search mode
+        if let Err(error) = execute!(stdout(), Show)
+        {
+            warn!("Failed to show cursor: {error}");
+        }
+    }
+
+    /// Cancels the search being typed, returning to where it started with
+    /// the search preview.
+    pub fn cancel_search(&mut self)
+    {
+        if let Some(origin) = self.search_origin
+        {
+            self.restore_search_origin(origin);
+        }
+
+        // The highlights of the canceled query are dropped
+        if self.has_incremental_highlights
+        {
+            self.clear_search_matches();
+        }
+
+        self.exit_search_mode();
+    }
+
+    /// Exits search mode and returns to normal mode.
+    pub fn exit_search_mode(&mut self)
+    {
+        self.mode = AppMode::Normal;
+        self.search_origin = None;
+        self.query_changed_at = None;
+        self.incremental_pattern = None;
+        self.has_incremental_highlights = false;
+
+        // Hide cursor when exiting search mode
+        if let Err(error) = execute!(stdout(), Hide)
+        {
+            warn!("Failed to hide cursor: {error}");
+        }
+    }
+
+    /// Checks if there are any search results.
+    ///
+    /// # Returns
+    ///
+    /// A boolean indicating if there are any search results.
+    const fn has_search_results(&self) -> bool
+    {
+        !self.query_text.is_empty() && !self.query_match_line_nums.is_empty()
+    }
+
+    /// Adds a character to the search text at cursor position.
+    ///
+    /// # Arguments
+    ///
+    /// * `ch` - The character to add
+    pub fn add_search_char(&mut self, ch: char)
+    {
+        self.query_text
+            .insert(self.query_cursor_pos, ch);
+        self.query_cursor_pos = self
+            .query_cursor_pos
+            .saturating_add(ch.len_utf8());
+        self.on_query_changed();
+    }
+
+    /// Removes the character before the cursor in the search text.
+    pub fn remove_search_char(&mut self)
+    {
+        if self.query_cursor_pos > 0
+        {
+            self.move_search_cursor_left();
+            self.delete_search_char();
+        }
+    }
+
+    /// Deletes the character front of the cursor in the search text.
+    pub fn delete_search_char(&mut self)
+    {
+        if self.query_cursor_pos < self.query_text.len()
+        {
+            self.query_text.remove(self.query_cursor_pos);
+            self.on_query_changed();
+        }
+    }
+
+    /// Moves the search cursor left by one character.
+    pub fn move_search_cursor_left(&mut self)
+    {
+        if self.query_cursor_pos > 0
+        {
+            // Find the previous character boundary
+            let mut pos = self.query_cursor_pos.saturating_sub(1);
+            while pos > 0 && !self.query_text.is_char_boundary(pos)
+            {
+                pos = pos.saturating_sub(1);
+            }
+            self.query_cursor_pos = pos;
+        }
+    }
+
+    /// Moves the search cursor right by one character.
+    pub fn move_search_cursor_right(&mut self)
+    {
+        if self.query_cursor_pos < self.query_text.len()
+        {
+            let mut pos = self.query_cursor_pos.saturating_add(1);
+            while pos < self.query_text.len() &&
+                !self.query_text.is_char_boundary(pos)
+            {
+                pos = pos.saturating_add(1);
+            }
+            self.query_cursor_pos = pos;
+        }
+    }
+
+    /// Moves the search cursor to the start of the text.
+    pub const fn move_search_cursor_home(&mut self)
+    {
+        self.query_cursor_pos = 0;
+    }
+
+    /// Moves the search cursor to the end of the text.
+    pub const fn move_search_cursor_end(&mut self)
+    {
+        self.query_cursor_pos = self.query_text.len();
+    }
+
+    /// Updates the view for the changed query being typed.
+    ///
+    /// Follows the nearest match with the search preview, and schedules
+    /// highlighting the matches with the incremental search.
+    fn on_query_changed(&mut self)
+    {
+        self.preview_search();
+
+        if self.config.incremental_search
+        {
+            self.query_changed_at = Some(Instant::now());
+        }
+    }
+
+    /// Highlights the matches of the query being typed once it stayed
+    /// unchanged for a moment.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time
+    pub fn tick_incremental_search(&mut self, now: Instant)
+    {
+        let Some(changed_at) = self.query_changed_at
+        else
+        {
+            return;
+        };
+
+        if self.mode != AppMode::Search ||
+            now.saturating_duration_since(changed_at) <
+                INCREMENTAL_SEARCH_DELAY
+        {
+            return;
+        }
+
+        self.query_changed_at = None;
+        self.update_search_highlights();
+    }
+
+    /// Highlights the matches of the query being typed without jumping.
+    ///
+    /// Recomputed only if the query or the search flags changed since the
+    /// last time. An invalid regex, likely not fully typed yet, keeps the
+    /// previous highlights.
+    fn update_search_highlights(&mut self)
+    {
+        if self.query_text.is_empty()
+        {
+            self.clear_search_matches();
+            self.incremental_pattern = None;
+            return;
+        }
+
+        let Some(regex) = self.search_regex()
+        else
+        {
+            return;
+        };
+
+        if self.incremental_pattern.as_deref() == Some(regex.as_str())
+        {
+            return;
+        }
+
+        let (search_results, is_capped) = self.collect_query_matches(&regex);
+        self.set_search_results(search_results, is_capped);
+
+        // The nearest match is selected, like after the search
+        let current_line = self.current_line();
+        self.current_query_match_index = self
+            .query_match_line_nums
+            .partition_point(|&line_num| line_num < current_line)
+            .min(
+                self.query_match_line_nums
+                    .len()
+                    .saturating_sub(1),
+            );
+
+        self.incremental_pattern = Some(regex.as_str().to_owned());
+        self.has_incremental_highlights = true;
+    }
+
+    /// Clears the matches of the search, keeping the query.
+    fn clear_search_matches(&mut self)
+    {
+        self.query_match_line_nums.clear();
+        self.query_matches.clear();
+        self.current_query_match_index = 0;
+        self.app_state.remove(
+            AppStateFlags::HAS_NO_RESULTS | AppStateFlags::HAS_CAPPED_RESULTS,
+        );
+    }
+
+    /// Scrolls to the match of the search being typed nearest after where
+    /// the search started, wrapping around to the start of the document.
+    ///
+    /// Returns to where the search started if nothing matches. Does nothing
+    /// without the search preview.
+    fn preview_search(&mut self)
+    {
+        let Some(origin) = self.search_origin
+        else
+        {
+            return;
+        };
+
+        let regex = if self.query_text.is_empty()
+        {
+            None
+        }
+        else
+        {
+            self.search_regex()
+        };
+        let Some(regex) = regex
+        else
+        {
+            self.restore_search_origin(origin);
+            return;
+        };
+
+        let (_, origin_line) = origin;
+        let match_line = if self
+            .app_state
+            .contains(AppStateFlags::IS_CROSS_LINE_SEARCH)
+        {
+            let match_lines: Vec<LineNumber> =
+                collect_phrase_matches(&regex, &self.rfc_content, usize::MAX)
+                    .0
+                    .into_iter()
+                    .map(|(line_num, _)| line_num)
+                    .collect();
+
+            match_lines
+                .iter()
+                .find(|&&line_num| line_num >= origin_line)
+                .or_else(|| match_lines.first())
+                .copied()
+        }
+        else
+        {
+            self.rfc_content
+                .lines()
+                .enumerate()
+                .skip(origin_line)
+                .chain(
+                    self.rfc_content
+                        .lines()
+                        .enumerate()
+                        .take(origin_line),
+                )
+                .find(|(_, line)| regex.is_match(line))
+                .map(|(line_num, _)| line_num)
+        };
+
+        match match_line
+        {
+            Some(line_num) => self.jump_to_line(line_num),
+            None => self.restore_search_origin(origin),
+        }
+    }
+
+    /// Returns to where the search being typed started.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The scroll position and current line to return to
+    const fn restore_search_origin(
+        &mut self,
+        (scroll_pos, line_num): (LineNumber, LineNumber),
+    )
+    {
+        self.current_scroll_pos = scroll_pos;
+        self.last_jump = Some((line_num, scroll_pos));
+    }
+
+    /// Performs a search using the current search text.
+    ///
+    /// Finds all occurrences of the search text in the RFC content
+    /// and stores the results. If results are found, jumps to the
+    /// first result starting from the current scroll position.
+    pub fn perform_search(&mut self)
+    {
+        self.query_match_line_nums.clear();
+        self.query_matches.clear();
+        self.current_query_match_index = 0;
+
+        if self.query_text.is_empty()
+        {
+            return;
+        }
+
+        self.last_query.clone_from(&self.query_text);
+
+        let Some(regex) = self.search_regex()
+        else
+        {
+            self.app_state
+                .insert(AppStateFlags::HAS_NO_RESULTS);
+            return;
+        };
+
+        // Compute all search matches first, then commit to app state
+        // atomically
+        let (search_results, is_capped) = self.collect_query_matches(&regex);
+        self.set_search_results(search_results, is_capped);
+
+        if self.query_match_line_nums.is_empty()
+        {
+            self.app_state
+                .insert(AppStateFlags::HAS_NO_RESULTS);
+        }
+        // Jump to the first result starting from our location.
+        else
+        {
+            self.app_state
+                .remove(AppStateFlags::HAS_NO_RESULTS);
+
+            let current_line = self.current_line();
+            let last_index = self
+                .query_match_line_nums
+                .len()
+                .saturating_sub(1);
+            // First position where line_num >= current_line, the last match
+            // if all of them are above
+            self.current_query_match_index = self
+                .query_match_line_nums
+                .partition_point(|&line_num: &LineNumber| {
+                    line_num < current_line
+                })
+                .min(last_index);
+
+            self.jump_to_search_result();
+        }
+    }
+
+    /// Collects the matches of the query, capped at the match limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `regex` - The compiled query
+    ///
+    /// # Returns
+    ///
+    /// The lines with matches and the spans of the matches in each, and
+    /// whether matches past the limit were dropped.
+    fn collect_query_matches(
+        &self,
+        regex: &Regex,
+    ) -> (Vec<(LineNumber, Vec<MatchSpan>)>, bool)
+    {
+        let max_matches = self.config.max_search_matches;
+
+        if self
+            .app_state
+            .contains(AppStateFlags::IS_CROSS_LINE_SEARCH)
+        {
+            return collect_phrase_matches(
+                regex,
+                &self.rfc_content,
+                max_matches,
+            );
+        }
+
+        // One past the limit to tell whether it was hit
+        let mut search_results = collect_search_matches(
+            regex,
+            &self.rfc_content,
+            max_matches.saturating_add(1),
+        );
+        let is_capped =
+            truncate_search_matches(&mut search_results, max_matches);
+
+        (search_results, is_capped)
+    }
+
+    /// Replaces the matches of the search.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_results` - The lines with matches and the spans of the matches
+    ///   in each
+    /// * `is_capped` - Whether matches past the match limit were dropped
+    fn set_search_results(
+        &mut self,
+        search_results: Vec<(LineNumber, Vec<MatchSpan>)>,
+        is_capped: bool,
+    )
+    {
+        self.app_state
+            .set(AppStateFlags::HAS_CAPPED_RESULTS, is_capped);
+
+        self.query_match_line_nums.clear();
+        self.query_matches.clear();
+        self.query_match_line_nums
+            .reserve(search_results.len());
+        self.query_matches
+            .reserve(search_results.len());
+
+        for (line_num, matches_in_line) in search_results
+        {
+            self.query_match_line_nums.push(line_num);
+            self.query_matches
+                .insert(line_num, matches_in_line);
+        }
+    }
+
+    /// Gets the search regex, compiled again only if the query or the search
+    /// flags changed since the last time.
+    ///
+    /// # Returns
+    ///
+    /// The compiled regex, or `None` if the query is an invalid regex.
+    fn search_regex(&mut self) -> Option<Regex>
+    {
+        /// The flags changing how the query is compiled.
+        const SEARCH_FLAGS: AppStateFlags = AppStateFlags::IS_USING_REGEX
+            .union(AppStateFlags::IS_CASE_SENSITIVE)
+            .union(AppStateFlags::IS_CROSS_LINE_SEARCH);
+
+        let flags = self.app_state.bits() & SEARCH_FLAGS.bits();
+        if let Some((query, search_flags, regex)) = &self.search_regex &&
+            *query == self.query_text &&
+            *search_flags == flags
+        {
+            return regex.clone();
+        }
+
+        let regex = self.build_search_regex();
+        self.search_regex =
+            Some((self.query_text.clone(), flags, regex.clone()));
+
+        regex
+    }
+
+    /// Compiles the search text with the current search flags.
+    ///
+    /// Across line breaks, the whitespace of a plain query is matched like
+    /// that of the joined document text.
+    ///
+    /// # Returns
+    ///
+    /// The compiled regex, or `None` if the query is an invalid regex.
+    #[must_use]
+    pub fn build_search_regex(&self) -> Option<Regex>
+    {
+        let is_regex = self
+            .app_state
+            .contains(AppStateFlags::IS_USING_REGEX);
+        let query = if !is_regex &&
+            self.app_state
+                .contains(AppStateFlags::IS_CROSS_LINE_SEARCH)
+        {
+            normalize_phrase(&self.query_text)
+        }
+        else
+        {
+            self.query_text.clone()
+        };
+
+        compile_regex(
+            &query,
+            self.app_state
+                .contains(AppStateFlags::IS_CASE_SENSITIVE),
+            is_regex,
+        )
+    }
+
+    /// Moves to the next search result after the current scroll position.
+    ///
+    /// If there are no search results, does nothing.
+    pub fn next_search_result(&mut self)
+    {
+        if !self.has_search_results()
+        {
+            return;
+        }
+
+        // Find the first result after the current scroll position
+        if let Some(next_index) = self
+            .query_match_line_nums
+            .iter()
+            .position(|&line_num| line_num > self.current_line())
+        {
+            self.current_query_match_index = next_index;
+            self.jump_to_search_result();
+        }
+    }
+
+    /// Moves to the previous search result before the current scroll position.
+    ///
+    /// If there are no search results, does nothing.
+    pub fn prev_search_result(&mut self)
+    {
+        if !self.has_search_results()
+        {
+            return;
+        }
+
+        // Find the last result before the current scroll position
+        if let Some(prev_index) = self
+            .query_match_line_nums
+            .iter()
+            .rposition(|&line_num| line_num < self.current_line())
+        {
+            self.current_query_match_index = prev_index;
+            self.jump_to_search_result();
+        }
+    }
+
+    /// Jumps to the current search result by scrolling to its line.
+    fn jump_to_search_result(&mut self)
+    {
+        if let Some(&line_num) = self
+            .query_match_line_nums
             .get(self.current_query_match_index)
         {
-            self.current_scroll_pos = *line_num;
+            self.jump_to_line(line_num);
+        }
+    }
+
+    /// Searches the last query again, e.g. in a newly opened document.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there was a previous query to search for.
+    pub fn rerun_last_search(&mut self) -> bool
+    {
+        if self.last_query.is_empty()
+        {
+            return false;
+        }
+
+        self.query_text.clone_from(&self.last_query);
+        self.query_cursor_pos = self.query_text.len();
+        self.perform_search();
+
+        true
+    }
+
+    /// Resets the search highlights.
+    pub fn reset_search_highlights(&mut self)
+    {
+        self.query_text.clear();
+        self.query_match_line_nums.clear();
+        self.query_matches.clear();
+        self.current_query_match_index = 0;
+        self.app_state.remove(
+            AppStateFlags::HAS_NO_RESULTS | AppStateFlags::HAS_CAPPED_RESULTS,
+        );
+    }
+}
+
+impl Default for App
+{
+    fn default() -> Self
+    {
+        /// Initial capacities for common collections.
+        const QUERY_TEXT_INITIAL_CAPACITY: usize = 20;
+        const QUERY_RESULTS_INITIAL_CAPACITY: usize = 50;
+
+        Self {
+            rfc_content: DocumentText::default(),
+            rfc_number: None,
+            has_document: false,
+            rfc_toc_panel: TocPanel::default(),
+            rfc_line_number: 0,
+            rfc_word_count: 0,
+            rfc_source: DocumentSource::Cache,
+            rfc_abnf_index: AbnfIndex::default(),
+            rfc_metadata: RfcMetadata::default(),
+            rfc_page_starts: Vec::new(),
+            reflow_cache: None,
+            pending_motion: None,
+            bookmarks: Vec::new(),
+            autoscroll_last: None,
+            current_scroll_pos: 0,
+            viewport_height: 0,
+            terminal_height: 0,
+            is_toc_overlaid: false,
+            last_jump: None,
+            mode: AppMode::Normal,
+            help_scroll_pos: 0,
+            app_state: AppStateFlags::default(),
+            config: Config::default(),
+            theme: Theme::default(),
+            pending_fetch: None,
+            fetch_count: 0,
+            message: None,
+            index_browser: None,
+            peek: None,
+            reference_list: None,
+            cache_browser: None,
+            guard: None,
+            query_text: String::with_capacity(QUERY_TEXT_INITIAL_CAPACITY),
+            query_cursor_pos: 0,
+            goto_text: String::new(),
+            last_query: String::new(),
+            search_origin: None,
+            query_changed_at: None,
+            incremental_pattern: None,
+            search_regex: None,
+            has_incremental_highlights: false,
+            query_match_line_nums: Vec::with_capacity(
+                QUERY_RESULTS_INITIAL_CAPACITY,
+            ),
+            current_query_match_index: 0,
+            query_matches: HashMap::with_capacity(
+                QUERY_RESULTS_INITIAL_CAPACITY,
+            ),
+        }
+    }
+}
+
+/// Replaces whitespace and control characters with visible symbols.
+///
+/// Spaces become middots, tabs arrows and other control characters their
+/// Unicode control pictures, styled on top of their span's style.
+///
+/// # Arguments
+///
+/// * `line` - The line to reveal the whitespace of
+/// * `style` - The style patched onto the substituted symbols
+///
+/// # Returns
+///
+/// The line with the substitutions.
+fn reveal_whitespace(line: Line<'_>, style: Style) -> Line<'_>
+{
+    /// Gets the visible substitute of a character, if it needs one.
+    fn substitute(ch: char) -> Option<char>
+    {
+        /// Offset of the Unicode control pictures block.
+        const CONTROL_PICTURES_START: u32 = 0x2400;
+
+        match ch
+        {
+            ' ' => Some('·'),
+            '\t' => Some('→'),
+            '\x7f' => Some('␡'),
+            _ if ch.is_ascii_control() => char::from_u32(
+                CONTROL_PICTURES_START.saturating_add(u32::from(ch)),
+            ),
+            _ => None,
+        }
+    }
+
+    let mut spans = Vec::with_capacity(line.spans.len());
+
+    for span in line.spans
+    {
+        if !span
+            .content
+            .chars()
+            .any(|ch| substitute(ch).is_some())
+        {
+            spans.push(span);
+            continue;
+        }
+
+        let revealed_style = span.style.patch(style);
+        let run_style = |is_revealed: bool| {
+            if is_revealed
+            {
+                revealed_style
+            }
+            else
+            {
+                span.style
+            }
+        };
+        let mut text = String::new();
+        let mut is_revealed_run = false;
+
+        for ch in span.content.chars()
+        {
+            let substituted = substitute(ch);
+
+            // Flush the run when switching between plain and revealed text
+            if substituted.is_some() != is_revealed_run && !text.is_empty()
+            {
+                spans.push(Span::styled(
+                    std::mem::take(&mut text),
+                    run_style(is_revealed_run),
+                ));
+            }
+
+            is_revealed_run = substituted.is_some();
+            text.push(substituted.unwrap_or(ch));
+        }
+
+        spans.push(Span::styled(text, run_style(is_revealed_run)));
+    }
+
+    Line::from(spans)
+}
+
+/// Finds where the pages of a document start.
+///
+/// Pages end with a `[Page N]` footer or a form feed, the next page starts
+/// at the first line with text after it.
+///
+/// # Arguments
+///
+/// * `content` - The text of the document
+///
+/// # Returns
+///
+/// The first line of each page but the first one, in order.
+fn find_page_starts(content: &str) -> Vec<LineNumber>
+{
+    let is_blank = |line: &str| line.replace('\x0c', "").trim().is_empty();
+    let lines: Vec<&str> = content.lines().collect();
+    let mut page_starts: Vec<LineNumber> = Vec::new();
+
+    for (line_num, line) in lines.iter().enumerate()
+    {
+        let search_start = if is_page_footer(line) || line.contains('\x0c')
+        {
+            // Text following a form feed starts the page on the same line
+            if line.contains('\x0c') && !is_blank(line)
+            {
+                line_num
+            }
+            else
+            {
+                line_num.saturating_add(1)
+            }
+        }
+        else
+        {
+            continue;
+        };
+
+        let Some(page_start) = lines
+            .iter()
+            .enumerate()
+            .skip(search_start)
+            .find(|(_, line)| !is_blank(line))
+            .map(|(page_start, _)| page_start)
+        else
+        {
+            break;
+        };
+
+        // A footer followed by a form feed ends a single page
+        if page_start > 0 && page_starts.last() != Some(&page_start)
+        {
+            page_starts.push(page_start);
+        }
+    }
+
+    page_starts
+}
+
+/// Checks whether a line is a page footer, ending with `[Page N]`.
+///
+/// # Arguments
+///
+/// * `line` - The line to check
+///
+/// # Returns
+///
+/// `true` if the line ends with a page number.
+fn is_page_footer(line: &str) -> bool
+{
+    line.trim_end()
+        .strip_suffix(']')
+        .and_then(|rest| rest.rsplit_once("[Page "))
+        .is_some_and(|(_, page_number)| {
+            !page_number.is_empty() &&
+                page_number
+                    .bytes()
+                    .all(|byte| byte.is_ascii_digit())
+        })
+}
+
+/// Replaces form feeds with spaces, which would garble the terminal.
+///
+/// The replacement keeps the byte offsets of the search matches.
+///
+/// # Arguments
+///
+/// * `line` - The line to clean
+///
+/// # Returns
+///
+/// The line without form feeds.
+fn hide_form_feeds(mut line: Line<'_>) -> Line<'_>
+{
+    for span in &mut line.spans
+    {
+        if span.content.contains('\x0c')
+        {
+            span.content = span.content.replace('\x0c', " ").into();
+        }
+    }
+
+    line
+}
+
+/// Creates a centered rectangle inside the given area.
+///
+/// # Arguments
+///
+/// * `area` - The parent area
+/// * `horizontal` - The horizontal constraint
+/// * `vertical` - The vertical constraint
+///
+/// # Returns
+///
+/// A new rectangle positioned in the center of the parent.
+fn centered_rect(
+    area: Rect,
+    horizontal: Constraint,
+    vertical: Constraint,
+) -> Rect
+{
+    let [area] = Layout::horizontal([horizontal])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([vertical])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+/// Computes the area of the list overlays, like the index browser.
+///
+/// # Arguments
+///
+/// * `area` - The area of the frame
+///
+/// # Returns
+///
+/// The area of the overlay, centered in the frame.
+fn list_overlay_area(area: Rect) -> Rect
+{
+    /// List overlay size as percentage of the terminal size.
+    const LIST_OVERLAY_CONSTRAINT: Constraint = Constraint::Percentage(80);
+
+    centered_rect(area, LIST_OVERLAY_CONSTRAINT, LIST_OVERLAY_CONSTRAINT)
+}
+
+/// Computes the area of the search box.
+///
+/// The box sits at the bottom of the main area, so it never covers the
+/// statusbar, and shrinks to fit areas shorter than the box.
+///
+/// # Arguments
+///
+/// * `main_area` - The area above the statusbar
+///
+/// # Returns
+///
+/// The area of the search box within the main area.
+const fn search_box_area(main_area: Rect) -> Rect
+{
+    /// Search box height in rows.
+    const SEARCH_BOX_HEIGHT_ROWS: u16 = 3;
+    /// Horizontal start position divisor (x = width /
+    /// `SEARCH_BOX_X_DIVISOR`).
+    const SEARCH_BOX_X_DIVISOR: u16 = 4;
+    /// Box width divisor (`box_width` = width /
+    /// `SEARCH_BOX_WIDTH_DIVISOR`).
+    const SEARCH_BOX_WIDTH_DIVISOR: u16 = 2;
+
+    let height = if main_area.height < SEARCH_BOX_HEIGHT_ROWS
+    {
+        main_area.height
+    }
+    else
+    {
+        SEARCH_BOX_HEIGHT_ROWS
+    };
+
+    Rect::new(
+        main_area
+            .x
+            .saturating_add(main_area.width / SEARCH_BOX_X_DIVISOR),
+        main_area.bottom().saturating_sub(height),
+        main_area.width / SEARCH_BOX_WIDTH_DIVISOR,
+        height,
+    )
+}
+
+/// Search execution strategy for collecting query matches.
+#[derive(Debug, Clone, Copy)]
+enum SearchStrategy
+{
+    /// Process search linearly on a single thread.
+    Serial,
+    /// Process search using multiple workers.
+    Parallel
+    {
+        /// Number of worker threads to spawn.
+        worker_count: usize,
+    },
+}
+
+/// Collects all search matches for the given content.
+///
+/// Uses bounded parallelism for larger documents and falls back to serial
+/// processing for small documents or if a worker panics.
+///
+/// # Arguments
+///
+/// * `regex` - The regex to search with
+/// * `content` - The content to search in
+/// * `max_matches` - The number of matches after which each worker stops
+///   collecting, the total may exceed it
+///
+/// # Returns
+///
+/// An array of 2-tuples, where each tuple contains a line number and a vector
+/// of match spans for that line.
+fn collect_search_matches(
+    regex: &Regex,
+    content: &str,
+    max_matches: usize,
+) -> Vec<(LineNumber, Vec<MatchSpan>)>
+{
+    let lines: Vec<&str> = content.lines().collect();
+
+    let worker_count = match determine_search_strategy(lines.len())
+    {
+        SearchStrategy::Serial =>
+        {
+            return collect_search_matches_serial(
+                regex,
+                &lines,
+                0,
+                max_matches,
+            );
+        },
+        SearchStrategy::Parallel { worker_count } => worker_count,
+    };
+
+    // Assign each worker a contiguous chunk of lines.
+    let chunk_size = lines.len().div_ceil(worker_count);
+
+    let parallel_result: Option<Vec<(LineNumber, Vec<MatchSpan>)>> =
+        thread::scope(|scope| {
+            let mut handles = Vec::with_capacity(worker_count);
+
+            for (chunk_index, chunk) in lines.chunks(chunk_size).enumerate()
+            {
+                let line_offset = chunk_index.saturating_mul(chunk_size);
+                handles.push(scope.spawn(move || {
+                    collect_search_matches_serial(
+                        regex,
+                        chunk,
+                        line_offset,
+                        max_matches,
+                    )
+                }));
+            }
+
+            let mut all_matches: Vec<(LineNumber, Vec<MatchSpan>)> =
+                Vec::with_capacity(handles.len());
+
+            for handle in handles
+            {
+                match handle.join()
+                {
+                    Ok(mut chunk_matches) =>
+                    {
+                        all_matches.append(&mut chunk_matches);
+                    },
+                    Err(_) => return None,
+                }
+            }
+
+            Some(all_matches)
+        });
+
+    parallel_result
+        // Fallback to serial processing if any worker panicked.
+        .unwrap_or_else(|| {
+            collect_search_matches_serial(regex, &lines, 0, max_matches)
+        })
+}
+
+/// Collects search matches line-by-line in a serial pass.
+///
+/// # Arguments
+///
+/// * `regex` - The regex to search with
+/// * `lines` - The lines to search through
+/// * `line_offset` - The line number offset to apply to the results (used for
+///   parallel chunks)
+/// * `max_matches` - The number of matches after which to stop collecting
+///
+/// # Returns
+///
+/// An array of 2-tuples, where each tuple contains a line number and a vector
+/// of match spans for that line.
+fn collect_search_matches_serial(
+    regex: &Regex,
+    lines: &[&str],
+    line_offset: LineNumber,
+    max_matches: usize,
+) -> Vec<(LineNumber, Vec<MatchSpan>)>
+{
+    let mut results = Vec::new();
+    let mut remaining_matches = max_matches;
+
+    for (relative_line_num, line) in lines.iter().enumerate()
+    {
+        if remaining_matches == 0
+        {
+            break;
+        }
+
+        let mut matches_in_line: Vec<MatchSpan> = Vec::new();
+        for r#match in regex.find_iter(line).take(remaining_matches)
+        {
+            matches_in_line.push(r#match.range());
+        }
+        remaining_matches =
+            remaining_matches.saturating_sub(matches_in_line.len());
+
+        if !matches_in_line.is_empty()
+        {
+            // Sort ranges defensively to keep deterministic highlight order.
+            matches_in_line.sort_unstable_by_key(|span: &MatchSpan| span.start);
+            matches_in_line.shrink_to_fit();
+
+            results.push((
+                line_offset.saturating_add(relative_line_num),
+                matches_in_line,
+            ));
+        }
+    }
+
+    results
+}
+
+/// Limits the collected search matches to the given count.
+///
+/// Matches past the limit are dropped, along with lines left without any.
+///
+/// # Arguments
+///
+/// * `results` - The search matches per line, in line order
+/// * `max_matches` - The maximum number of matches to keep
+///
+/// # Returns
+///
+/// `true` if any matches were dropped.
+fn truncate_search_matches(
+    results: &mut Vec<(LineNumber, Vec<MatchSpan>)>,
+    max_matches: usize,
+) -> bool
+{
+    let mut remaining_matches = max_matches;
+
+    // First line with more matches than there is room left for
+    let Some(cut_index) = results
+        .iter()
+        .position(|(_, matches_in_line)| {
+            if matches_in_line.len() > remaining_matches
+            {
+                return true;
+            }
+            remaining_matches =
+                remaining_matches.saturating_sub(matches_in_line.len());
+            false
+        })
+    else
+    {
+        return false;
+    };
+
+    let mut kept_lines = cut_index;
+    if let Some((_, matches_in_line)) = results.get_mut(cut_index)
+    {
+        matches_in_line.truncate(remaining_matches);
+
+        // Lines whose matches were all dropped are no longer results
+        if !matches_in_line.is_empty()
+        {
+            kept_lines = cut_index.saturating_add(1);
+        }
+    }
+    results.truncate(kept_lines);
+
+    true
+}
+
+/// Determines whether search should run serially or in parallel.
+///
+/// # Arguments
+///
+/// * `total_lines` - The total number of lines in the document to search
+///   through
+///
+/// # Returns
+///
+/// * [`SearchStrategy::Serial`] if the document is small or if parallelism is
+///   not available
+/// * [`SearchStrategy::Parallel`] with the number of worker threads to use for
+///   larger documents
+fn determine_search_strategy(total_lines: usize) -> SearchStrategy
+{
+    if total_lines < MIN_LINES_FOR_PARALLEL_SEARCH
+    {
+        return SearchStrategy::Serial;
+    }
+
+    let Ok(available_workers) =
+        thread::available_parallelism().map(std::num::NonZeroUsize::get)
+    else
+    {
+        return SearchStrategy::Serial;
+    };
+
+    let line_limited_workers =
+        (total_lines / PARALLEL_SEARCH_MIN_LINES_PER_WORKER).max(1);
+
+    let worker_count = available_workers.min(line_limited_workers);
+
+    if worker_count <= 1
+    {
+        // 1 worker ain't making sense for parallelism, just do it serially.
+        SearchStrategy::Serial
+    }
+    else
+    {
+        SearchStrategy::Parallel { worker_count }
+    }
+}
+
+/// Compiles a regex for the given query, case sensitivity, and regex mode.
+///
+/// # Arguments
+///
+/// * `query` - The search query string
+/// * `is_case_sensitive` - Whether the search is case sensitive
+/// * `is_regex` - Whether the query is a regex
+///
+/// # Returns
+///
+/// A compiled `Regex` if the query is valid, or `None` if invalid.
+fn compile_regex(
+    query: &str,
+    is_case_sensitive: bool,
+    is_regex: bool,
+) -> Option<Regex>
+{
+    let pattern = if is_regex
+    {
+        Cow::Borrowed(query)
+    }
+    else
+    {
+        Cow::Owned(regex::escape(query))
+    };
+
+    let case_prefix = if is_case_sensitive { "" } else { "(?i)" };
+
+    Regex::new(&format!("{case_prefix}{pattern}")).ok()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::cell::Cell;
+    use std::convert::Infallible;
+    use std::io;
+
+    use anyhow::Context as _;
+    use ratatui::backend::{ClearType, TestBackend, WindowSize};
+    use ratatui::buffer::{Buffer, Cell as BufferCell};
+    use ratatui::layout::{Position, Size};
+
+    use super::*;
+    use crate::config::DEFAULT_MAX_SEARCH_MATCHES;
+    use crate::ui::CachedRfc;
+    use crate::ui::theme::ThemeName;
+
+    /// Creates an app for the given content without touching the terminal.
+    fn headless_app(content: &str, config: Config) -> App
+    {
+        App::without_terminal(
+            RfcNum::new(1),
+            DocumentText::from(Box::from(content)),
+            DocumentSource::Cache,
+            config,
+        )
+    }
+
+    /// Collects the text of each span in a line.
+    fn span_texts<'line>(line: &'line Line<'_>) -> Vec<&'line str>
+    {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_trimmed_line_keeps_match_offsets()
+    {
+        let line_str = "  foo bar   ";
+        // "bar" and a match lying in the trailing whitespace
+        let matches = [6..9, 10..11];
+
+        let line = App::build_line_with_search_and_title_highlights(
+            line_str.trim_end(),
+            &matches,
+            false,
+            &Theme::DARK,
+        );
+
+        assert_eq!(span_texts(&line), ["  foo ", "bar"]);
+        assert_eq!(line.spans[1].style, Theme::DARK.match_highlight);
+    }
+
+    #[test]
+    fn test_untrimmed_line_keeps_trailing_matches()
+    {
+        let line_str = "  foo bar   ";
+        let matches = [6..9, 10..11];
+
+        let line = App::build_line_with_search_and_title_highlights(
+            line_str,
+            &matches,
+            false,
+            &Theme::DARK,
+        );
+
+        assert_eq!(span_texts(&line), ["  foo ", "bar", " ", " ", " "]);
+    }
+
+    #[test]
+    fn test_search_stops_at_match_cap()
+    {
+        // Large enough to be searched in parallel
+        let content = "x x x x x x x x x x\n".repeat(2000);
+        let mut app = headless_app(&content, Config::default());
+
+        app.query_text.push('x');
+        app.perform_search();
+
+        let total_matches: usize = app
+            .query_matches
+            .values()
+            .map(Vec::len)
+            .sum();
+        assert_eq!(total_matches, DEFAULT_MAX_SEARCH_MATCHES);
+        assert!(
+            app.app_state
+                .contains(AppStateFlags::HAS_CAPPED_RESULTS)
+        );
+        assert_eq!(
+            app.build_search_info().as_deref(),
+            Some("M 1/1000 | 10000+ matches (refine query)")
+        );
+
+        // Navigation stays within the capped set
+        app.current_scroll_pos = 1500;
+        app.prev_search_result();
+        assert_eq!(app.current_scroll_pos, 999);
+    }
+
+    #[test]
+    fn test_cross_line_search()
+    {
+        let mut app = headless_app(
+            "   key words MUST\n   NOT be used\n   MUST NOT",
+            Config::default(),
+        );
+
+        app.query_text.push_str("must not");
+        app.perform_search();
+        assert_eq!(app.query_match_line_nums, [2]);
+
+        // The whitespace of the query is matched loosely as well
+        app.query_text = "must  not".to_owned();
+        app.toggle_cross_line_search();
+        app.perform_search();
+        assert_eq!(app.query_match_line_nums, [0, 1, 2]);
+        assert_eq!(
+            app.query_matches[&0],
+            vec![MatchSpan { start: 13, end: 17 }]
+        );
+        assert_eq!(app.query_matches[&1], vec![MatchSpan { start: 3, end: 6 }]);
+        assert_eq!(
+            app.query_matches[&2],
+            vec![MatchSpan { start: 3, end: 11 }]
+        );
+    }
+
+    #[test]
+    fn test_original_text_is_normalized_for_display()
+    {
+        let content = "\n  page 1\n\x0c\npage 2\n\n";
+        let app = headless_app(content, Config::default());
+        assert_eq!(&*app.rfc_content, content);
+
+        let config = Config {
+            keep_original_text: true,
+            ..Config::default()
+        };
+        let mut app = headless_app(content, config);
+        assert_eq!(&*app.rfc_content, "page 1\n\npage 2");
+
+        // The same text as served isn't a change
+        assert!(!app.reload_document(DocumentText::from(Box::from(content))));
+
+        app.config.keep_page_breaks = true;
+        assert!(app.reload_document(DocumentText::from(Box::from(content))));
+        assert_eq!(&*app.rfc_content, "page 1\n\x0c\npage 2");
+    }
+
+    #[test]
+    fn test_search_truncates_line_at_match_cap()
+    {
+        let config = Config {
+            max_search_matches: 5,
+            ..Config::default()
+        };
+        let mut app = headless_app("aaaa\naa\naaa", config);
+
+        app.query_text.push('a');
+        app.perform_search();
+
+        assert_eq!(app.query_match_line_nums, [0, 1]);
+        assert_eq!(app.query_matches[&1], vec![MatchSpan { start: 0, end: 1 }]);
+        assert!(
+            app.app_state
+                .contains(AppStateFlags::HAS_CAPPED_RESULTS)
+        );
+
+        // Exactly reaching the cap isn't capping
+        app.config.max_search_matches = 9;
+        app.perform_search();
+        assert!(
+            !app.app_state
+                .contains(AppStateFlags::HAS_CAPPED_RESULTS)
+        );
+    }
+
+    #[test]
+    fn test_cross_line_search_caps_by_match()
+    {
+        let config = Config {
+            max_search_matches: 2,
+            ..Config::default()
+        };
+        let mut app = headless_app("MUST\nNOT MUST\nNOT", config);
+        app.toggle_cross_line_search();
+
+        app.query_text.push_str("MUST NOT");
+        app.perform_search();
+
+        // Both wrapped matches keep their spans on either line
+        assert_eq!(app.query_match_line_nums, [0, 1, 2]);
+        assert_eq!(
+            app.query_matches[&1],
+            vec![
+                MatchSpan { start: 0, end: 3 },
+                MatchSpan { start: 4, end: 8 }
+            ]
+        );
+        assert_eq!(app.query_matches[&2], vec![MatchSpan { start: 0, end: 3 }]);
+        assert!(
+            !app.app_state
+                .contains(AppStateFlags::HAS_CAPPED_RESULTS)
+        );
+
+        app.config.max_search_matches = 1;
+        app.perform_search();
+        assert_eq!(app.query_match_line_nums, [0, 1]);
+        assert_eq!(app.query_matches[&1], vec![MatchSpan { start: 0, end: 3 }]);
+        assert!(
+            app.app_state
+                .contains(AppStateFlags::HAS_CAPPED_RESULTS)
+        );
+    }
+
+    #[test]
+    fn test_reveal_whitespace_keeps_styles()
+    {
+        let line = Line::from(vec![
+            Span::raw("a b\t"),
+            Span::styled("x\x0c", Theme::DARK.match_highlight),
+        ]);
+
+        let line = reveal_whitespace(line, Theme::DARK.whitespace);
+
+        assert_eq!(span_texts(&line), ["a", "·", "b", "→", "x", "␌"]);
+        assert_eq!(line.spans[1].style, Theme::DARK.whitespace);
+        assert_eq!(
+            line.spans[5].style,
+            Theme::DARK
+                .match_highlight
+                .patch(Theme::DARK.whitespace)
+        );
+    }
+
+    #[test]
+    fn test_jump_alignment()
+    {
+        let content = "line\n".repeat(100);
+        let mut app = headless_app(&content, Config::default());
+        app.viewport_height = 20;
+
+        app.jump_to_line(50);
+        assert_eq!(app.current_scroll_pos, 50);
+
+        app.config.jump_alignment = JumpAlignment::Center;
+        app.jump_to_line(50);
+        assert_eq!(app.current_scroll_pos, 40);
+        assert_eq!(app.current_line(), 50);
+        // Near the start there is nothing to center against
+        app.jump_to_line(3);
+        assert_eq!(app.current_scroll_pos, 0);
+        assert_eq!(app.current_line(), 3);
+
+        app.config.jump_alignment = JumpAlignment::ScrollOff(5);
+        app.jump_to_line(50);
+        assert_eq!(app.current_scroll_pos, 45);
+
+        // Scrolling away makes the top line current again
+        app.scroll_down(1);
+        assert_eq!(app.current_line(), 46);
+    }
+
+    #[test]
+    fn test_loading_document_clears_search()
+    {
+        let mut app = headless_app("alpha\nbeta alpha", Config::default());
+
+        app.query_text.push_str("alpha");
+        app.perform_search();
+        app.next_search_result();
+        assert!(app.has_search_results());
+
+        app.load_document(
+            RfcNum::new(2).expect("its non-zero"),
+            DocumentText::from(Box::from("gamma\nalpha")),
+            DocumentSource::Cache,
+        );
+
+        assert!(!app.has_search_results());
+        assert!(app.query_matches.is_empty());
+        assert_eq!(app.current_query_match_index, 0);
+        assert_eq!(app.current_scroll_pos, 0);
+        // No highlight from the previous document's matches is rendered
+        let text = app.build_text();
+        assert!(
+            text.lines
+                .iter()
+                .flat_map(|line| &line.spans)
+                .all(|span| span.style != app.theme.match_highlight)
+        );
+    }
+
+    #[test]
+    fn test_current_line_highlight_under_matches()
+    {
+        let config = Config {
+            highlight_current_line: true,
+            ..Config::default()
+        };
+        let mut app = headless_app("foo\nbar foo\nbaz", config);
+
+        app.query_text.push_str("foo");
+        app.perform_search();
+        app.scroll_down(1);
+
+        let text = app.build_text();
+        assert_eq!(text.lines[0].style, Style::default());
+        assert_eq!(text.lines[1].style, app.theme.current_line);
+        // The match keeps its own style, patched over the line style
+        assert_eq!(text.lines[1].spans[1].style, app.theme.match_highlight);
+
+        app.toggle_current_line_highlight();
+        assert_eq!(app.build_text().lines[1].style, Style::default());
+    }
+
+    #[test]
+    fn test_peek_keeps_document() -> anyhow::Result<()>
+    {
+        let mut app = headless_app("See RFC 2119.", Config::default());
+        let referenced = app
+            .current_line_reference()
+            .context("No reference found")?;
+
+        // A canceled fetch finishing late is ignored
+        let canceled_id = app.start_fetch(referenced, FetchPurpose::Open);
+        app.cancel_fetch();
+        let fetch_id = app.start_fetch(referenced, FetchPurpose::Peek);
+        assert_eq!(app.finish_fetch(canceled_id), None);
+        assert_eq!(app.mode, AppMode::Fetching);
+        assert_eq!(app.finish_fetch(fetch_id), Some(FetchPurpose::Peek));
+        app.show_peek(
+            referenced,
+            "Network Working Group\n\n    Key words for use in RFCs\n",
+        );
+
+        assert_eq!(app.mode, AppMode::Peek);
+        assert_eq!(
+            app.peek
+                .as_ref()
+                .and_then(|peek| peek.metadata.title.as_deref()),
+            Some("Key words for use in RFCs")
+        );
+        assert_eq!(app.close_peek(), Some(referenced));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.rfc_number.map(RfcNum::get), Some(1));
+
+        Ok(())
+    }
+
+    /// Renders the app to a buffer of the given size.
+    fn render_to_buffer(
+        app: &mut App,
+        width: u16,
+        height: u16,
+    ) -> anyhow::Result<Buffer>
+    {
+        let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+        terminal.draw(|frame| app.render(frame))?;
+
+        Ok(terminal.backend().buffer().clone())
+    }
+
+    /// Collects the symbols of a buffer row.
+    fn row_text(buffer: &Buffer, row: u16) -> String
+    {
+        (0..buffer.area.width)
+            .map(|column| buffer[(column, row)].symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_reflow_with_search_highlights() -> anyhow::Result<()>
+    {
+        let mut app =
+            headless_app("   Some prose\n   wrapped here.", Config::default());
+        app.toggle_reflow();
+
+        let buffer = render_to_buffer(&mut app, 100, 30)?;
+        assert!(
+            row_text(&buffer, 0).starts_with("   Some prose wrapped here.")
+        );
+        assert_eq!(
+            app.reflow_cache
+                .as_ref()
+                .map(|&(width, _)| width),
+            Some(100)
+        );
+
+        // The original lines keep the match highlighted
+        app.query_text = String::from("wrapped");
+        app.perform_search();
+        assert_eq!(app.current_scroll_pos, 1);
+        let buffer = render_to_buffer(&mut app, 100, 30)?;
+        assert!(row_text(&buffer, 0).starts_with("   wrapped here."));
+        assert_eq!(Some(buffer[(3, 0)].fg), app.theme.match_highlight.fg);
+
+        app.clear_search_matches();
+        render_to_buffer(&mut app, 100, 30)?;
+        assert!(app.reflow_cache.is_some());
+        app.load_document(
+            RfcNum::new(2).expect("its non-zero"),
+            DocumentText::from(Box::from("Other")),
+            DocumentSource::Cache,
+        );
+        assert!(app.reflow_cache.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_toc_overlays_narrow_content() -> anyhow::Result<()>
+    {
+        let content = "1. Introduction\n\n   Body text.\n";
+        let mut app = headless_app(content, Config::default());
+        app.toggle_toc();
+
+        // A quarter of the width for the ToC leaves less than a line of RFC
+        // text for the content
+        let buffer = render_to_buffer(&mut app, MIN_TERMINAL_WIDTH, 20)?;
+        assert!(app.is_toc_overlaid);
+        assert!(row_text(&buffer, 0).starts_with("Contents"));
+        assert!(!row_text(&buffer, 2).contains("Body text."));
+
+        let buffer = render_to_buffer(&mut app, 100, 20)?;
+        assert!(!app.is_toc_overlaid);
+        assert!(row_text(&buffer, 0).starts_with("Contents"));
+        assert!(row_text(&buffer, 2).contains("Body text."));
+
+        // Jumping from the overlay reveals the content
+        render_to_buffer(&mut app, MIN_TERMINAL_WIDTH, 20)?;
+        app.jump_to_toc_entry();
+        assert!(
+            !app.app_state
+                .contains(AppStateFlags::SHOULD_SHOW_TOC)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_jump_to_section_top()
+    {
+        let content = "1. Introduction\n\n   Body.\n\n2. Terminology\n\n   \
+                       More.\n\n   Even more.\n";
+        let mut app = headless_app(content, Config::default());
+        app.scroll_down(8);
+
+        assert!(app.jump_to_section_top());
+        assert_eq!(app.current_line(), 4);
+        // At the top already, the previous section is next
+        assert!(app.jump_to_section_top());
+        assert_eq!(app.current_line(), 0);
+        assert!(!app.jump_to_section_top());
+        assert_eq!(app.current_line(), 0);
+    }
+
+    #[test]
+    fn test_section_navigation_with_missing_heading()
+    {
+        // Section 2's heading is missing from the body
+        let content =
+            "Table of Contents\n\n   1.  Introduction ........ 2\n   2.  \
+             Terminology ......... 3\n   3.  Security ............ 4\n\n1.  \
+             Introduction\n\n   Body.\n\n3.  Security\n\n   More.\n";
+        let mut app = headless_app(content, Config::default());
+
+        let lines: Vec<LineNumber> = app
+            .rfc_toc_panel
+            .entries()
+            .iter()
+            .map(|entry| entry.line_number)
+            .collect();
+        assert_eq!(lines, [6, 6, 10]);
+
+        let text = app.build_text();
+        assert_eq!(text.lines[6].spans[0].style, app.theme.title_highlight);
+        assert_eq!(text.lines[10].spans[0].style, app.theme.title_highlight);
+
+        app.scroll_down(12);
+        assert!(app.jump_to_section_top());
+        assert_eq!(app.current_line(), 10);
+        assert!(app.jump_to_section_top());
+        assert_eq!(app.current_line(), 6);
+        assert!(!app.jump_to_section_top());
+        assert_eq!(app.current_line(), 6);
+    }
+
+    #[test]
+    fn test_rerun_last_search_in_new_document()
+    {
+        let mut app = headless_app("alpha\nbeta", Config::default());
+        assert!(!app.rerun_last_search());
+
+        app.query_text.push_str("beta");
+        app.perform_search();
+        app.load_document(
+            RfcNum::new(2).expect("its non-zero"),
+            DocumentText::from(Box::from("gamma\ndelta\nbeta")),
+            DocumentSource::Cache,
+        );
+        assert!(!app.has_search_results());
+
+        assert!(app.rerun_last_search());
+        assert_eq!(app.query_text, "beta");
+        assert_eq!(app.query_match_line_nums, [2]);
+    }
+
+    #[test]
+    fn test_centered_overlay_is_cleared() -> anyhow::Result<()>
+    {
+        let app = headless_app("", Config::default());
+        let mut terminal = Terminal::new(TestBackend::new(40, 10))?;
+        let mut overlay_area = Rect::default();
+
+        terminal.draw(|frame| {
+            let background =
+                Paragraph::new(vec![Line::from("x".repeat(40)); 10]);
+            frame.render_widget(background, frame.area());
+
+            overlay_area = app.render_centered_overlay(
+                frame,
+                Constraint::Percentage(50),
+                Constraint::Length(4),
+                Block::new(),
+                Text::default(),
+                Style::default(),
+            );
+        })?;
+
+        assert_eq!(overlay_area, Rect::new(10, 3, 20, 4));
+        let buffer = terminal.backend().buffer();
+        for row in 0..10
+        {
+            let text = row_text(buffer, row);
+            let expected = if (3..7).contains(&row)
+            {
+                format!(
+                    "{}{}{}",
+                    "x".repeat(10),
+                    " ".repeat(20),
+                    "x".repeat(10)
+                )
+            }
+            else
+            {
+                "x".repeat(40)
+            };
+            assert_eq!(text, expected, "row {row}");
         }
+
+        Ok(())
     }
 
-    /// Resets the search highlights.
-    pub fn reset_search_highlights(&mut self)
+    #[test]
+    fn test_search_box_stays_above_statusbar() -> anyhow::Result<()>
     {
-        self.query_text.clear();
-        self.query_match_line_nums.clear();
-        self.query_matches.clear();
-        self.current_query_match_index = 0;
-        self.app_state
-            .remove(AppStateFlags::HAS_NO_RESULTS);
+        let mut app = headless_app("alpha", Config::default());
+        app.enter_search_mode();
+        app.query_text.push_str("alpha");
+
+        let buffer = render_to_buffer(
+            &mut app,
+            MIN_TERMINAL_WIDTH,
+            MIN_TERMINAL_HEIGHT,
+        )?;
+        let last_row = MIN_TERMINAL_HEIGHT.saturating_sub(1);
+        assert!(
+            row_text(&buffer, last_row.saturating_sub(3)).contains("Search")
+        );
+        assert!(
+            row_text(&buffer, last_row.saturating_sub(2)).contains("/alpha")
+        );
+        assert!(!row_text(&buffer, last_row).contains('─'));
+
+        // Shorter main areas shrink the box instead of moving it past them
+        let main_area = Rect::new(0, 0, 80, 2);
+        assert_eq!(search_box_area(main_area), Rect::new(20, 0, 40, 2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reload_document_keeps_position()
+    {
+        let mut app = headless_app("one\ntwo\nthree\nfour", Config::default());
+        app.scroll_down(2);
+
+        assert!(!app.reload_document(DocumentText::from(Box::from(
+            "one\ntwo\nthree\nfour"
+        ))));
+        assert_eq!(app.rfc_source, DocumentSource::Network);
+
+        assert!(app.reload_document(DocumentText::from(Box::from(
+            "one\ntwo\nthree, revised\nfour"
+        ))));
+        assert_eq!(app.current_scroll_pos, 2);
+        assert!(app.rfc_content.contains("revised"));
+
+        // A shorter version clamps the position to its last line
+        assert!(app.reload_document(DocumentText::from(Box::from("one"))));
+        assert_eq!(app.current_scroll_pos, 0);
+    }
+
+    #[test]
+    fn test_help_scrolls_to_the_end() -> anyhow::Result<()>
+    {
+        /// The text of all the rows of the buffer.
+        fn screen_text(buffer: &Buffer) -> String
+        {
+            (0..buffer.area.height)
+                .map(|row| row_text(buffer, row))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+
+        let mut app = headless_app("alpha", Config::default());
+        app.toggle_help();
+
+        let screen = screen_text(&render_to_buffer(&mut app, 100, 30)?);
+        assert!(screen.contains("(more below)"));
+        assert!(!screen.contains("F2: Toggle the statusbar"));
+
+        for _ in 0..100
+        {
+            app.scroll_help_down();
+        }
+        let screen = screen_text(&render_to_buffer(&mut app, 100, 30)?);
+        assert!(screen.contains("F2: Toggle the statusbar"));
+        assert!(!screen.contains("(more below)"));
+
+        // Clamped to the end, so a single step goes back up
+        let end = app.help_scroll_pos;
+        app.scroll_help_up();
+        assert_eq!(app.help_scroll_pos, end.saturating_sub(1));
+
+        // Reopened at the top
+        app.toggle_help();
+        app.toggle_help();
+        assert_eq!(app.help_scroll_pos, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_legend_reserves_row() -> anyhow::Result<()>
+    {
+        let mut app = headless_app("alpha", Config::default());
+        let last_row = MIN_TERMINAL_HEIGHT.saturating_sub(1);
+
+        let buffer = render_to_buffer(
+            &mut app,
+            MIN_TERMINAL_WIDTH,
+            MIN_TERMINAL_HEIGHT,
+        )?;
+        assert!(
+            !row_text(&buffer, last_row.saturating_sub(1))
+                .contains("?: all keys")
+        );
+        let viewport_height = app.viewport_height;
+
+        app.toggle_legend();
+        let buffer = render_to_buffer(
+            &mut app,
+            MIN_TERMINAL_WIDTH,
+            MIN_TERMINAL_HEIGHT,
+        )?;
+        assert!(
+            row_text(&buffer, last_row.saturating_sub(1))
+                .contains("?: all keys")
+        );
+        assert!(row_text(&buffer, last_row).contains("?:help"));
+        assert_eq!(app.viewport_height, viewport_height.saturating_sub(1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hidden_statusbar_frees_row() -> anyhow::Result<()>
+    {
+        let content = (0..40)
+            .map(|line_num| format!("line {line_num}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut app = headless_app(&content, Config::default());
+        let last_row = MIN_TERMINAL_HEIGHT.saturating_sub(1);
+
+        let buffer = render_to_buffer(
+            &mut app,
+            MIN_TERMINAL_WIDTH,
+            MIN_TERMINAL_HEIGHT,
+        )?;
+        assert!(row_text(&buffer, last_row).contains("?:help"));
+        let viewport_height = app.viewport_height;
+
+        app.toggle_statusbar();
+        let buffer = render_to_buffer(
+            &mut app,
+            MIN_TERMINAL_WIDTH,
+            MIN_TERMINAL_HEIGHT,
+        )?;
+        assert_eq!(app.viewport_height, viewport_height.saturating_add(1));
+        assert_eq!(app.viewport_height, usize::from(MIN_TERMINAL_HEIGHT));
+        assert!(row_text(&buffer, last_row).contains("line 14"));
+
+        // The search box is still shown within the content
+        app.mode = AppMode::Search;
+        let buffer = render_to_buffer(
+            &mut app,
+            MIN_TERMINAL_WIDTH,
+            MIN_TERMINAL_HEIGHT,
+        )?;
+        assert!(
+            (0..MIN_TERMINAL_HEIGHT)
+                .any(|row| row_text(&buffer, row).contains("Search"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_narrowed_search_keeps_index_in_range()
+    {
+        let content = (0..40)
+            .map(|line_num| format!("alpha {line_num}"))
+            .chain(["beta".to_owned()])
+            .chain((0..40).map(|line_num| format!("alpha {line_num}")))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut app = headless_app(&content, Config::default());
+
+        app.query_text.push_str("alpha");
+        app.perform_search();
+        app.jump_to_line(70);
+        app.next_search_result();
+        assert_eq!(app.current_query_match_index, 70);
+
+        // The only match is above the current line
+        app.query_text = "beta".to_owned();
+        app.perform_search();
+        assert_eq!(app.current_query_match_index, 0);
+        assert_eq!(app.current_line(), 40);
+        assert_eq!(app.build_search_info().as_deref(), Some("M 1/1"));
+
+        // No matches at all resets the index
+        app.query_text = "gamma".to_owned();
+        app.perform_search();
+        assert_eq!(app.current_query_match_index, 0);
+        assert_eq!(app.build_search_info(), None);
+    }
+
+    /// Renders the app and collects the text of its statusbar.
+    fn statusbar_text(app: &mut App) -> anyhow::Result<String>
+    {
+        let buffer = render_to_buffer(app, 120, MIN_TERMINAL_HEIGHT)?;
+
+        Ok(row_text(&buffer, MIN_TERMINAL_HEIGHT.saturating_sub(1)))
+    }
+
+    #[test]
+    fn test_statusbar_per_mode() -> anyhow::Result<()>
+    {
+        let content = "1. Introduction\n\n   The alpha text.\n".to_owned() +
+            &"filler\n".repeat(6) +
+            "   More alpha.";
+        let mut app = headless_app(content.as_str(), Config::default());
+
+        let statusbar = statusbar_text(&mut app)?;
+        assert!(statusbar.starts_with("RFC 1 | L 1/10 (0%) "));
+        assert!(statusbar.contains(" NORMAL "));
+        assert!(
+            statusbar
+                .trim_end()
+                .ends_with("up/down:scroll  /:search  ?:help  q:quit")
+        );
+
+        app.mode = AppMode::Search;
+        app.query_text.push_str("alpha");
+        let statusbar = statusbar_text(&mut app)?;
+        assert!(statusbar.starts_with("RFC 1 | L 1/10 (0%) "));
+        assert!(statusbar.contains(" SEARCH | C:☐ R:☐ "));
+        assert!(
+            statusbar
+                .trim_end()
+                .ends_with("Enter:search  Esc:cancel")
+        );
+
+        // The current match and the match hints show once searched
+        app.mode = AppMode::Normal;
+        app.perform_search();
+        let statusbar = statusbar_text(&mut app)?;
+        assert!(statusbar.starts_with("RFC 1 | L 3/10 (22%) | M 1/2 "));
+        assert!(statusbar.contains(" NORMAL "));
+        assert!(
+            statusbar
+                .trim_end()
+                .ends_with("n/N:next/prev  Esc:clear")
+        );
+
+        app.toggle_toc();
+        let statusbar = statusbar_text(&mut app)?;
+        assert!(statusbar.contains(" NORMAL (ToC) "));
+        assert!(
+            statusbar
+                .trim_end()
+                .ends_with("t:toggle ToC  w/s:nav  Enter:jump  q:quit")
+        );
+
+        // No results leaves the match count out
+        app.toggle_toc();
+        app.query_text = "gamma".to_owned();
+        app.perform_search();
+        let statusbar = statusbar_text(&mut app)?;
+        assert!(statusbar.starts_with("RFC 1 | L 3/10 (22%) "));
+        assert!(!statusbar.contains(" M "));
+        assert!(
+            statusbar
+                .trim_end()
+                .ends_with("up/down:scroll  /:search  ?:help  q:quit")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_draft_without_rfc_number() -> anyhow::Result<()>
+    {
+        let mut app = App::without_terminal(
+            None,
+            DocumentText::from(Box::from("Draft text")),
+            DocumentSource::Url,
+            Config::default(),
+        );
+        assert!(app.has_document());
+        assert!(!App::default().has_document());
+
+        let statusbar = statusbar_text(&mut app)?;
+        assert!(!statusbar.contains("RFC"));
+
+        app.load_document(
+            RfcNum::new(2).expect("its non-zero"),
+            DocumentText::from(Box::from("RFC text")),
+            DocumentSource::Cache,
+        );
+        assert!(statusbar_text(&mut app)?.contains("RFC 2"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_category_in_statusbar() -> anyhow::Result<()>
+    {
+        let content = [
+            "Internet Engineering Task Force (IETF)             E. Rescorla",
+            "Category: Informational                            August 2018",
+            "",
+            "            Example Document",
+        ]
+        .join("\n");
+        let mut config = Config::default();
+        config.statusbar.middle = vec![StatusField::Category];
+        let mut app = headless_app(&content, config);
+
+        let buffer = render_to_buffer(
+            &mut app,
+            MIN_TERMINAL_WIDTH,
+            MIN_TERMINAL_HEIGHT,
+        )?;
+        let last_row = MIN_TERMINAL_HEIGHT.saturating_sub(1);
+        let row = row_text(&buffer, last_row);
+        let column = row
+            .find("Informational")
+            .expect("category is shown");
+
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "Column fits in u16"
+        )]
+        let cell = &buffer[(column as u16, last_row)];
+        assert_eq!(
+            cell.bg,
+            app.theme
+                .category
+                .informational
+                .bg
+                .unwrap_or_default()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_page_navigation()
+    {
+        let content =
+            ["page 1", "[Page 1]", "\x0c", "page 2", "\x0cpage 3", "end"]
+                .join("\n");
+        let mut app = headless_app(&content, Config::default());
+        assert_eq!(app.rfc_page_starts, [3, 4]);
+        assert_eq!(app.current_page(), Some((1, 3)));
+
+        assert!(app.next_page());
+        assert_eq!(app.current_line(), 3);
+        assert!(app.next_page());
+        assert_eq!(app.current_line(), 4);
+        assert!(!app.next_page());
+
+        assert!(app.prev_page());
+        assert_eq!(app.current_line(), 3);
+        assert!(app.prev_page());
+        assert_eq!(app.current_line(), 0);
+        assert!(!app.prev_page());
+
+        // Footers mark the pages of RFCs without form feeds
+        let content = [
+            "Introduction",
+            "Rescorla                 Standards Track               [Page 1]",
+            "",
+            "RFC 8446                    TLS                     August 2018",
+            "Definitions",
+            "Rescorla                 Standards Track               [Page 2]",
+        ]
+        .join("\n");
+        let mut footer_app = headless_app(&content, Config::default());
+        assert_eq!(footer_app.rfc_page_starts, [3]);
+        assert!(footer_app.next_page());
+        assert_eq!(
+            footer_app
+                .build_statusbar_field(StatusField::Page)
+                .as_deref(),
+            Some("Page 2/2")
+        );
+        assert!(!is_page_footer("See [Page 12] of the document."));
+
+        // Form feeds aren't rendered as is
+        let text = app.build_text();
+        assert_eq!(text.lines[4].spans[0].content, " page 3");
+    }
+
+    #[test]
+    fn test_toc_position() -> anyhow::Result<()>
+    {
+        let content = "1. Introduction\n\nBody text.\n";
+
+        for toc_position in [TocPosition::Left, TocPosition::Right]
+        {
+            let config = Config {
+                toc_position,
+                ..Config::default()
+            };
+            let mut app = headless_app(content, config);
+            app.toggle_toc();
+
+            let buffer = render_to_buffer(&mut app, 120, 20)?;
+            let row = row_text(&buffer, 2);
+            let toc_column = row_text(&buffer, 0)
+                .find("Contents")
+                .expect("ToC is shown");
+            let body_column = row
+                .find("Body text.")
+                .expect("body is shown");
+            let border_column = row.find('│').expect("border is shown");
+
+            // The border sits between the ToC and the content
+            match toc_position
+            {
+                TocPosition::Left =>
+                {
+                    assert_eq!(toc_column, 0);
+                    assert!(border_column < body_column);
+                },
+                TocPosition::Right =>
+                {
+                    assert_eq!(body_column, 0);
+                    assert!(border_column < toc_column);
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_current_line_without_clipboard()
+    {
+        let app = headless_app("first\nsecond", Config::default());
+
+        // Headless apps have no terminal to copy through
+        assert!(!app.copy_current_line());
+    }
+
+    #[test]
+    fn test_canceled_search_preview_restores_position()
+    {
+        let content =
+            ["alpha", "beta", "gamma", "delta", "beta again"].join("\n");
+        let config = Config {
+            search_preview: true,
+            ..Config::default()
+        };
+        let mut app = headless_app(&content, config);
+        app.current_scroll_pos = 2;
+
+        app.enter_search_mode();
+        for ch in "bet".chars()
+        {
+            app.add_search_char(ch);
+        }
+        // The nearest match after the start, not the first one
+        assert_eq!(app.current_line(), 4);
+
+        app.add_search_char('x');
+        assert_eq!(app.current_scroll_pos, 2);
+        app.remove_search_char();
+        assert_eq!(app.current_line(), 4);
+
+        app.cancel_search();
+        assert_eq!(app.current_scroll_pos, 2);
+        assert_eq!(app.current_line(), 2);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_title_highlight_toggle()
+    {
+        let mut app = headless_app(
+            "1. Introduction\nfoo\n2. More foo",
+            Config::default(),
+        );
+        app.query_text.push_str("foo");
+        app.perform_search();
+
+        let text = app.build_text();
+        assert_eq!(text.lines[0].spans[0].style, app.theme.title_highlight);
+        assert_eq!(text.lines[2].spans[0].style, app.theme.title_highlight);
+        assert_eq!(text.lines[2].spans[1].style, app.theme.match_highlight);
+
+        app.toggle_title_highlight();
+
+        // Titles are plain, but matches in them still highlight
+        let text = app.build_text();
+        assert_eq!(text.lines[0].spans[0].style, Style::default());
+        assert_eq!(text.lines[2].spans[0].style, Style::default());
+        assert_eq!(text.lines[2].spans[1].style, app.theme.match_highlight);
+    }
+
+    #[test]
+    fn test_cycle_match_color()
+    {
+        let config = Config {
+            theme: ThemeName::Dark,
+            ..Config::default()
+        };
+        let mut app = headless_app("foo\nbar", config);
+        app.query_text.push_str("foo");
+        app.perform_search();
+
+        // Starts after the theme's own yellow
+        app.cycle_match_color();
+        assert_eq!(app.config.match_color, Some(ConfigColor(Color::Magenta)));
+        let text = app.build_text();
+        assert_eq!(
+            text.lines[0].spans[0].style,
+            Theme::DARK
+                .match_highlight
+                .fg(Color::Magenta)
+        );
+
+        for _ in 1..MATCH_COLORS.len()
+        {
+            app.cycle_match_color();
+        }
+        assert_eq!(app.config.match_color, Some(ConfigColor(Color::Yellow)));
+
+        // The persisted color applies to the next start
+        let config = Config {
+            match_color: Some(ConfigColor(Color::Cyan)),
+            ..app.config
+        };
+        assert_eq!(
+            Theme::from_config(&config)
+                .match_highlight
+                .fg,
+            Some(Color::Cyan)
+        );
+    }
+
+    #[test]
+    fn test_search_matches_text()
+    {
+        let mut app = headless_app(
+            "MUST here\nnothing\n  also MUST  \nMUST NOT",
+            Config::default(),
+        );
+        assert_eq!(app.search_matches_text(), None);
+
+        app.query_text.push_str("MUST");
+        app.perform_search();
+
+        assert_eq!(
+            app.search_matches_text().as_deref(),
+            Some("1: MUST here\n3:   also MUST\n4: MUST NOT")
+        );
+    }
+
+    #[test]
+    fn test_swapping_documents_rederives_state()
+    {
+        let first = [
+            "RFC 1                                                    Example",
+            "",
+            "1. Introduction",
+            "",
+            "   Text.                                               [Page 1]",
+            "\x0c",
+            "2. Details",
+        ]
+        .join("\n");
+        let second = "Short\ndocument";
+        let mut app = headless_app(&first, Config::default());
+        let first_state = (
+            app.rfc_line_number,
+            app.rfc_word_count,
+            app.rfc_toc_panel.entries().len(),
+            app.rfc_page_starts.clone(),
+        );
+        assert_ne!(first_state.2, 0);
+        assert!(!first_state.3.is_empty());
+        app.current_scroll_pos = 5;
+        app.pending_motion = Some(']');
+
+        app.load_document(
+            RfcNum::new(2).expect("its non-zero"),
+            DocumentText::from(Box::from(second)),
+            DocumentSource::Network,
+        );
+
+        assert_eq!(app.rfc_number, RfcNum::new(2));
+        assert_eq!(app.rfc_source, DocumentSource::Network);
+        assert_eq!(app.rfc_line_number, 2);
+        assert_eq!(app.rfc_word_count, 2);
+        assert!(app.rfc_toc_panel.entries().is_empty());
+        assert!(app.rfc_page_starts.is_empty());
+        assert_eq!(app.current_scroll_pos, 0);
+        assert_eq!(app.pending_motion, None);
+
+        app.load_document(
+            RfcNum::new(1).expect("its non-zero"),
+            DocumentText::from(Box::from(first.as_str())),
+            DocumentSource::Cache,
+        );
+
+        assert_eq!(
+            (
+                app.rfc_line_number,
+                app.rfc_word_count,
+                app.rfc_toc_panel.entries().len(),
+                app.rfc_page_starts.clone(),
+            ),
+            first_state
+        );
+    }
+
+    #[test]
+    fn test_end_markers() -> anyhow::Result<()>
+    {
+        let content = "first\nsecond\nlast";
+        let mut app = headless_app(content, Config::default());
+
+        let buffer = render_to_buffer(&mut app, MIN_TERMINAL_WIDTH, 20)?;
+        assert!(row_text(&buffer, 3).trim().is_empty());
+
+        let config = Config {
+            show_end_markers: true,
+            ..Config::default()
+        };
+        let mut app = headless_app(content, config);
+        app.scroll_down(app.rfc_line_number);
+
+        let buffer = render_to_buffer(&mut app, MIN_TERMINAL_WIDTH, 20)?;
+        assert!(row_text(&buffer, 0).starts_with("last"));
+        assert_eq!(row_text(&buffer, 1).trim_end(), END_MARKER);
+        assert_eq!(buffer[(0, 1)].fg, app.theme.end_marker.fg.expect("set"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_standard_sections()
+    {
+        let content = "\
+Table of Contents
+
+   1.  Introduction ................................................    2
+   2.  Security Considerations .....................................    3
+   3.  References ..................................................    4
+     3.1.  Normative References ....................................    4
+   4.  Acknowledgments .............................................    5
+
+1.  Introduction
+
+   Hello.
+
+2.  Security Considerations
+
+   None.
+
+3.  References
+
+3.1.  Normative References
+
+   [RFC2119]
+
+4.  Acknowledgments
+
+   Thanks.
+";
+        let mut app = headless_app(content, Config::default());
+        let lines: Vec<_> = app
+            .standard_sections()
+            .into_iter()
+            .map(|(_, line_num)| line_num)
+            .collect();
+        assert_eq!(lines, [Some(12), None, Some(16), Some(22)]);
+
+        app.open_sections_menu();
+        assert_eq!(app.mode, AppMode::Sections);
+        // Missing sections keep the menu open
+        assert!(!app.jump_to_standard_section(1));
+        assert_eq!(app.mode, AppMode::Sections);
+
+        assert!(app.jump_to_standard_section(2));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.current_scroll_pos, 16);
+
+        let mut app = headless_app("No contents here", Config::default());
+        app.open_sections_menu();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.message.is_some());
+    }
+
+    /// A test backend whose size queries fail on demand.
+    struct FlakyBackend
+    {
+        /// The backend doing the actual work.
+        inner: TestBackend,
+        /// Whether the size queries fail.
+        fails: Cell<bool>,
+    }
+
+    /// Converts the never occurring errors of the test backend.
+    fn infallible<T>(result: Result<T, Infallible>) -> io::Result<T>
+    {
+        result.map_err(|never| match never {})
+    }
+
+    impl Backend for FlakyBackend
+    {
+        type Error = io::Error;
+
+        fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+        where
+            I: Iterator<Item = (u16, u16, &'a BufferCell)>,
+        {
+            infallible(self.inner.draw(content))
+        }
+
+        fn hide_cursor(&mut self) -> io::Result<()>
+        {
+            infallible(self.inner.hide_cursor())
+        }
+
+        fn show_cursor(&mut self) -> io::Result<()>
+        {
+            infallible(self.inner.show_cursor())
+        }
+
+        fn get_cursor_position(&mut self) -> io::Result<Position>
+        {
+            infallible(self.inner.get_cursor_position())
+        }
+
+        fn set_cursor_position<P: Into<Position>>(
+            &mut self,
+            position: P,
+        ) -> io::Result<()>
+        {
+            infallible(self.inner.set_cursor_position(position))
+        }
+
+        fn clear(&mut self) -> io::Result<()>
+        {
+            infallible(self.inner.clear())
+        }
+
+        fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()>
+        {
+            infallible(self.inner.clear_region(clear_type))
+        }
+
+        fn size(&self) -> io::Result<Size>
+        {
+            if self.fails.get()
+            {
+                return Err(io::Error::other("terminal went away"));
+            }
+
+            infallible(self.inner.size())
+        }
+
+        fn window_size(&mut self) -> io::Result<WindowSize>
+        {
+            infallible(self.inner.window_size())
+        }
+
+        fn flush(&mut self) -> io::Result<()>
+        {
+            infallible(self.inner.flush())
+        }
+    }
+
+    #[test]
+    fn test_page_height_survives_failed_size_query() -> anyhow::Result<()>
+    {
+        let mut app = headless_app("foo\nbar", Config::default());
+        let mut terminal = Terminal::new(FlakyBackend {
+            inner: TestBackend::new(MIN_TERMINAL_WIDTH, 30),
+            fails: Cell::new(false),
+        })?;
+        terminal.draw(|frame| app.render(frame))?;
+        assert_eq!(app.page_height(&terminal), 30);
+
+        terminal
+            .backend_mut()
+            .inner
+            .resize(MIN_TERMINAL_WIDTH, 40);
+        terminal.backend().fails.set(true);
+        assert_eq!(app.page_height(&terminal), 30);
+
+        terminal.backend().fails.set(false);
+        assert_eq!(app.page_height(&terminal), 40);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_match_percent()
+    {
+        let content = "foo\n\n\nfoo\n\n\n\n\n\nfoo\nbar";
+        let mut app = headless_app(content, Config::default());
+        app.query_text.push_str("foo");
+        app.perform_search();
+        app.next_search_result();
+        assert_eq!(app.build_search_info().as_deref(), Some("M 2/3"));
+
+        app.config.match_percent = MatchPercent::Matches;
+        assert_eq!(app.build_search_info().as_deref(), Some("M 2/3 (66%)"));
+
+        app.config.match_percent = MatchPercent::Document;
+        assert_eq!(app.build_search_info().as_deref(), Some("M 2/3 (30%)"));
     }
-}
 
-impl Default for App
-{
-    fn default() -> Self
+    #[test]
+    fn test_reference_list()
     {
-        /// Initial capacities for common collections.
-        const QUERY_TEXT_INITIAL_CAPACITY: usize = 20;
-        const QUERY_RESULTS_INITIAL_CAPACITY: usize = 50;
+        let content = "\
+Table of Contents
 
-        let guard =
-            TerminalGuard::new().expect("Failed to create terminal guard");
+   1.  Introduction ................................................    2
+   2.  References ..................................................    3
+     2.1.  Normative References ....................................    3
+   Appendix A.  Example ............................................    4
 
-        Self {
-            rfc_content: Box::from(""),
-            rfc_number: NonZeroU16::new(1).expect("its non-zero"),
-            rfc_toc_panel: TocPanel::default(),
-            rfc_line_number: 0,
-            current_scroll_pos: 0,
-            mode: AppMode::Normal,
-            app_state: AppStateFlags::default(),
-            guard,
-            query_text: String::with_capacity(QUERY_TEXT_INITIAL_CAPACITY),
-            query_cursor_pos: 0,
-            query_match_line_nums: Vec::with_capacity(
-                QUERY_RESULTS_INITIAL_CAPACITY,
-            ),
-            current_query_match_index: 0,
-            query_matches: HashMap::with_capacity(
-                QUERY_RESULTS_INITIAL_CAPACITY,
-            ),
-        }
-    }
-}
+1.  Introduction
 
-/// Creates a centered rectangle inside the given area.
-///
-/// # Arguments
-///
-/// * `area` - The parent area
-/// * `horizontal` - The horizontal constraint
-/// * `vertical` - The vertical constraint
-///
-/// # Returns
-///
-/// A new rectangle positioned in the center of the parent.
-fn centered_rect(
-    area: Rect,
-    horizontal: Constraint,
-    vertical: Constraint,
-) -> Rect
-{
-    let [area] = Layout::horizontal([horizontal])
-        .flex(Flex::Center)
-        .areas(area);
-    let [area] = Layout::vertical([vertical])
-        .flex(Flex::Center)
-        .areas(area);
-    area
-}
+   See [RFC2119] and [TLS13].
 
-/// Search execution strategy for collecting query matches.
-#[derive(Debug, Clone, Copy)]
-enum SearchStrategy
-{
-    /// Process search linearly on a single thread.
-    Serial,
-    /// Process search using multiple workers.
-    Parallel
-    {
-        /// Number of worker threads to spawn.
-        worker_count: usize,
-    },
-}
+2.  References
 
-/// Collects all search matches for the given content.
-///
-/// Uses bounded parallelism for larger documents and falls back to serial
-/// processing for small documents or if a worker panics.
-///
-/// # Arguments
-///
-/// * `regex` - The regex to search with
-/// * `content` - The content to search in
-///
-/// # Returns
-///
-/// An array of 2-tuples, where each tuple contains a line number and a vector
-/// of match spans for that line.
-fn collect_search_matches(
-    regex: &Regex,
-    content: &str,
-) -> Vec<(LineNumber, Vec<MatchSpan>)>
-{
-    let lines: Vec<&str> = content.lines().collect();
+2.1.  Normative References
 
-    let worker_count = match determine_search_strategy(lines.len())
-    {
-        SearchStrategy::Serial =>
-        {
-            return collect_search_matches_serial(regex, &lines, 0);
-        },
-        SearchStrategy::Parallel { worker_count } => worker_count,
-    };
+   [RFC2119]  Bradner, S., \"Key words\", BCP 14, RFC 2119.
 
-    // Assign each worker a contiguous chunk of lines.
-    let chunk_size = lines.len().div_ceil(worker_count);
+   [TLS13]    Rescorla, E., \"TLS 1.3\", RFC 8446.
 
-    let parallel_result: Option<Vec<(LineNumber, Vec<MatchSpan>)>> =
-        thread::scope(|scope| {
-            let mut handles = Vec::with_capacity(worker_count);
+Appendix A.  Example
 
-            for (chunk_index, chunk) in lines.chunks(chunk_size).enumerate()
-            {
-                let line_offset = chunk_index.saturating_mul(chunk_size);
-                handles.push(scope.spawn(move || {
-                    collect_search_matches_serial(regex, chunk, line_offset)
-                }));
-            }
+   [RFC1234]  Not a reference.
+";
+        let mut app = headless_app(content, Config::default());
+        app.open_reference_list();
+        assert_eq!(app.mode, AppMode::References);
+        assert_eq!(app.current_scroll_pos, 11);
 
-            let mut all_matches: Vec<(LineNumber, Vec<MatchSpan>)> =
-                Vec::with_capacity(handles.len());
+        let reference_list = app
+            .reference_list
+            .as_mut()
+            .expect("references are listed");
+        reference_list.next(5);
+        let selected = reference_list
+            .selected()
+            .expect("an entry is selected");
+        assert_eq!(&*selected.label, "TLS13");
+        assert_eq!(selected.rfc_number.map(RfcNum::get), Some(8446));
 
-            for handle in handles
-            {
-                match handle.join()
-                {
-                    Ok(mut chunk_matches) =>
-                    {
-                        all_matches.append(&mut chunk_matches);
-                    },
-                    Err(_) => return None,
-                }
-            }
+        app.close_reference_list();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.reference_list.is_none());
 
-            Some(all_matches)
-        });
+        let mut app = headless_app("No contents here", Config::default());
+        app.open_reference_list();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.message.is_some());
+    }
 
-    parallel_result
-        // Fallback to serial processing if any worker panicked.
-        .unwrap_or_else(|| collect_search_matches_serial(regex, &lines, 0))
-}
+    #[test]
+    fn test_cache_browser_loads_rfc() -> anyhow::Result<()>
+    {
+        let mut app = headless_app("first\nmatch\nmatch", Config::default());
+        app.current_scroll_pos = 2;
+        app.query_text = String::from("match");
+        app.perform_search();
+        assert!(app.has_search_results());
+
+        app.open_cache_browser(CacheBrowser::new(Vec::new()));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.message.is_some());
+
+        let number = |number| RfcNum::new(number).expect("its non-zero");
+        app.open_cache_browser(CacheBrowser::new(vec![
+            CachedRfc {
+                number: number(1),
+                title: None,
+                is_title_loaded: false,
+                size: 10,
+            },
+            CachedRfc {
+                number: number(2119),
+                title: None,
+                is_title_loaded: false,
+                size: 2048,
+            },
+        ]));
+        assert_eq!(app.mode, AppMode::CacheBrowser);
+
+        let cache_browser = app
+            .cache_browser
+            .as_mut()
+            .expect("the cache is listed");
+        cache_browser.set_title(number(1), Some(Box::from("First")));
+        cache_browser.set_title(number(2119), None);
+
+        let buffer = render_to_buffer(&mut app, 100, 30)?;
+        let has_row =
+            |text| (0..30).any(|row| row_text(&buffer, row).contains(text));
+        assert!(has_row("1      1 KiB  First"));
+        assert!(has_row("2119      2 KiB  (no title)"));
+
+        let cache_browser = app
+            .cache_browser
+            .as_mut()
+            .expect("the cache is listed");
+        cache_browser.next(5);
+        let selected = cache_browser
+            .selected()
+            .expect("an RFC is selected");
+        assert_eq!(selected.get(), 2119);
+        app.close_cache_browser();
+
+        app.load_document(
+            selected,
+            DocumentText::from(Box::from("other\ncontent")),
+            DocumentSource::Cache,
+        );
+        assert_eq!(app.rfc_number, Some(selected));
+        assert_eq!(app.rfc_line_number, 2);
+        assert_eq!(app.current_scroll_pos, 0);
+        assert!(!app.has_search_results());
+        assert_eq!(app.mode, AppMode::Normal);
 
-/// Collects search matches line-by-line in a serial pass.
-///
-/// # Arguments
-///
-/// * `regex` - The regex to search with
-/// * `lines` - The lines to search through
-/// * `line_offset` - The line number offset to apply to the results (used for
-///   parallel chunks)
-///
-/// # Returns
-///
-/// An array of 2-tuples, where each tuple contains a line number and a vector
-/// of match spans for that line.
-fn collect_search_matches_serial(
-    regex: &Regex,
-    lines: &[&str],
-    line_offset: LineNumber,
-) -> Vec<(LineNumber, Vec<MatchSpan>)>
-{
-    let mut results = Vec::new();
+        Ok(())
+    }
 
-    for (relative_line_num, line) in lines.iter().enumerate()
+    #[test]
+    fn test_auto_toc_for_long_documents()
     {
-        let mut matches_in_line: Vec<MatchSpan> = Vec::new();
-        for r#match in regex.find_iter(line)
-        {
-            matches_in_line.push(r#match.range());
-        }
+        let content =
+            "1. Introduction\n\n   Text.\n\n2. Terminology\n\n   Text.\n";
+        let is_toc_shown = |app: &App| {
+            app.app_state
+                .contains(AppStateFlags::SHOULD_SHOW_TOC)
+        };
 
-        if !matches_in_line.is_empty()
-        {
-            // Sort ranges defensively to keep deterministic highlight order.
-            matches_in_line.sort_unstable_by_key(|span: &MatchSpan| span.start);
-            matches_in_line.shrink_to_fit();
+        assert!(!is_toc_shown(&headless_app(content, Config::default())));
 
-            results.push((
-                line_offset.saturating_add(relative_line_num),
-                matches_in_line,
-            ));
-        }
+        let config = Config {
+            auto_toc_min_entries: Some(1),
+            ..Config::default()
+        };
+        let mut app = headless_app(content, config.clone());
+        assert!(is_toc_shown(&app));
+
+        // Not opened again for documents with fewer entries
+        app.toggle_toc();
+        app.load_document(
+            RfcNum::new(2).expect("its non-zero"),
+            DocumentText::from(Box::from("1. Introduction\n")),
+            DocumentSource::Cache,
+        );
+        assert!(!is_toc_shown(&app));
+
+        let config = Config {
+            auto_toc_min_entries: Some(2),
+            ..config
+        };
+        assert!(!is_toc_shown(&headless_app(content, config)));
     }
 
-    results
-}
+    #[test]
+    fn test_restore_scroll_pos()
+    {
+        let mut app = headless_app("one\ntwo\nthree", Config::default());
 
-/// Determines whether search should run serially or in parallel.
-///
-/// # Arguments
-///
-/// * `total_lines` - The total number of lines in the document to search
-///   through
-///
-/// # Returns
-///
-/// * [`SearchStrategy::Serial`] if the document is small or if parallelism is
-///   not available
-/// * [`SearchStrategy::Parallel`] with the number of worker threads to use for
-///   larger documents
-fn determine_search_strategy(total_lines: usize) -> SearchStrategy
-{
-    if total_lines < MIN_LINES_FOR_PARALLEL_SEARCH
+        app.restore_scroll_pos(1);
+        assert_eq!(app.current_scroll_pos, 1);
+
+        app.restore_scroll_pos(500);
+        assert_eq!(app.current_scroll_pos, 2);
+    }
+
+    #[test]
+    fn test_bookmarks() -> anyhow::Result<()>
     {
-        return SearchStrategy::Serial;
+        let content = "line\n".repeat(30);
+        let mut app = headless_app(&content, Config::default());
+        assert!(!app.next_bookmark());
+
+        app.current_scroll_pos = 20;
+        assert!(app.toggle_bookmark());
+        app.current_scroll_pos = 5;
+        assert!(app.toggle_bookmark());
+        assert_eq!(app.bookmarks, [5, 20]);
+        assert!(app.is_bookmarked());
+        assert!(statusbar_text(&mut app)?.starts_with("RFC 1 | L 6/30 * "));
+
+        app.current_scroll_pos = 0;
+        assert!(app.next_bookmark());
+        assert_eq!(app.current_scroll_pos, 5);
+        assert!(app.next_bookmark());
+        assert_eq!(app.current_scroll_pos, 20);
+        assert!(!app.next_bookmark());
+        assert!(app.prev_bookmark());
+        assert_eq!(app.current_scroll_pos, 5);
+        assert!(!app.prev_bookmark());
+
+        assert!(!app.toggle_bookmark());
+        assert_eq!(app.bookmarks, [20]);
+        assert!(!app.is_bookmarked());
+
+        // Stored ones are sorted, deduplicated and kept within the document
+        app.set_bookmarks(vec![12, 3, 12, 30]);
+        assert_eq!(app.bookmarks, [3, 12]);
+
+        Ok(())
     }
 
-    let Ok(available_workers) =
-        thread::available_parallelism().map(std::num::NonZeroUsize::get)
-    else
+    #[test]
+    fn test_autoscroll()
     {
-        return SearchStrategy::Serial;
-    };
+        let mut app = headless_app(&"line\n".repeat(10), Config::default());
+        app.viewport_height = 4;
+        let start = Instant::now();
+        let after = |millis| start + Duration::from_millis(millis);
+
+        app.tick_autoscroll(after(5000));
+        assert_eq!(app.current_scroll_pos, 0);
+
+        app.toggle_autoscroll(start);
+        app.tick_autoscroll(after(200));
+        assert_eq!(app.current_scroll_pos, 0);
+        app.tick_autoscroll(after(1000));
+        assert_eq!(app.current_scroll_pos, 1);
+        app.tick_autoscroll(after(3200));
+        assert_eq!(app.current_scroll_pos, 3);
+
+        // Paused while reading the help
+        app.mode = AppMode::Help;
+        app.tick_autoscroll(after(9000));
+        app.mode = AppMode::Normal;
+        app.tick_autoscroll(after(9500));
+        assert_eq!(app.current_scroll_pos, 3);
+
+        app.speed_up_autoscroll();
+        assert_eq!(app.config.autoscroll_interval_ms, 900);
+        app.tick_autoscroll(after(30_000));
+        assert_eq!(app.current_scroll_pos, 6);
+        assert!(!app.is_autoscrolling());
+
+        app.tick_autoscroll(after(60_000));
+        assert_eq!(app.current_scroll_pos, 6);
+    }
 
-    let line_limited_workers =
-        (total_lines / PARALLEL_SEARCH_MIN_LINES_PER_WORKER).max(1);
+    #[test]
+    fn test_plus_minus_with_toc_shown()
+    {
+        let mut app = headless_app(&"line\n".repeat(10), Config::default());
+        app.app_state
+            .insert(AppStateFlags::SHOULD_SHOW_TOC);
+
+        // Adjusts the speed rather than folding the ToC
+        app.toggle_autoscroll(Instant::now());
+        app.collapse_or_slow_down();
+        assert_eq!(app.config.autoscroll_interval_ms, 1100);
+        assert!(!app.rfc_toc_panel.is_collapsed());
+        app.expand_or_speed_up();
+        app.expand_or_speed_up();
+        assert_eq!(app.config.autoscroll_interval_ms, 900);
+
+        app.toggle_autoscroll(Instant::now());
+        app.collapse_or_slow_down();
+        assert!(app.rfc_toc_panel.is_collapsed());
+        app.expand_or_speed_up();
+        assert!(!app.rfc_toc_panel.is_collapsed());
+        assert_eq!(app.config.autoscroll_interval_ms, 900);
+    }
 
-    let worker_count = available_workers.min(line_limited_workers);
+    #[test]
+    fn test_goto_line() -> anyhow::Result<()>
+    {
+        let mut app = headless_app(&"line\n".repeat(50), Config::default());
 
-    if worker_count <= 1
+        app.enter_goto_mode();
+        for ch in "1x2".chars()
+        {
+            app.add_goto_char(ch);
+        }
+        assert_eq!(app.goto_text, "12");
+        assert!(statusbar_text(&mut app)?.contains(" GOTO :12 "));
+
+        app.confirm_goto();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.current_scroll_pos, 11);
+
+        // Clamped to the last line
+        app.enter_goto_mode();
+        app.add_goto_char('9');
+        app.add_goto_char('9');
+        app.confirm_goto();
+        assert_eq!(app.current_scroll_pos, 49);
+
+        app.enter_goto_mode();
+        app.add_goto_char('3');
+        app.remove_goto_char();
+        app.add_goto_char('4');
+        app.cancel_goto();
+        assert_eq!(app.current_scroll_pos, 49);
+
+        app.enter_goto_mode();
+        app.confirm_goto();
+        assert_eq!(app.current_scroll_pos, 49);
+        assert_eq!(app.mode, AppMode::Normal);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_long_query_scrolls_input_box() -> anyhow::Result<()>
     {
-        // 1 worker ain't making sense for parallelism, just do it serially.
-        SearchStrategy::Serial
+        let mut app = headless_app("text", Config::default());
+        app.enter_search_mode();
+        "0123456789"
+            .repeat(8)
+            .chars()
+            .for_each(|char| app.add_search_char(char));
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30))?;
+        terminal.draw(|frame| app.render(frame))?;
+        let cursor = terminal.get_cursor_position()?;
+        let buffer = terminal.backend().buffer();
+
+        // The end of the query is shown on the cursor row, right before it
+        assert!(row_text(buffer, cursor.y).contains("0123456789 "));
+        assert_eq!(
+            buffer[(cursor.x.saturating_sub(1), cursor.y)].symbol(),
+            "9"
+        );
+        assert!(!row_text(buffer, cursor.y.saturating_add(1)).contains('0'));
+
+        app.move_search_cursor_home();
+        terminal.draw(|frame| app.render(frame))?;
+        let cursor = terminal.get_cursor_position()?;
+        assert!(
+            row_text(terminal.backend().buffer(), cursor.y).contains("/0123")
+        );
+
+        Ok(())
     }
-    else
+
+    #[test]
+    fn test_incremental_search()
     {
-        SearchStrategy::Parallel { worker_count }
+        let content = ["alpha", "beta", "gamma", "beta again"].join("\n");
+        let config = Config {
+            incremental_search: true,
+            ..Config::default()
+        };
+        let mut app = headless_app(&content, config);
+        app.mode = AppMode::Search;
+        let later = || Instant::now() + INCREMENTAL_SEARCH_DELAY;
+
+        app.add_search_char('b');
+        // Not highlighted until typing pauses
+        app.tick_incremental_search(Instant::now());
+        assert!(!app.has_search_results());
+        app.tick_incremental_search(later());
+        assert_eq!(app.query_match_line_nums, [1, 3]);
+        assert_eq!(app.current_scroll_pos, 0);
+
+        app.add_search_char('e');
+        app.add_search_char('t');
+        app.add_search_char('a');
+        app.add_search_char(' ');
+        app.tick_incremental_search(later());
+        assert_eq!(app.query_match_line_nums, [3]);
+
+        // A partial regex keeps the previous highlights
+        app.toggle_regex_mode();
+        app.add_search_char('(');
+        app.tick_incremental_search(later());
+        assert_eq!(app.query_match_line_nums, [3]);
+
+        app.remove_search_char();
+        app.tick_incremental_search(later());
+        assert_eq!(app.query_match_line_nums, [3]);
+
+        // The same pattern isn't searched again
+        app.query_match_line_nums.clear();
+        app.add_search_char('x');
+        app.remove_search_char();
+        app.tick_incremental_search(later());
+        assert!(app.query_match_line_nums.is_empty());
+
+        // Searched again once the flags change it
+        app.toggle_case_sensitivity();
+        app.tick_incremental_search(later());
+        assert_eq!(app.query_match_line_nums, [3]);
+
+        // Canceling drops the highlights of the typed query, even while the
+        // latest change isn't highlighted yet
+        app.add_search_char('x');
+        app.cancel_search();
+        assert!(!app.has_search_results());
+        assert_eq!(app.mode, AppMode::Normal);
     }
-}
 
-/// Gets a compiled regex for the given query, case sensitivity, and regex mode.
-/// Uses caching to avoid recompiling the same regex multiple times.
-///
-/// # Arguments
-///
-/// * `query` - The search query string
-/// * `is_case_sensitive` - Whether the search is case sensitive
-/// * `is_regex` - Whether the query is a regex
-///
-/// # Returns
-///
-/// A compiled `Regex` if the query is valid, or `None` if invalid.
-#[cached(
-    size = 20,
-    key = "String",
-    convert = r#"{ format!("{}-{}-{}", query, is_case_sensitive, is_regex) }"#
-)]
-fn get_compiled_regex(
-    query: String,
-    is_case_sensitive: bool,
-    is_regex: bool,
-) -> Option<Regex>
-{
-    let pattern = if is_regex
+    #[test]
+    fn test_build_search_regex()
     {
-        query
+        let mut app = headless_app("", Config::default());
+        let pattern = |app: &App| {
+            app.build_search_regex()
+                .map(|regex| regex.as_str().to_owned())
+        };
+
+        app.query_text.push_str("a.b  c");
+        assert_eq!(pattern(&app).as_deref(), Some(r"(?i)a\.b  c"));
+
+        app.toggle_case_sensitivity();
+        assert_eq!(pattern(&app).as_deref(), Some(r"a\.b  c"));
+
+        app.toggle_cross_line_search();
+        assert_eq!(pattern(&app).as_deref(), Some(r"a\.b c"));
+
+        // Regexes are kept as typed
+        app.toggle_regex_mode();
+        assert_eq!(pattern(&app).as_deref(), Some("a.b  c"));
+
+        app.query_text.push('(');
+        assert_eq!(pattern(&app), None);
     }
-    else
+
+    #[test]
+    fn test_search_regex_recompiled_on_change()
     {
-        regex::escape(&query)
-    };
+        let mut app = headless_app("", Config::default());
+        let pattern = |app: &mut App| {
+            app.search_regex()
+                .map(|regex| regex.as_str().to_owned())
+        };
 
-    let case_prefix = if is_case_sensitive { "" } else { "(?i)" };
+        app.query_text.push_str("a.b");
+        assert_eq!(pattern(&mut app).as_deref(), Some(r"(?i)a\.b"));
+        assert_eq!(
+            app.search_regex
+                .as_ref()
+                .map(|(query, flags, _)| (query.as_str(), *flags)),
+            Some(("a.b", 0))
+        );
 
-    Regex::new(&format!("{case_prefix}{pattern}")).ok()
+        // Flags outside of the search are left out of the key
+        app.toggle_title_highlight();
+        assert_eq!(pattern(&mut app).as_deref(), Some(r"(?i)a\.b"));
+
+        app.toggle_case_sensitivity();
+        assert_eq!(pattern(&mut app).as_deref(), Some(r"a\.b"));
+
+        app.query_text.push('(');
+        app.toggle_regex_mode();
+        assert_eq!(pattern(&mut app), None);
+        assert!(
+            app.search_regex
+                .as_ref()
+                .is_some_and(
+                    |(query, _, regex)| query == "a.b(" && regex.is_none()
+                )
+        );
+    }
+
+    #[test]
+    fn test_search_box_title_shows_modes() -> anyhow::Result<()>
+    {
+        let mut app = headless_app("alpha", Config::default());
+        app.mode = AppMode::Search;
+        let has_title = |app: &mut App, title: &str| {
+            render_to_buffer(app, 100, 20).map(|buffer| {
+                (0..20).any(|row| row_text(&buffer, row).contains(title))
+            })
+        };
+
+        assert!(has_title(&mut app, "┌Search─")?);
+
+        app.toggle_regex_mode();
+        app.toggle_case_sensitivity();
+        assert!(has_title(&mut app, "┌Search [regex] [case]─")?);
+
+        app.toggle_regex_mode();
+        app.toggle_cross_line_search();
+        assert!(has_title(&mut app, "┌Search [case] [lines]─")?);
+
+        Ok(())
+    }
 }
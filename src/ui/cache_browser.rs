@@ -0,0 +1,185 @@
+//! List of the cached RFCs.
+//!
+//! Lets a cached RFC be picked to open in place of the current document
+//! without knowing its number beforehand.
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
+
+use super::theme::Theme;
+use crate::types::RfcNum;
+
+/// Symbol used to highlight the currently selected RFC.
+const CACHE_HIGHLIGHT_SYMBOL: &str = "> ";
+
+/// Number of bytes in a KiB.
+const BYTES_PER_KIB: u64 = 1024;
+
+/// An RFC in the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedRfc
+{
+    /// The number of the RFC.
+    pub number: RfcNum,
+    /// The title of the RFC, if it could be found.
+    pub title: Option<Box<str>>,
+    /// Whether the title has been looked up yet.
+    pub is_title_loaded: bool,
+    /// The size of the cached file in bytes.
+    pub size: u64,
+}
+
+/// List of the cached RFCs.
+#[derive(Default)]
+pub struct CacheBrowser
+{
+    /// The cached RFCs in ascending order.
+    entries: Vec<CachedRfc>,
+    /// Current selection state.
+    state: ListState,
+}
+
+impl CacheBrowser
+{
+    /// Creates a new `CacheBrowser` of the given RFCs.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The cached RFCs
+    ///
+    /// # Returns
+    ///
+    /// A new `CacheBrowser` with the first RFC selected.
+    #[must_use]
+    pub fn new(entries: Vec<CachedRfc>) -> Self
+    {
+        let state = ListState::default()
+            .with_selected((!entries.is_empty()).then_some(0));
+
+        Self { entries, state }
+    }
+
+    /// Moves the selection down by the given amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Number of entries to move by
+    pub fn next(&mut self, amount: usize)
+    {
+        let last_index = self.entries.len().saturating_sub(1);
+
+        if let Some(index) = self.state.selected()
+        {
+            self.state
+                .select(Some(index.saturating_add(amount).min(last_index)));
+        }
+    }
+
+    /// Moves the selection up by the given amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Number of entries to move by
+    pub const fn previous(&mut self, amount: usize)
+    {
+        if let Some(index) = self.state.selected()
+        {
+            self.state
+                .select(Some(index.saturating_sub(amount)));
+        }
+    }
+
+    /// Gets the selected RFC.
+    ///
+    /// # Returns
+    ///
+    /// The number of the selected RFC, or `None` if the cache is empty.
+    #[must_use]
+    pub fn selected(&self) -> Option<RfcNum>
+    {
+        self.state
+            .selected()
+            .and_then(|index| self.entries.get(index))
+            .map(|entry| entry.number)
+    }
+
+    /// Gets the numbers of the listed RFCs.
+    ///
+    /// # Returns
+    ///
+    /// The RFC numbers in ascending order.
+    #[must_use]
+    pub fn numbers(&self) -> Vec<RfcNum>
+    {
+        self.entries
+            .iter()
+            .map(|entry| entry.number)
+            .collect()
+    }
+
+    /// Sets the title of a listed RFC once it has been looked up.
+    ///
+    /// # Arguments
+    ///
+    /// * `rfc_number` - The RFC the title belongs to
+    /// * `title` - The title, or `None` if the RFC has no title
+    pub fn set_title(&mut self, rfc_number: RfcNum, title: Option<Box<str>>)
+    {
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.number == rfc_number)
+        {
+            entry.title = title;
+            entry.is_title_loaded = true;
+        }
+    }
+
+    /// Renders the list to the specified area.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render to
+    /// * `area` - The area within the frame to render the list
+    /// * `theme` - The theme to take the styles from
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme)
+    {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.toc_border)
+            .title(format!("Cached RFCs ({})", self.entries.len()))
+            .title_alignment(Alignment::Center)
+            .title_style(theme.toc_title)
+            .title_bottom(
+                Line::from("Enter:open  Esc:close")
+                    .alignment(Alignment::Center),
+            );
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let size = entry.size.div_ceil(BYTES_PER_KIB);
+                let title = match &entry.title
+                {
+                    Some(title) => title,
+                    None if entry.is_title_loaded => "(no title)",
+                    None => "",
+                };
+
+                ListItem::new(format!(
+                    "{:>4}  {size:>5} KiB  {title}",
+                    entry.number
+                ))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme.toc_highlight)
+            .highlight_symbol(CACHE_HIGHLIGHT_SYMBOL);
+
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
@@ -9,8 +9,12 @@ use std::time::{Duration, Instant};
 use anyhow::{Context as _, Result};
 use crossterm::event::{self, Event as CrosstermEvent, KeyEvent};
 
+use crate::client::ConditionalFetch;
+use crate::index::IndexEntry;
+use crate::types::RfcNum;
+
 /// Events that can be processed by the application.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug)]
 pub enum Event
 {
     /// Regular time tick for updating UI elements.
@@ -19,6 +23,39 @@ pub enum Event
     Key(KeyEvent),
     /// Terminal resize event with new dimensions.
     Resize(u16, u16),
+    /// A background fetch of an RFC has finished.
+    FetchFinished
+    {
+        /// The id the fetch was started with.
+        fetch_id: u64,
+        /// The RFC that was fetched.
+        rfc_number: RfcNum,
+        /// The fetched content, or that the copy at hand is current, or the
+        /// error that occurred.
+        result: Result<ConditionalFetch>,
+    },
+    /// A batch of the RFC index has been parsed in the background.
+    IndexLoaded
+    {
+        /// The entries of the batch.
+        entries: Vec<IndexEntry>,
+        /// The percentage of the index parsed so far, 100 for the last batch.
+        percent: u8,
+    },
+    /// The title of a cached RFC has been read in the background.
+    CachedTitleLoaded
+    {
+        /// The cached RFC.
+        rfc_number: RfcNum,
+        /// The title, or `None` if the RFC cannot be read or has no title.
+        title: Option<Box<str>>,
+    },
+    /// Loading the RFC index in the background has failed.
+    IndexFailed
+    {
+        /// The error that occurred.
+        error: anyhow::Error,
+    },
 }
 
 /// Handles terminal events.
@@ -30,6 +67,8 @@ pub struct EventHandler
     /// Receiver side of the event channel to get events from the handler
     /// thread.
     event_receiver: mpsc::Receiver<Event>,
+    /// Sender side of the event channel, handed out to background workers.
+    event_sender: mpsc::Sender<Event>,
     /// Sender for shutdown the thread for graceful shutdown.
     // The receiver is moved to the thread
     shutdown_sender: mpsc::Sender<()>,
@@ -66,6 +105,8 @@ impl EventHandler
         // application
         let (event_sender, event_receiver) = mpsc::channel();
         let (shutdown_sender, shutdown_receiver) = mpsc::channel();
+        // Keep a sender around to hand out to background workers
+        let worker_event_sender = event_sender.clone();
 
         // Spawn a thread that continuously polls for terminal events
         // Move the `shutdown_receiver` to the thread.
@@ -146,6 +187,7 @@ impl EventHandler
 
         Self {
             event_receiver,
+            event_sender: worker_event_sender,
             shutdown_sender,
             thread_handle: Some(handle),
         }
@@ -168,6 +210,17 @@ impl EventHandler
             .recv()
             .context("Event channel disconnected")
     }
+
+    /// Gets a sender for posting events from other threads.
+    ///
+    /// # Returns
+    ///
+    /// A sender connected to the event channel.
+    #[must_use]
+    pub fn sender(&self) -> mpsc::Sender<Event>
+    {
+        self.event_sender.clone()
+    }
 }
 
 impl Drop for EventHandler
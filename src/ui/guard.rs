@@ -6,10 +6,12 @@
 //! A guard object is created to initialize the TUI,
 //! and its `Drop` implementation automatically restores the terminal when it
 //! goes out of scope, either on normal exit or during a panic unwind.
-use std::io::stdout;
+use std::io::{Write as _, stdout};
 use std::panic::{set_hook, take_hook};
 
 use anyhow::Result;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
 use crossterm::ExecutableCommand as _;
 use crossterm::cursor::{SetCursorStyle, Show};
 use crossterm::terminal::{
@@ -20,11 +22,25 @@ use log::error;
 use ratatui::Terminal;
 use ratatui::backend::{Backend as RatatuiBackend, CrosstermBackend};
 
+/// Saves the window title on the terminal's title stack (`XTWINOPS`).
+const PUSH_TITLE: &[u8] = b"\x1b[22;0t";
+/// Restores the window title saved on the terminal's title stack.
+const POP_TITLE: &[u8] = b"\x1b[23;0t";
+
+/// Starts setting the clipboard (`OSC 52`), followed by the base64 text.
+const SET_CLIPBOARD: &[u8] = b"\x1b]52;c;";
+/// Terminates an operating system command.
+const STRING_TERMINATOR: &[u8] = b"\x07";
+
 /// RAII wrapper for terminal state.
 ///
 /// Manages the terminal's configuration, ensuring it is always returned
 /// to its original state when this struct is dropped.
-pub struct TerminalGuard;
+pub struct TerminalGuard
+{
+    /// Whether the original window title was saved to be restored.
+    has_saved_title: bool,
+}
 
 impl TerminalGuard
 {
@@ -47,7 +63,56 @@ impl TerminalGuard
         enable_raw_mode()?;
         stdout().execute(SetCursorStyle::BlinkingBar)?;
         stdout().execute(EnterAlternateScreen)?;
-        Ok(Self)
+        Ok(Self {
+            has_saved_title: false,
+        })
+    }
+
+    /// Saves the current window title to restore it on drop.
+    ///
+    /// The title can't be queried, so it's pushed onto the terminal's title
+    /// stack instead. Terminals without one ignore the request. Only the
+    /// first call saves, later titles are set by the app itself.
+    pub fn save_title(&mut self)
+    {
+        if self.has_saved_title
+        {
+            return;
+        }
+
+        let mut stdout = stdout();
+        if let Err(err) = stdout
+            .write_all(PUSH_TITLE)
+            .and_then(|()| stdout.flush())
+        {
+            error!("Failed to save the window title: {err}");
+            return;
+        }
+
+        self.has_saved_title = true;
+    }
+
+    /// Copies text to the system clipboard through the terminal.
+    ///
+    /// Uses `OSC 52`, which also works over SSH. Terminals without support
+    /// ignore the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to copy
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the terminal fails.
+    pub fn copy_to_clipboard(&self, text: &str) -> Result<()>
+    {
+        let mut stdout = stdout();
+        stdout.write_all(SET_CLIPBOARD)?;
+        stdout.write_all(STANDARD.encode(text).as_bytes())?;
+        stdout.write_all(STRING_TERMINATOR)?;
+        stdout.flush()?;
+
+        Ok(())
     }
 }
 
@@ -82,6 +147,17 @@ impl Drop for TerminalGuard
         {
             error!("Failed to leave alternate screen: {err}");
         }
+
+        if self.has_saved_title
+        {
+            let mut stdout = stdout();
+            if let Err(err) = stdout
+                .write_all(POP_TITLE)
+                .and_then(|()| stdout.flush())
+            {
+                error!("Failed to restore the window title: {err}");
+            }
+        }
     }
 }
 
@@ -0,0 +1,323 @@
+//! Browser for the RFC index.
+//!
+//! Lists every RFC with its title, filtered by the typed query, so that RFCs
+//! can be opened without knowing their numbers.
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+
+use super::theme::Theme;
+use crate::index::IndexEntry;
+use crate::types::RfcNum;
+
+/// Symbol used to highlight the currently selected RFC.
+const BROWSER_HIGHLIGHT_SYMBOL: &str = "> ";
+
+/// Browser listing the RFCs of the index.
+#[derive(Default)]
+pub struct IndexBrowser
+{
+    /// All RFCs of the index.
+    entries: Vec<IndexEntry>,
+    /// Lowercased `number title` of each entry to match the filter against.
+    search_keys: Vec<String>,
+    /// Text the entries are filtered by.
+    filter: String,
+    /// Indexes of the entries matching the filter.
+    filtered: Vec<usize>,
+    /// Current selection state within the filtered entries.
+    state: ListState,
+    /// Percentage of the index parsed while the entries are still loading.
+    loading_percent: Option<u8>,
+}
+
+impl IndexBrowser
+{
+    /// Creates a new `IndexBrowser` listing the given entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The entries of the index
+    ///
+    /// # Returns
+    ///
+    /// A new `IndexBrowser` with no filter applied.
+    #[must_use]
+    pub fn new(entries: Vec<IndexEntry>) -> Self
+    {
+        let mut browser = Self::default();
+        browser.push_entries(entries, 100);
+
+        browser
+    }
+
+    /// Creates a new `IndexBrowser` whose entries are yet to be loaded.
+    ///
+    /// # Returns
+    ///
+    /// A new empty `IndexBrowser`, filled by [`Self::push_entries`].
+    #[must_use]
+    pub fn loading() -> Self
+    {
+        Self {
+            loading_percent: Some(0),
+            ..Default::default()
+        }
+    }
+
+    /// Appends loaded entries, keeping the filter and the selection.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The entries to append
+    /// * `percent` - The percentage of the index loaded so far, 100 once done
+    pub fn push_entries(&mut self, entries: Vec<IndexEntry>, percent: u8)
+    {
+        let filter = self.filter.to_lowercase();
+        let words: Vec<&str> = filter.split_whitespace().collect();
+
+        for entry in entries
+        {
+            let search_key =
+                format!("{} {}", entry.number, entry.title).to_lowercase();
+
+            if words
+                .iter()
+                .all(|word| search_key.contains(word))
+            {
+                self.filtered.push(self.entries.len());
+            }
+
+            self.search_keys.push(search_key);
+            self.entries.push(entry);
+        }
+
+        if self.state.selected().is_none() && !self.filtered.is_empty()
+        {
+            self.state.select(Some(0));
+        }
+
+        self.loading_percent = (percent < 100).then_some(percent);
+    }
+
+    /// Appends a character to the filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `ch` - The character to append
+    pub fn push_filter_char(&mut self, ch: char)
+    {
+        self.filter.push(ch);
+        self.apply_filter();
+    }
+
+    /// Removes the last character of the filter.
+    pub fn pop_filter_char(&mut self)
+    {
+        if self.filter.pop().is_some()
+        {
+            self.apply_filter();
+        }
+    }
+
+    /// Moves the selection down by the given amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Number of entries to move by
+    pub fn next(&mut self, amount: usize)
+    {
+        let last_index = self.filtered.len().saturating_sub(1);
+
+        if let Some(index) = self.state.selected()
+        {
+            self.state
+                .select(Some(index.saturating_add(amount).min(last_index)));
+        }
+    }
+
+    /// Moves the selection up by the given amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Number of entries to move by
+    pub const fn previous(&mut self, amount: usize)
+    {
+        if let Some(index) = self.state.selected()
+        {
+            self.state
+                .select(Some(index.saturating_sub(amount)));
+        }
+    }
+
+    /// Gets the number of the selected RFC.
+    ///
+    /// # Returns
+    ///
+    /// The selected RFC number, or `None` if no entry matches the filter.
+    #[must_use]
+    pub fn selected(&self) -> Option<RfcNum>
+    {
+        self.state
+            .selected()
+            .and_then(|index| self.filtered.get(index))
+            .and_then(|&entry_index| self.entries.get(entry_index))
+            .map(|entry| entry.number)
+    }
+
+    /// Filters the entries by the current filter text.
+    ///
+    /// Every whitespace-separated word of the filter must appear in the
+    /// number or the title of an entry, in any order and case.
+    fn apply_filter(&mut self)
+    {
+        let filter = self.filter.to_lowercase();
+        let words: Vec<&str> = filter.split_whitespace().collect();
+
+        self.filtered = self
+            .search_keys
+            .iter()
+            .enumerate()
+            .filter(|(_, key)| words.iter().all(|word| key.contains(word)))
+            .map(|(index, _)| index)
+            .collect();
+
+        self.state
+            .select((!self.filtered.is_empty()).then_some(0));
+    }
+
+    /// Renders the browser to the specified area.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render to
+    /// * `area` - The area within the frame to render the browser
+    /// * `theme` - The theme to take the styles from
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme)
+    {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.toc_border)
+            .title(match self.loading_percent
+            {
+                Some(percent) => format!(
+                    "RFC Index ({}/{}, loading {percent}%)",
+                    self.filtered.len(),
+                    self.entries.len()
+                ),
+                None => format!(
+                    "RFC Index ({}/{})",
+                    self.filtered.len(),
+                    self.entries.len()
+                ),
+            })
+            .title_alignment(Alignment::Center)
+            .title_style(theme.toc_title)
+            .title_bottom(
+                Line::from("Enter:open  Esc:close")
+                    .alignment(Alignment::Center),
+            );
+
+        let [filter_area, list_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)])
+                .areas(block.inner(area));
+
+        frame.render_widget(block, area);
+
+        let filter_line = Line::from(vec![
+            Span::styled("Filter: ", theme.toc_title),
+            Span::raw(self.filter.as_str()),
+        ]);
+        frame.render_widget(Paragraph::new(filter_line), filter_area);
+
+        // All entries are handed over, the list only renders the visible ones
+        let items: Vec<ListItem> = self
+            .filtered
+            .iter()
+            .filter_map(|&index| self.entries.get(index))
+            .map(|entry| {
+                ListItem::new(format!("{:>4}  {}", entry.number, entry.title))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .highlight_style(theme.toc_highlight)
+            .highlight_symbol(BROWSER_HIGHLIGHT_SYMBOL);
+
+        frame.render_stateful_widget(list, list_area, &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// Creates a browser listing the given RFCs.
+    fn browser(entries: &[(u16, &str)]) -> IndexBrowser
+    {
+        IndexBrowser::new(
+            entries
+                .iter()
+                .map(|&(number, title)| {
+                    IndexEntry::new(
+                        RfcNum::new(number).expect("its non-zero"),
+                        title,
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_filter_matches_all_words()
+    {
+        let mut browser = browser(&[
+            (791, "Internet Protocol"),
+            (2616, "Hypertext Transfer Protocol -- HTTP/1.1"),
+            (
+                8446,
+                "The Transport Layer Security (TLS) Protocol Version 1.3",
+            ),
+        ]);
+
+        for ch in "protocol tls".chars()
+        {
+            browser.push_filter_char(ch);
+        }
+        assert_eq!(browser.selected().map(RfcNum::get), Some(8446));
+
+        // Numbers can be filtered by as well
+        browser.filter.clear();
+        for ch in "261".chars()
+        {
+            browser.push_filter_char(ch);
+        }
+        assert_eq!(browser.selected().map(RfcNum::get), Some(2616));
+
+        browser.push_filter_char('x');
+        assert_eq!(browser.selected(), None);
+    }
+
+    #[test]
+    fn test_pushed_entries_keep_selection()
+    {
+        let entry = |number, title: &str| {
+            IndexEntry::new(RfcNum::new(number).expect("its non-zero"), title)
+        };
+        let mut browser = IndexBrowser::loading();
+        browser.push_filter_char('p');
+
+        browser.push_entries(
+            vec![entry(768, "User Datagram Protocol"), entry(1, "Host")],
+            50,
+        );
+        browser.push_entries(vec![entry(791, "Internet Protocol")], 100);
+        assert_eq!(browser.selected().map(RfcNum::get), Some(768));
+
+        browser.next(1);
+        assert_eq!(browser.selected().map(RfcNum::get), Some(791));
+        assert_eq!(browser.loading_percent, None);
+    }
+}
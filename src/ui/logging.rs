@@ -22,7 +22,7 @@ use env_logger::{Builder, Target};
 use file_rotate::compression::Compression;
 use file_rotate::suffix::AppendCount;
 use file_rotate::{ContentLimit, FileRotate};
-use log::LevelFilter;
+use log::{LevelFilter, warn};
 
 /// Maximum size of a log file in bytes.
 const LOG_FILE_SIZE: usize = 5 * 1024 * 1024; // 5 MiB
@@ -51,15 +51,11 @@ static LOG_FILES_PATH: LazyLock<Box<Path>> = LazyLock::new(|| {
         base_dirs.data_local_dir()
     };
 
-    fs::create_dir_all(base_path).expect("Failed to create base directory");
-
-    // Use a dedicated directory for logs
+    // Use a dedicated directory for logs, created by `init_logging`
     let logs_dir_path = base_path
         .join(env!("CARGO_PKG_NAME"))
         .join("logs");
 
-    fs::create_dir_all(&logs_dir_path).expect("Failed to create log directory");
-
     logs_dir_path.into_boxed_path()
 });
 
@@ -99,10 +95,50 @@ fn get_base_log_file_path() -> &'static Path
 /// This function sets up the logging configuration, including the
 /// log file path, log level, and log format.
 ///
+/// A logger set up beforehand, like by an application embedding the crate,
+/// is kept with a warning.
+///
 /// # Errors
 ///
-/// Returns an error if the logging system cannot be initialized.
+/// Returns an error if the log directory cannot be created.
 pub fn init_logging() -> Result<()>
+{
+    init_logging_in(get_log_files_dir_path())
+}
+
+/// Initializes the logging system with the log files in the given
+/// directory.
+///
+/// # Arguments
+///
+/// * `log_files_dir` - The directory to write the log files to
+///
+/// # Errors
+///
+/// Returns an error if the log directory cannot be created.
+fn init_logging_in(log_files_dir: &Path) -> Result<()>
+{
+    let mut builder = build_logger(log_files_dir)?;
+    keep_installed_logger(&builder.try_init());
+
+    Ok(())
+}
+
+/// Sets up the logger writing to the log files in the given directory,
+/// without installing it.
+///
+/// # Arguments
+///
+/// * `log_files_dir` - The directory to write the log files to
+///
+/// # Returns
+///
+/// The builder of the logger.
+///
+/// # Errors
+///
+/// Returns an error if the log directory cannot be created.
+fn build_logger(log_files_dir: &Path) -> Result<Builder>
 {
     // static assertion to prevent skill issues in the future
     const {
@@ -112,7 +148,11 @@ pub fn init_logging() -> Result<()>
         );
     }
 
-    let base_log_file_path = get_base_log_file_path();
+    fs::create_dir_all(log_files_dir)
+        .context("Failed to create log directory")?;
+
+    let base_log_file_path =
+        log_files_dir.join(concat!(env!("CARGO_PKG_NAME"), ".log"));
 
     let log_open_option = {
         let mut option = OpenOptions::new();
@@ -123,7 +163,7 @@ pub fn init_logging() -> Result<()>
 
     // Files are rotated as `<package-name>.log.<count>`
     let rotator = FileRotate::new(
-        base_log_file_path,
+        &base_log_file_path,
         AppendCount::new(MAX_LOG_FILE_COUNT),
         ContentLimit::Bytes(LOG_FILE_SIZE),
         Compression::OnRotate(UNCOMPRESSED_LOG_FILE_COUNT),
@@ -147,9 +187,29 @@ pub fn init_logging() -> Result<()>
         })
         .target(Target::Pipe(Box::new(rotator)));
 
-    builder
-        .try_init()
-        .context("Failed to initialize logger")
+    Ok(builder)
+}
+
+/// Keeps a logger installed beforehand with a warning.
+///
+/// # Arguments
+///
+/// * `install_result` - The result of installing the logger, an error if one
+///   was already installed
+///
+/// # Returns
+///
+/// `true` if the logger was installed, `false` if the previous one is kept.
+fn keep_installed_logger<E>(install_result: &Result<(), E>) -> bool
+{
+    if install_result.is_err()
+    {
+        // Goes to the logger already set
+        warn!("A logger is already initialized, keeping it");
+        return false;
+    }
+
+    true
 }
 
 /// Removes the log files.
@@ -213,3 +273,31 @@ pub fn clear_log_files() -> Result<()>
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests
+{
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_build_logger() -> Result<()>
+    {
+        let temp_dir = TempDir::new()?;
+        let log_files_dir = temp_dir.path().join("logs");
+
+        // Not installed, the global logger is left to the application
+        build_logger(&log_files_dir)?.build();
+        assert!(log_files_dir.is_dir());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_keep_installed_logger()
+    {
+        assert!(keep_installed_logger::<()>(&Ok(())));
+        assert!(!keep_installed_logger(&Err(())));
+    }
+}
@@ -3,10 +3,18 @@
 //! Provides rendering, event handling, and state management for the
 //! terminal-based user interface.
 mod app;
+mod cache_browser;
 mod event;
 pub mod guard;
+mod index_browser;
 pub mod logging;
+mod reference_list;
+pub mod theme;
 mod toc_panel;
 
-pub use app::{App, AppMode, AppStateFlags};
+pub use app::{App, AppMode, AppStateFlags, FetchPurpose};
+pub use cache_browser::{CacheBrowser, CachedRfc};
 pub use event::{Event, EventHandler};
+pub use index_browser::IndexBrowser;
+pub use reference_list::ReferenceList;
+pub use toc_panel::{TocEntry, TocPanel};
@@ -0,0 +1,127 @@
+//! List of the entries of the references section.
+//!
+//! Lets the entries be picked to open the referenced RFC or to jump to the
+//! entry in the document.
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
+
+use super::theme::Theme;
+use crate::references::ReferenceEntry;
+
+/// Symbol used to highlight the currently selected reference.
+const REFERENCE_HIGHLIGHT_SYMBOL: &str = "> ";
+
+/// List of the entries of the references section.
+#[derive(Default)]
+pub struct ReferenceList
+{
+    /// The entries of the references section.
+    entries: Vec<ReferenceEntry>,
+    /// Current selection state.
+    state: ListState,
+}
+
+impl ReferenceList
+{
+    /// Creates a new `ReferenceList` of the given entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The entries of the references section
+    ///
+    /// # Returns
+    ///
+    /// A new `ReferenceList` with the first entry selected.
+    #[must_use]
+    pub fn new(entries: Vec<ReferenceEntry>) -> Self
+    {
+        let state = ListState::default()
+            .with_selected((!entries.is_empty()).then_some(0));
+
+        Self { entries, state }
+    }
+
+    /// Moves the selection down by the given amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Number of entries to move by
+    pub fn next(&mut self, amount: usize)
+    {
+        let last_index = self.entries.len().saturating_sub(1);
+
+        if let Some(index) = self.state.selected()
+        {
+            self.state
+                .select(Some(index.saturating_add(amount).min(last_index)));
+        }
+    }
+
+    /// Moves the selection up by the given amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Number of entries to move by
+    pub const fn previous(&mut self, amount: usize)
+    {
+        if let Some(index) = self.state.selected()
+        {
+            self.state
+                .select(Some(index.saturating_sub(amount)));
+        }
+    }
+
+    /// Gets the selected entry.
+    ///
+    /// # Returns
+    ///
+    /// The selected entry, or `None` if there are no entries.
+    #[must_use]
+    pub fn selected(&self) -> Option<&ReferenceEntry>
+    {
+        self.state
+            .selected()
+            .and_then(|index| self.entries.get(index))
+    }
+
+    /// Renders the list to the specified area.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame to render to
+    /// * `area` - The area within the frame to render the list
+    /// * `theme` - The theme to take the styles from
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme)
+    {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.toc_border)
+            .title(format!("References ({})", self.entries.len()))
+            .title_alignment(Alignment::Center)
+            .title_style(theme.toc_title)
+            .title_bottom(
+                Line::from("Enter:open RFC or jump  Esc:close")
+                    .alignment(Alignment::Center),
+            );
+
+        // Entries without an RFC can only be jumped to
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| match entry.rfc_number
+            {
+                Some(_) => ListItem::new(&*entry.text),
+                None => ListItem::new(&*entry.text).style(theme.whitespace),
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme.toc_highlight)
+            .highlight_symbol(REFERENCE_HIGHLIGHT_SYMBOL);
+
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
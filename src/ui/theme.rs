@@ -0,0 +1,348 @@
+//! Color themes for the user interface.
+//!
+//! Provides the built-in dark and light themes and the detection of the
+//! terminal background to pick between them.
+use std::str::FromStr;
+use std::{env, fmt};
+
+use anyhow::{Error, bail};
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Config, ConfigColor};
+use crate::metadata::RfcCategory;
+
+/// Badges of the RFC categories, readable on any background.
+const CATEGORY_STYLES: CategoryStyles = CategoryStyles {
+    standards_track: Style::new()
+        .bg(Color::Green)
+        .fg(Color::Black),
+    best_current_practice: Style::new().bg(Color::Cyan).fg(Color::Black),
+    informational: Style::new().bg(Color::Blue).fg(Color::White),
+    experimental: Style::new()
+        .bg(Color::Yellow)
+        .fg(Color::Black),
+    historic: Style::new()
+        .bg(Color::DarkGray)
+        .fg(Color::White),
+};
+
+/// Styles used across the user interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme
+{
+    /// Style for highlighting matches in the search results.
+    pub match_highlight: Style,
+    /// Style for highlighting titles in the document.
+    pub title_highlight: Style,
+    /// Style for the statusbar.
+    pub statusbar: Style,
+    /// Style for the selected `ToC` entry.
+    pub toc_highlight: Style,
+    /// Style for the `ToC` border.
+    pub toc_border: Style,
+    /// Style for the `ToC` panel title.
+    pub toc_title: Style,
+    /// Style for revealed whitespace and control characters.
+    pub whitespace: Style,
+    /// Style for the markers of the rows past the end of the document.
+    pub end_marker: Style,
+    /// Background of the current line, under the other highlights.
+    pub current_line: Style,
+    /// Style for the content of overlays, like help and messages.
+    pub overlay: Style,
+    /// Style for the border of overlays.
+    pub overlay_border: Style,
+    /// Styles for the categories of RFCs.
+    pub category: CategoryStyles,
+}
+
+/// Styles for each category of RFCs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CategoryStyles
+{
+    /// Style for standards track RFCs.
+    pub standards_track: Style,
+    /// Style for best current practices.
+    pub best_current_practice: Style,
+    /// Style for informational RFCs.
+    pub informational: Style,
+    /// Style for experimental RFCs.
+    pub experimental: Style,
+    /// Style for historic RFCs.
+    pub historic: Style,
+}
+
+impl CategoryStyles
+{
+    /// Gets the style of a category.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The category to style
+    ///
+    /// # Returns
+    ///
+    /// The style of the category.
+    #[must_use]
+    pub const fn get(&self, category: RfcCategory) -> Style
+    {
+        match category
+        {
+            RfcCategory::StandardsTrack => self.standards_track,
+            RfcCategory::BestCurrentPractice => self.best_current_practice,
+            RfcCategory::Informational => self.informational,
+            RfcCategory::Experimental => self.experimental,
+            RfcCategory::Historic => self.historic,
+        }
+    }
+}
+
+impl Theme
+{
+    /// Theme for terminals with a dark background.
+    pub const DARK: Self = Self {
+        match_highlight: Style::new()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+        title_highlight: Style::new()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+        statusbar: Style::new()
+            .bg(Color::White)
+            .fg(Color::Black),
+        toc_highlight: Style::new()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD),
+        toc_border: Style::new().fg(Color::Gray),
+        toc_title: Style::new()
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+        whitespace: Style::new()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::DIM),
+        end_marker: Style::new().fg(Color::DarkGray),
+        current_line: Style::new().bg(Color::Indexed(236)),
+        overlay: Style::new(),
+        overlay_border: Style::new().fg(Color::Gray),
+        category: CATEGORY_STYLES,
+    };
+    /// Theme for terminals with a light background.
+    pub const LIGHT: Self = Self {
+        match_highlight: Style::new()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD),
+        title_highlight: Style::new()
+            .fg(Color::Blue)
+            .add_modifier(Modifier::BOLD),
+        statusbar: Style::new()
+            .bg(Color::Black)
+            .fg(Color::White),
+        toc_highlight: Style::new()
+            .fg(Color::Red)
+            .add_modifier(Modifier::BOLD),
+        toc_border: Style::new().fg(Color::DarkGray),
+        toc_title: Style::new()
+            .fg(Color::Black)
+            .add_modifier(Modifier::BOLD),
+        whitespace: Style::new()
+            .fg(Color::Gray)
+            .add_modifier(Modifier::DIM),
+        end_marker: Style::new().fg(Color::Gray),
+        current_line: Style::new().bg(Color::Indexed(254)),
+        overlay: Style::new(),
+        overlay_border: Style::new().fg(Color::DarkGray),
+        category: CATEGORY_STYLES,
+    };
+
+    /// Resolves a theme by its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the theme
+    ///
+    /// # Returns
+    ///
+    /// The theme, detecting the terminal background for
+    /// [`ThemeName::Auto`].
+    #[must_use]
+    pub fn from_name(name: ThemeName) -> Self
+    {
+        match name
+        {
+            ThemeName::Auto => match detect_background()
+            {
+                Some(Background::Light) => Self::LIGHT,
+                // Fall back to dark when inconclusive
+                Some(Background::Dark) | None => Self::DARK,
+            },
+            ThemeName::Dark => Self::DARK,
+            ThemeName::Light => Self::LIGHT,
+        }
+    }
+
+    /// Resolves the theme of a configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration naming the theme and its overrides
+    ///
+    /// # Returns
+    ///
+    /// The named theme with the overrides of the configuration applied.
+    #[must_use]
+    pub fn from_config(config: &Config) -> Self
+    {
+        Self::from_name(config.theme).with_overrides(config)
+    }
+
+    /// Applies the style overrides of a configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the overrides
+    ///
+    /// # Returns
+    ///
+    /// The theme with the overrides applied.
+    #[must_use]
+    pub const fn with_overrides(mut self, config: &Config) -> Self
+    {
+        if let Some(ConfigColor(color)) = config.overlay_background
+        {
+            // Clear overlays would show the transparent background otherwise
+            self.overlay = self.overlay.bg(color);
+            self.overlay_border = self.overlay_border.bg(color);
+        }
+
+        if let Some(ConfigColor(color)) = config.match_color
+        {
+            self.match_highlight = self.match_highlight.fg(color);
+        }
+
+        self
+    }
+}
+
+impl Default for Theme
+{
+    fn default() -> Self
+    {
+        Self::DARK
+    }
+}
+
+/// Names of the selectable themes.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName
+{
+    /// Pick dark or light based on the terminal background.
+    #[default]
+    Auto,
+    /// The dark theme.
+    Dark,
+    /// The light theme.
+    Light,
+}
+
+impl ThemeName
+{
+    /// Names accepted on the command line.
+    pub const VARIANTS: [&str; 3] = ["auto", "dark", "light"];
+}
+
+impl FromStr for ThemeName
+{
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err>
+    {
+        match name
+        {
+            "auto" => Ok(Self::Auto),
+            "dark" => Ok(Self::Dark),
+            "light" => Ok(Self::Light),
+            _ => bail!("Unknown theme '{name}'"),
+        }
+    }
+}
+
+impl fmt::Display for ThemeName
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Self::Auto => formatter.write_str("auto"),
+            Self::Dark => formatter.write_str("dark"),
+            Self::Light => formatter.write_str("light"),
+        }
+    }
+}
+
+/// Brightness of the terminal background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Background
+{
+    /// A dark background.
+    Dark,
+    /// A light background.
+    Light,
+}
+
+/// Detects the terminal background from the `COLORFGBG` variable.
+///
+/// # Returns
+///
+/// The detected background, or `None` if it couldn't be determined.
+fn detect_background() -> Option<Background>
+{
+    env::var("COLORFGBG")
+        .ok()
+        .and_then(|value| parse_colorfgbg(&value))
+}
+
+/// Parses the background out of a `COLORFGBG` value.
+///
+/// The value is formatted as `fg;bg` or `fg;default;bg`, where the colors
+/// are ANSI color indexes.
+///
+/// # Arguments
+///
+/// * `value` - The value of `COLORFGBG`
+///
+/// # Returns
+///
+/// The background brightness, or `None` if the value is malformed.
+fn parse_colorfgbg(value: &str) -> Option<Background>
+{
+    let background: u8 = value.rsplit(';').next()?.parse().ok()?;
+
+    match background
+    {
+        // White and bright colors except bright black
+        7 | 9..=15 => Some(Background::Light),
+        0..=6 | 8 => Some(Background::Dark),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_parse_colorfgbg()
+    {
+        assert_eq!(parse_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(parse_colorfgbg("0;15"), Some(Background::Light));
+        assert_eq!(parse_colorfgbg("0;default;7"), Some(Background::Light));
+        assert_eq!(parse_colorfgbg("7;8"), Some(Background::Dark));
+        assert_eq!(parse_colorfgbg("default"), None);
+        assert_eq!(parse_colorfgbg(""), None);
+    }
+}
@@ -1,27 +1,27 @@
 //! Manages the RFC Table of Contents panel.
 //!
 //! Displays, navigates, and tracks selection for RFC document entries.
+use std::borrow::Cow;
+use std::iter;
+use std::ops::Range;
+
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Rect};
-use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::Line;
 use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
 use regex::Regex;
 use textwrap::wrap;
 
+use super::theme::Theme;
+use crate::config::{TocPosition, TocTitleOverflow};
 use crate::types::LineNumber;
 
-/// Style for each individual `ToC` entry.
-const TOC_HIGHLIGHT_STYLE: Style = Style::new()
-    .fg(Color::LightYellow)
-    .add_modifier(Modifier::BOLD);
-
-/// Style for the `ToC` border.
-const TOC_BORDER_STYLE: Style = Style::new().fg(Color::Gray);
-
 /// Symbol used to highlight the currently selected `ToC` entry.
 const TOC_HIGHLIGHT_SYMBOL: &str = "> ";
 
+/// Ending of the truncated `ToC` titles.
+const ELLIPSIS: char = '…';
+
 /// Represents a table of contents entry.
 ///
 /// Contains a title and its document line number.
@@ -34,6 +34,36 @@ pub struct TocEntry
     pub line_number: LineNumber,
 }
 
+impl TocEntry
+{
+    /// Gets the nesting level of the entry from its section label.
+    ///
+    /// # Returns
+    ///
+    /// 1 for top level sections like `2.` or `Appendix A.`, 2 for `2.1.` and
+    /// so on. Entries without a label are top level.
+    #[must_use]
+    pub fn depth(&self) -> usize
+    {
+        let mut words = self.title.split_whitespace();
+        let label = match words.next()
+        {
+            Some("Appendix") => words.next(),
+            label => label,
+        };
+
+        label
+            .map(|label| {
+                label
+                    .split('.')
+                    .filter(|part| !part.is_empty())
+                    .count()
+            })
+            .unwrap_or_default()
+            .max(1)
+    }
+}
+
 /// Panel that displays and manages a table of contents.
 ///
 /// Provides navigation capabilities and tracks the currently selected entry.
@@ -42,8 +72,10 @@ pub struct TocPanel
 {
     /// Collection of table of contents entries.
     entries: Vec<TocEntry>,
-    /// Current selection state.
+    /// Current selection state, indexing the shown entries.
     state: ListState,
+    /// Whether only the top level entries are shown.
+    is_collapsed: bool,
 }
 
 impl TocPanel
@@ -60,6 +92,7 @@ impl TocPanel
     /// # Returns
     ///
     /// A new `TocPanel` instance.
+    #[must_use]
     pub fn new(content: &str) -> Self
     {
         let entries = parsing::parse_toc(content);
@@ -70,7 +103,11 @@ impl TocPanel
             state.select(Some(0));
         }
 
-        Self { entries, state }
+        Self {
+            entries,
+            state,
+            is_collapsed: false,
+        }
     }
 
     /// Returns a slice of `ToC` entries, sorted by their first appearance.
@@ -78,20 +115,135 @@ impl TocPanel
     /// # Returns
     ///
     /// A slice of all `ToC` entries.
+    #[must_use]
     pub fn entries(&self) -> &[TocEntry]
     {
         &self.entries
     }
 
+    /// Gets the indices of the entries shown, all of them unless collapsed.
+    ///
+    /// # Returns
+    ///
+    /// The indices of the shown entries in [`Self::entries`].
+    fn shown_indices(&self) -> Vec<usize>
+    {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| !self.is_collapsed || entry.depth() == 1)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Gets the index of the selected entry in [`Self::entries`].
+    ///
+    /// # Returns
+    ///
+    /// The index of the selected entry, or `None` if nothing is selected.
+    fn selected_index(&self) -> Option<usize>
+    {
+        self.state
+            .selected()
+            .and_then(|selected| self.shown_indices().get(selected).copied())
+    }
+
+    /// Checks whether only the top level entries are shown.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the nested entries are collapsed.
+    #[must_use]
+    pub const fn is_collapsed(&self) -> bool
+    {
+        self.is_collapsed
+    }
+
+    /// Collapses all the nested entries, showing only the top level ones.
+    ///
+    /// The selection moves to the top level entry containing it.
+    pub fn collapse_all(&mut self)
+    {
+        let selected = self.selected_index();
+
+        self.is_collapsed = true;
+        if let Some(selected) = selected
+        {
+            let position = self
+                .shown_indices()
+                .iter()
+                .rposition(|&index| index <= selected)
+                .unwrap_or_default();
+            self.state.select(Some(position));
+        }
+    }
+
+    /// Expands all the entries, keeping the selected one.
+    pub fn expand_all(&mut self)
+    {
+        let selected = self.selected_index();
+
+        self.is_collapsed = false;
+        if selected.is_some()
+        {
+            self.state.select(selected);
+        }
+    }
+
+    /// Computes the line range of each section.
+    ///
+    /// A section runs from its entry's line up to the next entry's line, the
+    /// last one to the end of the document.
+    ///
+    /// # Arguments
+    ///
+    /// * `line_count` - The total line count of the document
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the entries paired with their line ranges.
+    pub fn section_ranges(
+        &self,
+        line_count: LineNumber,
+    ) -> impl Iterator<Item = (&TocEntry, Range<LineNumber>)>
+    {
+        let next_starts = self
+            .entries
+            .iter()
+            .skip(1)
+            .map(|entry| entry.line_number)
+            .chain([line_count]);
+
+        self.entries
+            .iter()
+            .zip(next_starts)
+            .map(|(entry, next_start)| {
+                let start = entry.line_number;
+                // Keep the range well-formed for out of order entries
+                (entry, start..next_start.max(start))
+            })
+    }
+
     /// Renders the table of contents panel to the specified area.
     ///
     /// # Arguments
     ///
     /// * `frame` - The frame to render to
     /// * `area` - The area within the frame to render the panel
-    pub fn render(&mut self, frame: &mut Frame, area: Rect)
+    /// * `theme` - The theme to take the styles from
+    /// * `position` - The side of the content the panel is on, the border faces
+    ///   the content
+    /// * `overflow` - How the titles wider than the panel are shown
+    pub fn render(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        position: TocPosition,
+        overflow: TocTitleOverflow,
+    )
     {
-        // Long titles need to be wrapped to fit within the panel width.
+        // Long titles need to be wrapped or cut to fit within the panel width.
         // 2 for the border
         #[expect(
             clippy::arithmetic_side_effects,
@@ -100,42 +252,67 @@ impl TocPanel
         let wrap_width = usize::from(area.width)
             .saturating_sub(TOC_HIGHLIGHT_SYMBOL.len() + 2);
 
-        let items: Vec<ListItem> = self
-            .entries
+        let shown_indices = self.shown_indices();
+        let items: Vec<ListItem> = shown_indices
             .iter()
-            .map(|entry| {
-                let wrapped_title = wrap(&entry.title, wrap_width)
-                    .into_iter()
-                    .map(Line::raw)
-                    .collect::<Vec<Line>>();
+            .filter_map(|&index| self.entries.get(index))
+            .map(|entry| match overflow
+            {
+                TocTitleOverflow::Wrap =>
+                {
+                    let wrapped_title = wrap(&entry.title, wrap_width)
+                        .into_iter()
+                        .map(Line::raw)
+                        .collect::<Vec<Line>>();
 
-                ListItem::new(wrapped_title)
+                    ListItem::new(wrapped_title)
+                },
+                TocTitleOverflow::Truncate =>
+                {
+                    ListItem::new(truncate(&entry.title, wrap_width))
+                },
             })
             .collect();
 
+        let borders = match position
+        {
+            TocPosition::Left => Borders::RIGHT,
+            TocPosition::Right => Borders::LEFT,
+        };
+
+        let mut block = Block::default()
+            .borders(borders)
+            .border_style(theme.toc_border)
+            .title("Contents")
+            .title_alignment(Alignment::Left)
+            .title_style(theme.toc_title);
+
+        // The list scrolls when the entries overflow, show where we are
+        let entries_height: usize = items.iter().map(ListItem::height).sum();
+        if entries_height > usize::from(block.inner(area).height) &&
+            let Some(selected) = self.state.selected()
+        {
+            let position = format!(
+                "{}/{}",
+                selected.saturating_add(1),
+                shown_indices.len()
+            );
+            block = block.title_top(Line::from(position).right_aligned());
+        }
+
         let list = List::new(items)
-            .block(
-                Block::default()
-                    .borders(Borders::RIGHT)
-                    .border_style(TOC_BORDER_STYLE)
-                    .title("Contents")
-                    .title_alignment(Alignment::Left)
-                    .title_style(
-                        Style::new()
-                            .fg(Color::White)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-            )
-            .highlight_style(TOC_HIGHLIGHT_STYLE)
+            .block(block)
+            .highlight_style(theme.toc_highlight)
             .highlight_symbol(TOC_HIGHLIGHT_SYMBOL);
 
         frame.render_stateful_widget(list, area, &mut self.state);
     }
 
-    /// Moves the selection to the next entry.
-    pub const fn next(&mut self)
+    /// Moves the selection to the next entry, stopping at the last one.
+    pub fn next(&mut self)
     {
-        if let Some(i) = self.state.selected()
+        if let Some(i) = self.state.selected() &&
+            i.saturating_add(1) < self.shown_indices().len()
         {
             self.state.select(Some(i.saturating_add(1)));
         }
@@ -156,20 +333,39 @@ impl TocPanel
     ///
     /// The line number of the selected entry, or `None` if no entry is selected
     /// or the entries list is empty.
+    #[must_use]
     pub fn selected_line(&self) -> Option<LineNumber>
     {
-        if self.entries.is_empty()
-        {
-            return None;
-        }
-
-        self.state
-            .selected()
-            .and_then(|i| self.entries.get(i))
+        self.selected_index()
+            .and_then(|index| self.entries.get(index))
             .map(|entry| entry.line_number)
     }
 }
 
+/// Cuts a title to the given width, ending it with an ellipsis if it's cut.
+///
+/// # Arguments
+///
+/// * `title` - The title to cut
+/// * `width` - The maximum number of characters
+///
+/// # Returns
+///
+/// The title if it fits, otherwise its beginning and an ellipsis.
+fn truncate(title: &str, width: usize) -> Cow<'_, str>
+{
+    if title.chars().count() <= width
+    {
+        return Cow::Borrowed(title);
+    }
+
+    title
+        .chars()
+        .take(width.saturating_sub(1))
+        .chain(iter::once(ELLIPSIS))
+        .collect()
+}
+
 /// Specialized functions for parsing document content to extract a table of
 /// contents.
 pub mod parsing
@@ -202,6 +398,15 @@ pub mod parsing
         Regex::new(&pattern).expect("Invalid TOC header regex")
     });
 
+    /// Section labels of `ToC` rows, numbers optionally followed by a
+    /// lowercase letter like `2a.`, or an uppercase letter followed by
+    /// numbers like `A.1`.
+    ///
+    /// A lone letter needs its dot, so prose like `A method` isn't taken for
+    /// a label.
+    const SECTION_LABEL: &str =
+        r"\d+[a-z]?(?:\.\d+[a-z]?)*\.?|[A-Z](?:\.\d+[a-z]?)+\.?|[A-Z]\.";
+
     /// Patterns for individual `ToC` rows.
     ///
     /// Capture groups are intentionally consistent across patterns:
@@ -216,8 +421,12 @@ pub mod parsing
             // Numbered entry, for example:
             // `1. Introduction..................5`
             // `2.1  Terminology`
-            Regex::new(r"^\s*(\d+(?:\.\d+)*\.?)\s+(.*?)(?:\.{2,}\s*\d+)?$")
-                .expect("Invalid TOC entry regex"),
+            // `2a.  Lettered Section`
+            // `A.1.  Appendix Subsection`
+            Regex::new(&format!(
+                r"^\s*({SECTION_LABEL})\s+(.*?)(?:\.{{2,}}\s*\d+)?$"
+            ))
+            .expect("Invalid TOC entry regex"),
             // Appendix entry, for example:
             // `Appendix A. Packet Format`
             Regex::new(r"^\s*(Appendix\s+[A-Z]\.?)\s+(.*?)(?:\.{2,}\s*\d+)?$")
@@ -225,12 +434,25 @@ pub mod parsing
         ]
     });
 
+    /// Matches the delimited page number ending a `ToC` row.
+    static PAGE_NUMBER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"\.{2,}\s*\d+$").expect("Invalid page number regex")
+    });
+
+    /// Matches the continuation of a wrapped `ToC` title, capturing the text
+    /// without the delimited page number.
+    static CONTINUATION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^\s+(\S.*?)(?:\.{2,}\s*\d+)?$")
+            .expect("Invalid continuation regex")
+    });
+
     /// Matches numbered section headings in the body.
     ///
     /// This is used as a stop signal while parsing `ToC` lines: once a body
     /// heading appears after valid `ToC` entries, the parser exits `ToC` mode.
     static SECTION_HEADING_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-        Regex::new(r"^(\d+(?:\.\d+)*\.)\s+\S")
+        // Unlike `ToC` rows, body headings end their labels with a dot
+        Regex::new(r"^((?:\d+[a-z]?|[A-Z])(?:\.\d+[a-z]?)*\.)\s+\S")
             .expect("Invalid section heading regex")
     });
 
@@ -303,16 +525,19 @@ pub mod parsing
         start_index: LineNumber,
     ) -> Vec<TocEntry>
     {
-        let mut entries = Vec::new();
+        let mut entries: Vec<TocEntry> = Vec::new();
         let mut consecutive_empty_lines = 0;
         let mut has_found_entries = false;
         let mut lines_without_entries = 0;
 
-        for (index, trimmed_line) in lines
+        let mut toc_lines = lines
             .clone()
             .enumerate()
             .skip(start_index)
             .map(|(i, line)| (i, line.trim_end()))
+            .peekable();
+
+        while let Some((index, trimmed_line)) = toc_lines.next()
         {
             // Check stopping conditions
             if should_stop_parsing(
@@ -325,12 +550,33 @@ pub mod parsing
                 break;
             }
 
+            let continuation = toc_lines.peek().and_then(|&(_, next_line)| {
+                find_continuation(trimmed_line, next_line)
+            });
+
             // Try to match and extract entries
-            if let Some(entry) =
-                try_extract_entry(trimmed_line, lines.clone(), index)
+            if let Some(mut entry) = try_extract_entry(
+                trimmed_line,
+                continuation,
+                lines.clone(),
+                index,
+            )
             {
                 has_found_entries = true;
+                // Keep the entries sorted by line. An entry left pointing at
+                // its `ToC` row shares the line of the entry before it.
+                if let Some(previous) = entries.last()
+                {
+                    entry.line_number =
+                        entry.line_number.max(previous.line_number);
+                }
                 entries.push(entry);
+
+                // The continuation is part of this entry
+                if continuation.is_some()
+                {
+                    toc_lines.next();
+                }
             }
         }
 
@@ -403,11 +649,56 @@ pub mod parsing
         false
     }
 
+    /// Finds the rest of a title wrapped onto the line after a `ToC` entry.
+    ///
+    /// The next line continues the entry if the entry has no page number
+    /// yet, and the next line is indented deeper without being an entry
+    /// itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `trimmed_line` - The trimmed line of the entry
+    /// * `next_line` - The trimmed line following the entry
+    ///
+    /// # Returns
+    ///
+    /// The continued part of the title, or `None` if the next line doesn't
+    /// continue the entry.
+    fn find_continuation<'line>(
+        trimmed_line: &str,
+        next_line: &'line str,
+    ) -> Option<&'line str>
+    {
+        /// Width of the indentation of a line.
+        fn indentation(line: &str) -> usize
+        {
+            line.len()
+                .saturating_sub(line.trim_start().len())
+        }
+
+        let is_continuable = !PAGE_NUMBER_REGEX.is_match(trimmed_line) &&
+            indentation(next_line) > indentation(trimmed_line) &&
+            !TOC_ENTRY_PATTERNS
+                .iter()
+                .any(|re| re.is_match(next_line));
+
+        if !is_continuable
+        {
+            return None;
+        }
+
+        CONTINUATION_REGEX
+            .captures(next_line)
+            .and_then(|caps| caps.get(1))
+            .map(|title| title.as_str().trim())
+    }
+
     /// Try to extract a `ToC` entry from a line.
     ///
     /// # Arguments
     ///
     /// * `trimmed_line` - The trimmed line to check
+    /// * `continuation` - The rest of the title wrapped onto the next line
     /// * `lines` - The lines of the document
     /// * `index` - The index of the line
     ///
@@ -417,6 +708,7 @@ pub mod parsing
     /// entry is found.
     fn try_extract_entry(
         trimmed_line: &str,
+        continuation: Option<&str>,
         lines: Lines<'_>,
         index: LineNumber,
     ) -> Option<TocEntry>
@@ -432,33 +724,30 @@ pub mod parsing
                     let section_num = caps[1].trim();
                     let title = caps[2].trim();
 
-                    // Find actual section in document
-                    let section_pattern = format!(
-                        r"^\s*{}\s+{}",
-                        regex::escape(section_num),
-                        regex::escape(title)
-                    );
+                    // Find actual section in document. Only the first line
+                    // of a wrapped title is looked for, as the heading may
+                    // wrap at another point. A row with a page number is a
+                    // genuine `ToC` row, so it's kept pointing at itself if
+                    // its heading can't be found, until the caller moves it
+                    // after the previous entry.
+                    let line_number =
+                        find_section_heading(lines, index, section_num, title)
+                            .or_else(|| {
+                                PAGE_NUMBER_REGEX
+                                    .is_match(trimmed_line)
+                                    .then_some(index)
+                            })?;
 
-                    if let Ok(section_regex) = Regex::new(&section_pattern)
+                    let title = match continuation
                     {
-                        // Look for the section in the document after the ToC
-                        #[expect(
-                            clippy::arithmetic_side_effects,
-                            reason = "LineNumber not expected to overflow"
-                        )]
-                        for (line_number, doc_line) in
-                            lines.enumerate().skip(index + 1)
-                        {
-                            if section_regex.is_match(doc_line)
-                            {
-                                return Some(TocEntry {
-                                    title: format!("{section_num} {title}")
-                                        .into(),
-                                    line_number,
-                                });
-                            }
-                        }
-                    }
+                        Some(rest) => format!("{section_num} {title} {rest}"),
+                        None => format!("{section_num} {title}"),
+                    };
+
+                    return Some(TocEntry {
+                        title: title.into(),
+                        line_number,
+                    });
                 }
                 break; // Stop checking patterns if one matched
             }
@@ -466,6 +755,75 @@ pub mod parsing
         None
     }
 
+    /// Finds the heading of a `ToC` entry in the document after the `ToC`.
+    ///
+    /// Looks for the exact heading first, then for one differing only in
+    /// spacing, case or trailing dots, and finally for an unindented heading
+    /// with the same section number.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The lines of the document
+    /// * `index` - The index of the `ToC` line of the entry
+    /// * `section_num` - The section label, for example `1.2`
+    /// * `title` - The first line of the section title
+    ///
+    /// # Returns
+    ///
+    /// The line number of the heading, or `None` if no heading is found.
+    fn find_section_heading(
+        lines: Lines<'_>,
+        index: LineNumber,
+        section_num: &str,
+        title: &str,
+    ) -> Option<LineNumber>
+    {
+        /// Splits text into lowercase words without their trailing dots.
+        fn normalize(text: &str) -> Vec<String>
+        {
+            text.split_whitespace()
+                .map(|word| word.trim_end_matches('.').to_lowercase())
+                .collect()
+        }
+
+        #[expect(
+            clippy::arithmetic_side_effects,
+            reason = "LineNumber not expected to overflow"
+        )]
+        let body = lines.enumerate().skip(index + 1);
+
+        let section_pattern = format!(
+            r"^\s*{}\s+{}",
+            regex::escape(section_num),
+            regex::escape(title)
+        );
+        if let Ok(section_regex) = Regex::new(&section_pattern) &&
+            let Some((line_number, _)) = body
+                .clone()
+                .find(|(_, doc_line)| section_regex.is_match(doc_line))
+        {
+            return Some(line_number);
+        }
+
+        let heading = normalize(&format!("{section_num} {title}"));
+        if let Some((line_number, _)) = body
+            .clone()
+            .find(|(_, doc_line)| normalize(doc_line).starts_with(&heading))
+        {
+            return Some(line_number);
+        }
+
+        let section_num = normalize(section_num);
+        body.filter(|(_, doc_line)| {
+            doc_line.starts_with(|ch: char| !ch.is_whitespace())
+        })
+        .find(|(_, doc_line)| {
+            let words = normalize(doc_line);
+            words.len() > section_num.len() && words.starts_with(&section_num)
+        })
+        .map(|(line_number, _)| line_number)
+    }
+
     /// Parses the document content heuristically to extract a table of
     /// contents.
     ///
@@ -540,3 +898,348 @@ pub mod parsing
             .unwrap_or_else(|| parse_toc_heuristic(content))
     }
 }
+
+#[cfg(test)]
+mod tests
+{
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+
+    #[test]
+    fn test_section_ranges()
+    {
+        let toc_panel = TocPanel {
+            entries: [(1, "1. Introduction"), (10, "2. Terms"), (25, "3. Ref")]
+                .into_iter()
+                .map(|(line_number, title)| TocEntry {
+                    title: title.into(),
+                    line_number,
+                })
+                .collect(),
+            state: ListState::default(),
+            is_collapsed: false,
+        };
+
+        let ranges: Vec<Range<LineNumber>> = toc_panel
+            .section_ranges(40)
+            .map(|(_, range)| range)
+            .collect();
+
+        assert_eq!(ranges, [1..10, 10..25, 25..40]);
+    }
+
+    /// An RFC whose `ToC` wraps long titles before the page number.
+    const WRAPPED_TOC_RFC: &str = "\
+Table of Contents
+
+   1.  Introduction ................................................    2
+   2.  Extensible Authentication Protocol (EAP) Method Requirements
+       for Wireless LANs ...........................................    3
+     2.1.  Mandatory Requirements of Methods Used in Wireless LAN
+           Environments ............................................    4
+   3.  Security Considerations .....................................    5
+   Authors' Addresses ..............................................    6
+
+1.  Introduction
+
+   Text.
+
+2.  Extensible Authentication Protocol (EAP) Method Requirements for
+    Wireless LANs
+
+2.1.  Mandatory Requirements of Methods Used in Wireless LAN Environments
+
+3.  Security Considerations
+";
+
+    #[test]
+    fn test_wrapped_toc_titles_are_joined()
+    {
+        let entries = parsing::parse_toc(WRAPPED_TOC_RFC);
+        let entries: Vec<(&str, LineNumber)> = entries
+            .iter()
+            .map(|entry| (entry.title.as_ref(), entry.line_number))
+            .collect();
+
+        assert_eq!(
+            entries,
+            [
+                ("1. Introduction", 10),
+                (
+                    "2. Extensible Authentication Protocol (EAP) Method \
+                     Requirements for Wireless LANs",
+                    14,
+                ),
+                (
+                    "2.1. Mandatory Requirements of Methods Used in Wireless \
+                     LAN Environments",
+                    17,
+                ),
+                ("3. Security Considerations", 19),
+            ]
+        );
+    }
+
+    /// A draft numbering its sections with letters.
+    const LETTERED_TOC_RFC: &str = "\
+Table of Contents
+
+   1.  Introduction ................................................    2
+   2a. Lettered Section ............................................    3
+   2b. Another Lettered Section ....................................    3
+   A.  Examples ....................................................    4
+     A.1.  First Example ...........................................    4
+     A.2   Second Example ..........................................    5
+
+   A method of this draft is described below.
+
+1.  Introduction
+
+2a. Lettered Section
+
+2b. Another Lettered Section
+
+A.  Examples
+
+A.1.  First Example
+
+A.2   Second Example
+";
+
+    #[test]
+    fn test_lettered_sections_are_parsed()
+    {
+        let entries = parsing::parse_toc(LETTERED_TOC_RFC);
+        let entries: Vec<(&str, LineNumber)> = entries
+            .iter()
+            .map(|entry| (entry.title.as_ref(), entry.line_number))
+            .collect();
+
+        // The prose line starting with `A` isn't an entry
+        assert_eq!(
+            entries,
+            [
+                ("1. Introduction", 11),
+                ("2a. Lettered Section", 13),
+                ("2b. Another Lettered Section", 15),
+                ("A. Examples", 17),
+                ("A.1. First Example", 19),
+                ("A.2 Second Example", 21),
+            ]
+        );
+    }
+
+    /// An RFC whose body headings differ from its `ToC` rows.
+    const MISMATCHED_TOC_RFC: &str = "\
+Table of Contents
+
+   1.  Protocol   Overview .........................................    2
+   2.  IANA Considerations .........................................    3
+   3.  Acknowledgments .............................................    4
+   4.  Normative References ........................................    5
+
+1. Protocol Overview
+
+2.  IANA considerations
+
+3.  Acknowledgements
+";
+
+    #[test]
+    fn test_mismatched_headings_are_kept()
+    {
+        let entries = parsing::parse_toc(MISMATCHED_TOC_RFC);
+        let entries: Vec<(&str, LineNumber)> = entries
+            .iter()
+            .map(|entry| (entry.title.as_ref(), entry.line_number))
+            .collect();
+
+        assert_eq!(
+            entries,
+            [
+                // Differs in spacing
+                ("1. Protocol   Overview", 7),
+                // Differs in case
+                ("2. IANA Considerations", 9),
+                // Differs in the title, found by the section number
+                ("3. Acknowledgments", 11),
+                // Missing from the body, kept at the previous entry's line
+                ("4. Normative References", 11),
+            ]
+        );
+    }
+
+    /// Renders the panel to a 30 by 20 buffer, collecting the text of the
+    /// rows.
+    fn render_rows(
+        toc_panel: &mut TocPanel,
+        overflow: TocTitleOverflow,
+    ) -> anyhow::Result<Vec<String>>
+    {
+        let mut terminal = Terminal::new(TestBackend::new(30, 20))?;
+        terminal.draw(|frame| {
+            toc_panel.render(
+                frame,
+                frame.area(),
+                &Theme::DARK,
+                TocPosition::Left,
+                overflow,
+            );
+        })?;
+        let buffer = terminal.backend().buffer();
+
+        Ok((0..buffer.area.height)
+            .map(|row| {
+                (0..buffer.area.width)
+                    .map(|column| buffer[(column, row)].symbol())
+                    .collect::<String>()
+                    // Leave out the border
+                    .trim_end_matches('│')
+                    .trim_end()
+                    .to_owned()
+            })
+            .collect())
+    }
+
+    #[test]
+    fn test_long_toc_scrolls_selection_into_view() -> anyhow::Result<()>
+    {
+        let mut toc_panel = TocPanel {
+            entries: (1..=200)
+                .map(|section| TocEntry {
+                    title: format!("{section}. Section").into(),
+                    line_number: section,
+                })
+                .collect(),
+            state: ListState::default().with_selected(Some(0)),
+            is_collapsed: false,
+        };
+        for _ in 0..150
+        {
+            toc_panel.next();
+        }
+        let rows = render_rows(&mut toc_panel, TocTitleOverflow::Wrap)?;
+        assert!(rows[0].contains("151/200"));
+        assert!(
+            rows.iter()
+                .any(|row| row.contains("> 151. Section"))
+        );
+
+        // The selection stops at the last entry
+        for _ in 0..100
+        {
+            toc_panel.next();
+        }
+        assert_eq!(toc_panel.selected_line(), Some(200));
+        let rows = render_rows(&mut toc_panel, TocTitleOverflow::Wrap)?;
+        assert!(rows[0].contains("200/200"));
+        assert!(
+            rows.iter()
+                .any(|row| row.contains("> 200. Section"))
+        );
+
+        for _ in 0..199
+        {
+            toc_panel.previous();
+        }
+        let rows = render_rows(&mut toc_panel, TocTitleOverflow::Wrap)?;
+        assert!(rows[0].contains("1/200"));
+        assert!(rows[1].contains("> 1. Section"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_toc_title_overflow() -> anyhow::Result<()>
+    {
+        let mut toc_panel = TocPanel {
+            entries: vec![
+                TocEntry {
+                    title: "1. A rather long section title that overflows"
+                        .into(),
+                    line_number: 1,
+                },
+                TocEntry {
+                    title: "2. Short".into(),
+                    line_number: 9,
+                },
+            ],
+            state: ListState::default().with_selected(Some(0)),
+            is_collapsed: false,
+        };
+
+        let rows = render_rows(&mut toc_panel, TocTitleOverflow::Wrap)?;
+        assert_eq!(rows[1], "> 1. A rather long section");
+        assert_eq!(rows[2], "  title that overflows");
+        assert_eq!(rows[3], "  2. Short");
+
+        let rows = render_rows(&mut toc_panel, TocTitleOverflow::Truncate)?;
+        assert_eq!(rows[1], "> 1. A rather long section …");
+        assert_eq!(rows[2], "  2. Short");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_collapse_and_expand_all() -> anyhow::Result<()>
+    {
+        let mut toc_panel = TocPanel {
+            entries: [
+                (1, "1. Introduction"),
+                (5, "2. Protocol"),
+                (9, "2.1. Messages"),
+                (14, "2.1.1. Header"),
+                (20, "3. Security Considerations"),
+                (30, "Appendix A. Examples"),
+                (32, "A.1. First Example"),
+            ]
+            .into_iter()
+            .map(|(line_number, title)| TocEntry {
+                title: title.into(),
+                line_number,
+            })
+            .collect(),
+            state: ListState::default().with_selected(Some(3)),
+            is_collapsed: false,
+        };
+        assert_eq!(toc_panel.selected_line(), Some(14));
+
+        // The selection moves up to its top level section
+        toc_panel.collapse_all();
+        assert!(toc_panel.is_collapsed());
+        assert_eq!(toc_panel.selected_line(), Some(5));
+        let rows = render_rows(&mut toc_panel, TocTitleOverflow::Truncate)?;
+        let rows: Vec<&str> = rows
+            .iter()
+            .map(String::as_str)
+            .filter(|row| !row.is_empty())
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                "Contents",
+                "  1. Introduction",
+                "> 2. Protocol",
+                "  3. Security Considerations",
+                "  Appendix A. Examples",
+            ]
+        );
+
+        // Navigation skips the collapsed entries
+        toc_panel.next();
+        assert_eq!(toc_panel.selected_line(), Some(20));
+        toc_panel.next();
+        toc_panel.next();
+        assert_eq!(toc_panel.selected_line(), Some(30));
+
+        toc_panel.expand_all();
+        assert!(!toc_panel.is_collapsed());
+        assert_eq!(toc_panel.selected_line(), Some(30));
+        toc_panel.next();
+        assert_eq!(toc_panel.selected_line(), Some(32));
+
+        Ok(())
+    }
+}